@@ -0,0 +1,54 @@
+/*
+ * C interface to the bignumber crate. Build the shared library with
+ *
+ *     cargo rustc --release --lib --features ffi --crate-type cdylib
+ *
+ * (a cdylib is not in the default crate types, as it would need std even when
+ * the `std` feature is off).
+ *
+ * Every handle and string returned here is owned by the caller and must be
+ * released with bignumber_free or bignumber_string_free. Functions returning
+ * int return one of the BIGNUMBER_* status codes and leave their out pointer
+ * untouched unless the status is BIGNUMBER_OK.
+ */
+
+#ifndef BIGNUMBER_H
+#define BIGNUMBER_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define BIGNUMBER_OK 0
+#define BIGNUMBER_NULL_POINTER 1
+#define BIGNUMBER_PARSE_ERROR 2
+#define BIGNUMBER_DIVISION_BY_ZERO 3
+#define BIGNUMBER_INVALID_RADIX 4
+#define BIGNUMBER_INTERNAL_ERROR 5
+
+typedef struct bignumber_t bignumber_t;
+
+bignumber_t *bignumber_from_i64(int64_t value);
+bignumber_t *bignumber_clone(const bignumber_t *n);
+void bignumber_free(bignumber_t *n);
+
+int bignumber_parse(const char *input, uint32_t radix, bignumber_t **out);
+int bignumber_to_string(const bignumber_t *n, uint32_t radix, char **out);
+void bignumber_string_free(char *s);
+
+int bignumber_add(const bignumber_t *a, const bignumber_t *b, bignumber_t **out);
+int bignumber_sub(const bignumber_t *a, const bignumber_t *b, bignumber_t **out);
+int bignumber_mul(const bignumber_t *a, const bignumber_t *b, bignumber_t **out);
+/* Quotient rounded towards zero and remainder with the sign of a, as in C */
+int bignumber_div(const bignumber_t *a, const bignumber_t *b, bignumber_t **out);
+int bignumber_rem(const bignumber_t *a, const bignumber_t *b, bignumber_t **out);
+/* Writes -1, 0 or 1 to out */
+int bignumber_cmp(const bignumber_t *a, const bignumber_t *b, int *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
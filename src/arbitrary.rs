@@ -0,0 +1,116 @@
+//! Random value generation for property tests, behind the `proptest` and
+//! `quickcheck` features.
+//!
+//! Half of the generated values are special cases where limb level code tends
+//! to go wrong: zero, one, powers of the limb base and the numbers just below
+//! them, and powers of the decimal chunk base used for formatting. The rest
+//! have random limbs, with the number of limbs varying as well.
+
+use crate::{BigNumber, BigUint, Sign};
+
+// Most limbs in a random value
+const MAX_LIMBS: usize = 8;
+// Number of kinds of special case
+const SPECIAL_KINDS: u8 = 5;
+// The largest power of ten that fits in a limb
+const DECIMAL_BASE: u32 = 1_000_000_000;
+
+// A special case of the given kind and size
+fn special(kind: u8, limbs: usize) -> BigUint {
+    let base_power = &BigUint::one() << (32 * limbs);
+    match kind % SPECIAL_KINDS {
+        0 => BigUint::zero(),
+        1 => BigUint::one(),
+        2 => base_power,
+        3 => &base_power - &BigUint::one(),
+        _ => BigUint::from(DECIMAL_BASE).pow(limbs as u64),
+    }
+}
+
+fn signed(negative: bool, magnitude: BigUint) -> BigNumber {
+    BigNumber::from_biguint(if negative { Sign::Negative } else { Sign::Positive }, magnitude)
+}
+
+#[cfg(feature = "proptest")]
+mod proptest_impls {
+    use proptest::prelude::*;
+
+    use super::{signed, special, MAX_LIMBS, SPECIAL_KINDS};
+    use crate::{BigNumber, BigUint};
+
+    fn magnitude() -> BoxedStrategy<BigUint> {
+        prop_oneof![
+            (0..SPECIAL_KINDS, 1..=MAX_LIMBS).prop_map(|(kind, limbs)| special(kind, limbs)),
+            proptest::collection::vec(any::<u32>(), 1..=MAX_LIMBS).prop_map(BigUint::from_limbs),
+        ]
+        .boxed()
+    }
+
+    impl Arbitrary for BigUint {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<BigUint>;
+
+        fn arbitrary_with(_: ()) -> BoxedStrategy<BigUint> {
+            magnitude()
+        }
+    }
+
+    impl Arbitrary for BigNumber {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<BigNumber>;
+
+        fn arbitrary_with(_: ()) -> BoxedStrategy<BigNumber> {
+            (any::<bool>(), magnitude())
+                .prop_map(|(negative, magnitude)| signed(negative, magnitude))
+                .boxed()
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls {
+    use quickcheck::{Arbitrary, Gen};
+
+    use super::{signed, special, MAX_LIMBS, SPECIAL_KINDS};
+    use crate::{BigNumber, BigUint};
+
+    impl Arbitrary for BigUint {
+        fn arbitrary(g: &mut Gen) -> BigUint {
+            let limbs = 1 + usize::arbitrary(g) % MAX_LIMBS;
+            if bool::arbitrary(g) {
+                special(u8::arbitrary(g) % SPECIAL_KINDS, limbs)
+            } else {
+                BigUint::from_limbs((0..limbs).map(|_| u32::arbitrary(g)).collect())
+            }
+        }
+
+        // Halves towards zero, then steps down by one
+        fn shrink(&self) -> Box<dyn Iterator<Item = BigUint>> {
+            if self.is_zero() {
+                return quickcheck::empty_shrinker();
+            }
+            let mut candidates = vec![BigUint::zero()];
+            let mut half = self >> 1;
+            while !half.is_zero() {
+                candidates.push(half.clone());
+                half >>= 1;
+            }
+            candidates.push(self - &BigUint::one());
+            Box::new(candidates.into_iter())
+        }
+    }
+
+    impl Arbitrary for BigNumber {
+        fn arbitrary(g: &mut Gen) -> BigNumber {
+            signed(bool::arbitrary(g), BigUint::arbitrary(g))
+        }
+
+        // Shrinks the magnitude, trying the positive value first
+        fn shrink(&self) -> Box<dyn Iterator<Item = BigNumber>> {
+            let negative = self.is_negative();
+            let flipped = negative.then(|| BigNumber::from(self.magnitude.clone()));
+            let smaller = self.magnitude.shrink().map(move |magnitude| signed(negative, magnitude));
+            Box::new(flipped.into_iter().chain(smaller))
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! `rkyv` support, behind the `rkyv` feature.
+//!
+//! [`ArchivedBigUint`] stores the limbs in place and [`ArchivedBigNumber`] adds
+//! the sign, so archived values can be inspected and compared straight from a
+//! memory-mapped buffer. Deserializing normalizes the limbs, so a buffer that
+//! passed validation but was not written by this crate still yields canonical
+//! values.
+
+use rkyv::rancor::Fallible;
+use rkyv::Deserialize;
+
+use crate::{ArchivedBigNumber, ArchivedBigUint, ArchivedSign, BigNumber, BigUint, Sign};
+
+impl ArchivedBigUint {
+    /// Returns `true` if the archived number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|&limb| limb == 0)
+    }
+}
+
+impl ArchivedBigNumber {
+    /// Returns the sign of the archived number.
+    pub fn sign(&self) -> Sign {
+        match self.sign {
+            ArchivedSign::Positive => Sign::Positive,
+            ArchivedSign::Negative => Sign::Negative,
+        }
+    }
+
+    /// Returns the archived magnitude.
+    pub fn magnitude(&self) -> &ArchivedBigUint {
+        &self.magnitude
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<BigUint, D> for ArchivedBigUint {
+    fn deserialize(&self, _: &mut D) -> Result<BigUint, D::Error> {
+        Ok(BigUint::from_limbs(self.limbs.iter().map(|limb| limb.to_native()).collect()))
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<BigNumber, D> for ArchivedBigNumber {
+    fn deserialize(&self, deserializer: &mut D) -> Result<BigNumber, D::Error> {
+        Ok(BigNumber::from_biguint(self.sign(), self.magnitude.deserialize(deserializer)?))
+    }
+}
+
+// Compares limb by limb, ignoring zero limbs at the top of the archive
+impl PartialEq<BigUint> for ArchivedBigUint {
+    fn eq(&self, other: &BigUint) -> bool {
+        let len = self.limbs.iter().rposition(|&limb| limb != 0).map_or(0, |top| top + 1);
+        let limbs = if other.is_zero() { &[][..] } else { &other.limbs[..] };
+        len == limbs.len() && self.limbs.iter().zip(limbs).all(|(archived, &limb)| *archived == limb)
+    }
+}
+
+impl PartialEq<BigNumber> for ArchivedBigNumber {
+    fn eq(&self, other: &BigNumber) -> bool {
+        self.magnitude == other.magnitude && (self.sign() == other.sign || other.is_zero())
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::vec;
+
+use crate::{BigNumber, BigUint};
+
+// Moduli with more limbs than this get their reciprocal by Newton's method
+// rather than a long division
+const NEWTON_THRESHOLD: usize = 64;
+
+/// Precomputed parameters for Barrett reduction modulo a fixed number.
+///
+/// The context stores `floor(B^2k / m)` for a `k` limb modulus, which turns
+/// reducing any value below `B^2k` (in particular any product of two residues)
+/// into two multiplications and a couple of subtractions. Unlike
+/// [`MontgomeryContext`](crate::MontgomeryContext) it accepts even moduli and
+/// works on ordinary residues.
+///
+/// ```
+/// use bignumber::{BarrettContext, BigUint};
+///
+/// let ctx = BarrettContext::new(&"1000000".parse().unwrap()).unwrap();
+/// let x: BigUint = "123456789123".parse().unwrap();
+/// assert_eq!(ctx.reduce(&x).to_string(), "789123");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BarrettContext {
+    modulus: BigUint,
+    // floor(B^2k / m)
+    reciprocal: BigUint,
+}
+
+impl BarrettContext {
+    /// Creates a context for `modulus`, or `None` if it is zero.
+    pub fn new(modulus: &BigUint) -> Option<BarrettContext> {
+        if modulus.is_zero() {
+            return None;
+        }
+
+        Some(BarrettContext {
+            modulus: modulus.clone(),
+            reciprocal: reciprocal(modulus),
+        })
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Returns `x mod m`.
+    ///
+    /// Values of up to twice the modulus length take the fast path; longer ones
+    /// fall back to a full division.
+    pub fn reduce(&self, x: &BigUint) -> BigUint {
+        let k = self.modulus.limbs.len();
+        if *x < self.modulus {
+            return x.clone();
+        }
+        if x.limbs.len() > 2 * k {
+            return x % &self.modulus;
+        }
+
+        self.div_rem(x).1
+    }
+
+    // Quotient and remainder of x / m for x < B^2k, using two multiplications
+    // instead of a long division
+    pub(crate) fn div_rem(&self, x: &BigUint) -> (BigUint, BigUint) {
+        let k = self.modulus.limbs.len();
+        debug_assert!(x.limbs.len() <= 2 * k);
+        if *x < self.modulus {
+            return (BigUint::zero(), x.clone());
+        }
+
+        // The estimate floor(floor(x / B^(k-1)) * mu / B^(k+1)) is at most two
+        // below the true quotient
+        let high = BigUint::from_limbs(x.limbs[k - 1..].to_vec());
+        let product = &high * &self.reciprocal;
+        let mut quotient = BigUint::from_limbs(product.limbs.get(k + 1..).unwrap_or(&[]).to_vec());
+
+        let mut remainder = x - &(&quotient * &self.modulus);
+        while remainder >= self.modulus {
+            remainder.sub_assign_unchecked(&self.modulus);
+            quotient.add_small(1);
+        }
+        (quotient, remainder)
+    }
+
+    /// Returns `a * b mod m`.
+    pub fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.reduce(&(a * b))
+    }
+
+    /// Returns `base^exponent mod m`.
+    pub fn pow(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        let base = self.reduce(base);
+        let mut result = self.reduce(&BigUint::one());
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, &base);
+            }
+        }
+        result
+    }
+}
+
+// floor(B^2k / m) for a k limb m. Large moduli start from the reciprocal of
+// their top half and take one Newton step, x + x (B^2k - m x) / B^2k, which
+// roughly doubles the number of correct limbs; a few corrections then make it
+// exact.
+fn reciprocal(m: &BigUint) -> BigUint {
+    let k = m.limbs.len();
+    let mut power = vec![0u32; 2 * k];
+    power.push(1);
+    let power = BigUint::from_limbs(power);
+    if k <= NEWTON_THRESHOLD {
+        return &power / m;
+    }
+
+    // Two limbs more than half keep the error of the step below one
+    let h = k / 2 + 2;
+    let shift = 32 * (k - h);
+    let top = BigUint::from_limbs(m.limbs[k - h..].to_vec());
+    let x = BigNumber::from(&reciprocal(&top) << shift);
+
+    let power = BigNumber::from(power);
+    let m = BigNumber::from(m.clone());
+    let error = &power - &(&m * &x);
+    let mut x = &x + &(&(&x * &error) >> (64 * k));
+
+    let mut remainder = &power - &(&m * &x);
+    while remainder.is_negative() {
+        x -= BigNumber::one();
+        remainder += &m;
+    }
+    while remainder >= m {
+        x += BigNumber::one();
+        remainder -= &m;
+    }
+    x.magnitude().clone()
+}
@@ -0,0 +1,239 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::mul;
+
+/// An arbitrary precision unsigned integer.
+///
+/// This is the magnitude type behind [`BigNumber`](crate::BigNumber) and holds
+/// all of the limb level algorithms. The value is stored in base `2^32`, least
+/// significant limb first, without leading zero limbs; zero is a single `0` limb.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize), rkyv(derive(Debug)))]
+pub struct BigUint {
+    pub(crate) limbs: Vec<u32>,
+}
+
+// Bits per limb
+pub(crate) const LIMB_BITS: u32 = 32;
+
+impl BigUint {
+    /// Returns the number zero.
+    pub fn zero() -> BigUint {
+        BigUint { limbs: vec![0] }
+    }
+
+    /// Returns the number one.
+    pub fn one() -> BigUint {
+        BigUint { limbs: vec![1] }
+    }
+
+    /// Returns `true` if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs == [0]
+    }
+
+    // Returns `true` if the lowest bit is clear
+    pub(crate) fn is_even(&self) -> bool {
+        self.limbs[0] & 1 == 0
+    }
+
+    // Builds a number from raw limbs, bringing it into canonical form
+    pub(crate) fn from_limbs(limbs: Vec<u32>) -> BigUint {
+        let mut number = BigUint { limbs };
+        number.normalize();
+        number
+    }
+
+    // Strips leading zero limbs, leaving a single `0` limb for zero
+    pub(crate) fn normalize(&mut self) {
+        while self.limbs.len() > 1 && self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+
+        if self.limbs.is_empty() {
+            self.limbs.push(0);
+        }
+    }
+
+    /// Returns `self - other`, or `None` if the result would be negative.
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+
+        let mut result = self.clone();
+        result.sub_assign_unchecked(other);
+        Some(result)
+    }
+
+    // Returns `10^n`, by repeated squaring so that large powers take a few
+    // fast multiplications rather than n / 9 passes over a growing number
+    pub(crate) fn pow10(n: usize) -> BigUint {
+        BigUint::from(10u32).pow(n as u64)
+    }
+
+    // Multiplies the number by `10^n`
+    pub(crate) fn shift_left_digits(&mut self, n: usize) {
+        *self = self.mul_magnitude(&BigUint::pow10(n));
+    }
+
+    // Divides the number by `10^n`, discarding the dropped digits
+    pub(crate) fn shift_right_digits(&mut self, n: usize) {
+        *self = self.div_rem(&BigUint::pow10(n)).0;
+    }
+
+    pub(crate) fn add_assign_magnitude(&mut self, other: &BigUint) {
+        let mut carry = 0u64;
+        let max_len = self.limbs.len().max(other.limbs.len());
+
+        // Extend the length of self.limbs if necessary
+        self.limbs.resize(max_len, 0);
+
+        for i in 0..max_len {
+            let other_limb = other.limbs.get(i).copied().unwrap_or(0);
+
+            let sum = self.limbs[i] as u64 + other_limb as u64 + carry;
+            self.limbs[i] = sum as u32;
+            carry = sum >> LIMB_BITS;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    // Subtracts `other` in place; the caller guarantees `self >= other`
+    pub(crate) fn sub_assign_unchecked(&mut self, other: &BigUint) {
+        let mut borrow = false;
+        for i in 0..self.limbs.len() {
+            let other_limb = other.limbs.get(i).copied().unwrap_or(0);
+            let (diff, borrow1) = self.limbs[i].overflowing_sub(other_limb);
+            let (diff, borrow2) = diff.overflowing_sub(borrow as u32);
+            self.limbs[i] = diff;
+            borrow = borrow1 || borrow2;
+        }
+        self.normalize();
+    }
+
+    // Adds a single limb in place
+    pub(crate) fn add_small(&mut self, value: u32) {
+        let mut carry = value;
+        for limb in &mut self.limbs {
+            if carry == 0 {
+                return;
+            }
+            let (sum, overflow) = limb.overflowing_add(carry);
+            *limb = sum;
+            carry = overflow as u32;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    // Multiplies the number by a single limb in place
+    pub(crate) fn mul_small(&mut self, factor: u32) {
+        let mut carry = 0u64;
+
+        for limb in &mut self.limbs {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> LIMB_BITS;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+
+        self.normalize();
+    }
+
+    // Divides the number by a single non-zero limb in place, returning the remainder
+    pub(crate) fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (remainder << LIMB_BITS) | *limb as u64;
+            *limb = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+
+        self.normalize();
+        remainder as u32
+    }
+
+    // Returns the remainder of dividing by a single non-zero limb
+    pub(crate) fn rem_small(&self, divisor: u32) -> u32 {
+        self.limbs
+            .iter()
+            .rev()
+            .fold(0u64, |remainder, &limb| ((remainder << LIMB_BITS) | limb as u64) % divisor as u64)
+            as u32
+    }
+
+    pub(crate) fn mul_magnitude(&self, other: &BigUint) -> BigUint {
+        BigUint::from_limbs(mul::mul_limbs(&self.limbs, &other.limbs))
+    }
+
+    /// Returns the number of significant binary digits; zero has none.
+    pub fn bits(&self) -> u64 {
+        let top = *self.limbs.last().unwrap();
+        (self.limbs.len() as u64 - 1) * LIMB_BITS as u64 + (LIMB_BITS - top.leading_zeros()) as u64
+    }
+
+    /// Returns the number of trailing zero bits, the exponent of the largest
+    /// power of two dividing the number; zero is treated as having none.
+    pub fn trailing_zeros(&self) -> u64 {
+        match self.limbs.iter().position(|&limb| limb != 0) {
+            Some(i) => i as u64 * LIMB_BITS as u64 + self.limbs[i].trailing_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Returns the number of one bits.
+    pub fn count_ones(&self) -> u64 {
+        self.limbs.iter().map(|limb| limb.count_ones() as u64).sum()
+    }
+
+    // Multiplies the number by `2^n` in place
+    pub(crate) fn shl_bits(&mut self, n: u64) {
+        let limb_shift = (n / LIMB_BITS as u64) as usize;
+        let bit_shift = (n % LIMB_BITS as u64) as u32;
+
+        if bit_shift > 0 {
+            let mut carry = 0;
+            for limb in &mut self.limbs {
+                let next = *limb >> (LIMB_BITS - bit_shift);
+                *limb = (*limb << bit_shift) | carry;
+                carry = next;
+            }
+            if carry > 0 {
+                self.limbs.push(carry);
+            }
+        }
+
+        self.limbs.splice(0..0, core::iter::repeat_n(0, limb_shift));
+        self.normalize();
+    }
+
+    // Divides the number by `2^n` in place, discarding the shifted out bits
+    pub(crate) fn shr_bits(&mut self, n: u64) {
+        let limb_shift = (n / LIMB_BITS as u64) as usize;
+        let bit_shift = (n % LIMB_BITS as u64) as u32;
+
+        self.limbs.drain(..limb_shift.min(self.limbs.len()));
+
+        if bit_shift > 0 {
+            let mut carry = 0;
+            for limb in self.limbs.iter_mut().rev() {
+                let next = *limb << (LIMB_BITS - bit_shift);
+                *limb = (*limb >> bit_shift) | carry;
+                carry = next;
+            }
+        }
+
+        self.normalize();
+    }
+}
@@ -0,0 +1,178 @@
+//! Bitwise operations.
+//!
+//! [`BigUint`] values are plain bit strings. [`BigNumber`] values behave like
+//! two's complement integers of unbounded width, the same convention as Rust's
+//! primitive signed integers and Python's `int`: a negative number has
+//! infinitely many leading one bits, so `-1` is all ones and `x & -1 == x`.
+
+use alloc::vec::Vec;
+
+use crate::biguint::LIMB_BITS;
+use crate::{BigNumber, BigUint, Sign};
+
+impl BigUint {
+    // Applies `op` limb by limb, treating missing limbs as zero
+    pub(crate) fn bitwise(&self, other: &BigUint, op: fn(u32, u32) -> u32) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let limb = |limbs: &[u32], i: usize| limbs.get(i).copied().unwrap_or(0);
+        BigUint::from_limbs((0..len).map(|i| op(limb(&self.limbs, i), limb(&other.limbs, i))).collect())
+    }
+
+    /// Returns bit `index` of the number, counting from the least significant.
+    pub fn bit(&self, index: u64) -> bool {
+        let (limb, bit) = locate(index);
+        self.limbs.get(limb).is_some_and(|&l| (l >> bit) & 1 == 1)
+    }
+
+    /// Sets bit `index` to one.
+    pub fn set_bit(&mut self, index: u64) {
+        self.update_bit(index, |limb, mask| limb | mask);
+    }
+
+    /// Sets bit `index` to zero.
+    pub fn clear_bit(&mut self, index: u64) {
+        self.update_bit(index, |limb, mask| limb & !mask);
+    }
+
+    /// Flips bit `index`.
+    pub fn toggle_bit(&mut self, index: u64) {
+        self.update_bit(index, |limb, mask| limb ^ mask);
+    }
+
+    fn update_bit(&mut self, index: u64, op: fn(u32, u32) -> u32) {
+        let (limb, bit) = locate(index);
+        if limb >= self.limbs.len() {
+            self.limbs.resize(limb + 1, 0);
+        }
+        self.limbs[limb] = op(self.limbs[limb], 1 << bit);
+        self.normalize();
+    }
+
+    /// Flips the lowest `width` bits of the number, leaving higher bits alone.
+    ///
+    /// For a number below `2^width` this is `2^width - 1 - self`.
+    #[must_use]
+    pub fn not_bits(&self, width: u64) -> BigUint {
+        self.bitwise(&ones(width), |a, b| a ^ b)
+    }
+}
+
+impl BigNumber {
+    // Applies `op` to the two's complement representations. The result's sign
+    // is `op` applied to the sign bits, which extend past the longer operand.
+    pub(crate) fn bitwise(&self, other: &BigNumber, op: fn(u32, u32) -> u32) -> BigNumber {
+        // One extra limb leaves room for the sign of the result
+        let len = self.magnitude.limbs.len().max(other.magnitude.limbs.len()) + 1;
+        let a = twos_complement(&self.magnitude.limbs, self.is_negative(), len);
+        let b = twos_complement(&other.magnitude.limbs, other.is_negative(), len);
+        let limbs: Vec<u32> = a.iter().zip(&b).map(|(&x, &y)| op(x, y)).collect();
+
+        let negative = limbs[len - 1] >> 31 == 1;
+        let magnitude = twos_complement(&limbs, negative, len);
+        let sign = if negative { Sign::Negative } else { Sign::Positive };
+        BigNumber::from_biguint(sign, BigUint::from_limbs(magnitude))
+    }
+
+    /// Returns bit `index` of the magnitude, counting from the least
+    /// significant.
+    ///
+    /// The bit accessors work on the magnitude and leave the sign alone, unlike
+    /// the bitwise operators.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let mut n = BigNumber::from_string("-5");
+    /// assert!(n.bit(2) && !n.bit(1));
+    /// n.set_bit(100);
+    /// n.clear_bit(0);
+    /// assert_eq!(n.to_string(), "-1267650600228229401496703205380");
+    /// ```
+    pub fn bit(&self, index: u64) -> bool {
+        self.magnitude.bit(index)
+    }
+
+    /// Sets bit `index` of the magnitude to one.
+    pub fn set_bit(&mut self, index: u64) {
+        self.magnitude.set_bit(index);
+    }
+
+    /// Sets bit `index` of the magnitude to zero.
+    pub fn clear_bit(&mut self, index: u64) {
+        self.magnitude.clear_bit(index);
+        self.normalize();
+    }
+
+    /// Flips bit `index` of the magnitude.
+    pub fn toggle_bit(&mut self, index: u64) {
+        self.magnitude.toggle_bit(index);
+        self.normalize();
+    }
+
+    /// Returns the number of one bits in the magnitude.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("-255").count_ones(), 8);
+    /// ```
+    pub fn count_ones(&self) -> u64 {
+        self.magnitude.count_ones()
+    }
+
+    /// Returns the number of trailing zero bits in the magnitude, the exponent
+    /// of the largest power of two dividing the number; zero is treated as
+    /// having none.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("-96").trailing_zeros(), 5);
+    /// ```
+    pub fn trailing_zeros(&self) -> u64 {
+        self.magnitude.trailing_zeros()
+    }
+
+    /// Flips the lowest `width` bits of the two's complement representation,
+    /// which is `self ^ (2^width - 1)`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("5").not_bits(8).to_string(), "250");
+    /// assert_eq!(BigNumber::from_string("-1").not_bits(8).to_string(), "-256");
+    /// ```
+    #[must_use]
+    pub fn not_bits(&self, width: u64) -> BigNumber {
+        self.bitwise(&BigNumber::from(ones(width)), |a, b| a ^ b)
+    }
+}
+
+// The limb holding bit `index`, and the position of the bit within it
+fn locate(index: u64) -> (usize, u32) {
+    ((index / LIMB_BITS as u64) as usize, (index % LIMB_BITS as u64) as u32)
+}
+
+// 2^width - 1
+fn ones(width: u64) -> BigUint {
+    let mut mask = BigUint::one();
+    mask.shl_bits(width);
+    mask.sub_assign_unchecked(&BigUint::one());
+    mask
+}
+
+// The lowest `len` limbs of the two's complement of `limbs`, negated when
+// `negative` is set. Negating twice gives the original limbs back, so this
+// also recovers a magnitude from a negative two's complement value.
+fn twos_complement(limbs: &[u32], negative: bool, len: usize) -> Vec<u32> {
+    let mut result: Vec<u32> = (0..len).map(|i| limbs.get(i).copied().unwrap_or(0)).collect();
+    if negative {
+        let mut carry = true;
+        for limb in &mut result {
+            let (sum, overflow) = (!*limb).overflowing_add(carry as u32);
+            *limb = sum;
+            carry = overflow;
+        }
+    }
+    result
+}
@@ -0,0 +1,247 @@
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::{BigNumber, BigUint, Sign};
+
+// Version byte of the current `to_bin` layout
+const BIN_VERSION: u8 = 1;
+// Version, sign and limb count
+const BIN_HEADER_LEN: usize = 6;
+
+/// The error returned by [`BigNumber::from_bin`] for malformed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromBinError {
+    /// The input ended before the header or the limbs it announces.
+    Truncated,
+    /// The input continued past the announced limbs.
+    TrailingBytes,
+    /// The version byte is not one this version of the crate can read.
+    UnsupportedVersion(u8),
+    /// The sign byte is neither `0` nor `1`.
+    InvalidSign(u8),
+    /// The encoding is valid but not the one `to_bin` produces: the most
+    /// significant limb is zero, or zero is marked negative.
+    NonCanonical,
+}
+
+impl fmt::Display for FromBinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromBinError::Truncated => f.write_str("binary encoding is truncated"),
+            FromBinError::TrailingBytes => f.write_str("trailing bytes after binary encoding"),
+            FromBinError::UnsupportedVersion(version) => {
+                write!(f, "unsupported binary encoding version {}", version)
+            }
+            FromBinError::InvalidSign(sign) => write!(f, "invalid sign byte {}", sign),
+            FromBinError::NonCanonical => f.write_str("binary encoding is not canonical"),
+        }
+    }
+}
+
+impl Error for FromBinError {}
+
+impl BigUint {
+    /// Returns the number as bytes, most significant first, without leading
+    /// zeros; zero is a single `0` byte.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("65539");
+    /// assert_eq!(n.magnitude().to_bytes_be(), [0x01, 0x00, 0x03]);
+    /// ```
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Returns the number as bytes, least significant first, without trailing
+    /// zeros; zero is a single `0` byte.
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        while bytes.len() > 1 && bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        bytes
+    }
+
+    /// Decodes bytes given most significant first. Leading zeros are allowed,
+    /// and an empty slice is zero.
+    pub fn from_bytes_be(bytes: &[u8]) -> BigUint {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    /// Decodes bytes given least significant first. Trailing zeros are
+    /// allowed, and an empty slice is zero.
+    pub fn from_bytes_le(bytes: &[u8]) -> BigUint {
+        if bytes.is_empty() {
+            return BigUint::zero();
+        }
+        let limbs = bytes
+            .chunks(4)
+            .map(|chunk| chunk.iter().rev().fold(0u32, |limb, &byte| (limb << 8) | byte as u32))
+            .collect();
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl BigNumber {
+    /// Returns the sign and the magnitude as bytes, most significant first.
+    ///
+    /// See [`BigUint::to_bytes_be`]; use
+    /// [`to_signed_bytes_be`](BigNumber::to_signed_bytes_be) for a single byte
+    /// string that carries the sign.
+    pub fn to_bytes_be(&self) -> (Sign, Vec<u8>) {
+        (self.sign, self.magnitude.to_bytes_be())
+    }
+
+    /// Returns the sign and the magnitude as bytes, least significant first.
+    pub fn to_bytes_le(&self) -> (Sign, Vec<u8>) {
+        (self.sign, self.magnitude.to_bytes_le())
+    }
+
+    /// Builds a number from a sign and magnitude bytes, most significant first.
+    pub fn from_bytes_be(sign: Sign, bytes: &[u8]) -> BigNumber {
+        BigNumber::from_biguint(sign, BigUint::from_bytes_be(bytes))
+    }
+
+    /// Builds a number from a sign and magnitude bytes, least significant first.
+    pub fn from_bytes_le(sign: Sign, bytes: &[u8]) -> BigNumber {
+        BigNumber::from_biguint(sign, BigUint::from_bytes_le(bytes))
+    }
+
+    /// Returns the shortest two's complement encoding of the number, most
+    /// significant byte first, as used by ASN.1 DER `INTEGER`s.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("128").to_signed_bytes_be(), [0x00, 0x80]);
+    /// assert_eq!(BigNumber::from_string("-129").to_signed_bytes_be(), [0xff, 0x7f]);
+    /// ```
+    pub fn to_signed_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_signed_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Returns the shortest two's complement encoding of the number, least
+    /// significant byte first.
+    pub fn to_signed_bytes_le(&self) -> Vec<u8> {
+        // One more byte than the magnitude needs always has room for the sign
+        let mut bytes = self.magnitude.to_bytes_le();
+        bytes.push(0);
+        if self.is_negative() {
+            negate(&mut bytes);
+        }
+
+        // Drop leading bytes that only repeat the sign bit of the next one
+        let sign_byte = if self.is_negative() { 0xff } else { 0x00 };
+        while let [.., next, last] = bytes[..] {
+            if last != sign_byte || next & 0x80 != sign_byte & 0x80 {
+                break;
+            }
+            bytes.pop();
+        }
+        bytes
+    }
+
+    /// Decodes a two's complement number, most significant byte first. An
+    /// empty slice is zero.
+    pub fn from_signed_bytes_be(bytes: &[u8]) -> BigNumber {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        BigNumber::from_signed_bytes_le(&bytes)
+    }
+
+    /// Decodes a two's complement number, least significant byte first. An
+    /// empty slice is zero.
+    pub fn from_signed_bytes_le(bytes: &[u8]) -> BigNumber {
+        let negative = bytes.last().is_some_and(|&byte| byte & 0x80 != 0);
+        let mut bytes = bytes.to_vec();
+        if negative {
+            negate(&mut bytes);
+        }
+        let sign = if negative { Sign::Negative } else { Sign::Positive };
+        BigNumber::from_biguint(sign, BigUint::from_bytes_le(&bytes))
+    }
+}
+
+impl BigNumber {
+    /// Returns a compact binary encoding of the number, for storage and
+    /// transmission without a decimal round trip.
+    ///
+    /// The layout is a version byte (currently `1`), a sign byte (`0` for
+    /// non-negative, `1` for negative), the number of limbs as a little endian
+    /// `u32`, and then the base 2^32 limbs of the magnitude, least significant
+    /// first, each as a little endian `u32`. Zero has no limbs, and the most
+    /// significant limb of any other number is nonzero.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from(-(1i64 << 32) - 2);
+    /// assert_eq!(n.to_bin(), [1, 1, 2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0]);
+    /// assert_eq!(BigNumber::from_bin(&n.to_bin()), Ok(n));
+    /// ```
+    pub fn to_bin(&self) -> Vec<u8> {
+        let limbs: &[u32] = if self.is_zero() { &[] } else { &self.magnitude.limbs };
+        let mut bytes = Vec::with_capacity(BIN_HEADER_LEN + 4 * limbs.len());
+        bytes.push(BIN_VERSION);
+        bytes.push(self.is_negative() as u8);
+        bytes.extend_from_slice(&(limbs.len() as u32).to_le_bytes());
+        for limb in limbs {
+            bytes.extend_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes the output of [`to_bin`](BigNumber::to_bin), accepting only
+    /// the exact encoding it produces.
+    pub fn from_bin(bytes: &[u8]) -> Result<BigNumber, FromBinError> {
+        if bytes.len() < BIN_HEADER_LEN {
+            return Err(FromBinError::Truncated);
+        }
+        if bytes[0] != BIN_VERSION {
+            return Err(FromBinError::UnsupportedVersion(bytes[0]));
+        }
+        let sign = match bytes[1] {
+            0 => Sign::Positive,
+            1 => Sign::Negative,
+            other => return Err(FromBinError::InvalidSign(other)),
+        };
+
+        let count = u32::from_le_bytes(bytes[2..6].try_into().unwrap()) as usize;
+        let body = &bytes[BIN_HEADER_LEN..];
+        match body.len().cmp(&count.saturating_mul(4)) {
+            core::cmp::Ordering::Less => return Err(FromBinError::Truncated),
+            core::cmp::Ordering::Greater => return Err(FromBinError::TrailingBytes),
+            core::cmp::Ordering::Equal => {}
+        }
+
+        let limbs: Vec<u32> = body
+            .chunks_exact(4)
+            .map(|limb| u32::from_le_bytes(limb.try_into().unwrap()))
+            .collect();
+        match limbs.last() {
+            Some(0) => return Err(FromBinError::NonCanonical),
+            None if sign == Sign::Negative => return Err(FromBinError::NonCanonical),
+            _ => {}
+        }
+        Ok(BigNumber::from_biguint(sign, BigUint::from_limbs(limbs)))
+    }
+}
+
+// Replaces little-endian bytes by their two's complement negation
+fn negate(bytes: &mut [u8]) {
+    let mut carry = true;
+    for byte in bytes {
+        let (sum, overflow) = (!*byte).overflowing_add(carry as u8);
+        *byte = sum;
+        carry = overflow;
+    }
+}
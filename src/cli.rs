@@ -0,0 +1,387 @@
+//! The logic behind the `bignumber` binary, kept apart from its input and
+//! output so that it can be tested and reused by other front ends.
+//!
+//! ```
+//! use bignumber::cli::Calculator;
+//!
+//! let mut calculator = Calculator::new();
+//! calculator.eval_line("x = 2^64").unwrap();
+//! assert_eq!(calculator.eval_line("x - 1").unwrap().to_string(), "18446744073709551615");
+//! ```
+//!
+//! The one-shot commands, such as `bignumber factor -12`, are in [`COMMANDS`]:
+//!
+//! ```
+//! use bignumber::cli::Command;
+//!
+//! let factor = Command::find("factor").unwrap();
+//! assert_eq!(factor.run(&["-12"]).unwrap(), ["-1 * 2^2 * 3"]);
+//! ```
+//!
+//! The reductions over standard input, such as `bignumber --gcd`, are in
+//! [`REDUCTIONS`] and fold any iterator of lines.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::expr::{EvalError, Expr, ParseExprError};
+use crate::{BigNumber, ParseBigNumberError};
+
+/// The error returned by [`Calculator::eval_line`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalcError {
+    /// The left side of an assignment is not a variable name.
+    InvalidName(String),
+    /// The expression did not parse.
+    Parse(ParseExprError),
+    /// The expression did not evaluate.
+    Eval(EvalError),
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalcError::InvalidName(name) => write!(f, "cannot assign to {:?}", name),
+            CalcError::Parse(err) => err.fmt(f),
+            CalcError::Eval(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for CalcError {}
+
+/// The interactive calculator: evaluates lines of input, remembering the
+/// variables they assign.
+#[derive(Debug, Clone, Default)]
+pub struct Calculator {
+    variables: BTreeMap<String, BigNumber>,
+}
+
+impl Calculator {
+    /// Returns a calculator with no variables.
+    pub fn new() -> Calculator {
+        Calculator::default()
+    }
+
+    /// Evaluates a line, which is either an expression or an assignment
+    /// `name = expression` that also stores the value under `name`, and
+    /// returns its value.
+    pub fn eval_line(&mut self, line: &str) -> Result<BigNumber, CalcError> {
+        let Some((name, expression)) = line.split_once('=') else {
+            return self.evaluate(line);
+        };
+        let name = name.trim();
+        if !is_identifier(name) {
+            return Err(CalcError::InvalidName(name.to_string()));
+        }
+        let value = self.evaluate(expression)?;
+        self.variables.insert(name.to_string(), value.clone());
+        Ok(value)
+    }
+
+    /// Returns the value last assigned to `name`.
+    pub fn variable(&self, name: &str) -> Option<&BigNumber> {
+        self.variables.get(name)
+    }
+
+    fn evaluate(&self, input: &str) -> Result<BigNumber, CalcError> {
+        let expr = Expr::parse(input).map_err(CalcError::Parse)?;
+        expr.eval_with(|name| self.variables.get(name).cloned())
+            .map_err(CalcError::Eval)
+    }
+}
+
+// Whether `name` can be assigned to, which takes the same form as the
+// variables of an expression
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The error returned by [`Command::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// The command was given the wrong number of arguments.
+    Usage,
+    /// An argument is not a number.
+    InvalidNumber {
+        /// The argument as given.
+        argument: String,
+        /// Why it did not parse.
+        error: ParseBigNumberError,
+    },
+    /// The operation is undefined for its arguments, such as a division by
+    /// zero.
+    Failed(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Usage => f.write_str("wrong number of arguments"),
+            CommandError::InvalidNumber { argument, error } => {
+                write!(f, "invalid number {:?}: {}", argument, error)
+            }
+            CommandError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Error for CommandError {}
+
+/// A one-shot command of the binary: its name, the names of its operands,
+/// and a summary for the usage text.
+pub struct Command {
+    /// The name the command is invoked by.
+    pub name: &'static str,
+    /// The operands, separated by spaces, such as `"A E M"`.
+    pub operands: &'static str,
+    /// What the command prints.
+    pub about: &'static str,
+    run: fn(&[BigNumber]) -> Result<Vec<String>, String>,
+}
+
+/// Every command, in the order the usage text lists them.
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "add",
+        operands: "A B",
+        about: "A + B",
+        run: |n| Ok(vec![(&n[0] + &n[1]).to_string()]),
+    },
+    Command {
+        name: "mul",
+        operands: "A B",
+        about: "A * B",
+        run: |n| Ok(vec![(&n[0] * &n[1]).to_string()]),
+    },
+    Command {
+        name: "divrem",
+        operands: "A B",
+        about: "the quotient and remainder of A / B, rounded towards zero",
+        run: |n| {
+            let (quotient, remainder) = n[0].checked_div_rem(&n[1]).map_err(|err| err.to_string())?;
+            Ok(vec![quotient.to_string(), remainder.to_string()])
+        },
+    },
+    Command {
+        name: "gcd",
+        operands: "A B",
+        about: "the greatest common divisor of A and B",
+        run: |n| Ok(vec![n[0].gcd(&n[1]).to_string()]),
+    },
+    Command {
+        name: "pow",
+        operands: "A E",
+        about: "A^E",
+        run: |n| {
+            let exponent = exponent(&n[1])?.to_u64().ok_or("exponent too large")?;
+            Ok(vec![n[0].pow(exponent).to_string()])
+        },
+    },
+    Command {
+        name: "modpow",
+        operands: "A E M",
+        about: "A^E mod M, in 0..|M|",
+        run: |n| {
+            if n[2].is_zero() {
+                return Err("modulus is zero".to_string());
+            }
+            Ok(vec![n[0].modpow(exponent(&n[1])?, &n[2]).to_string()])
+        },
+    },
+    Command {
+        name: "isprime",
+        operands: "N",
+        about: "true if N is prime, false otherwise",
+        run: |n| Ok(vec![n[0].is_prime().to_string()]),
+    },
+    Command {
+        name: "factor",
+        operands: "N",
+        about: "the prime factorization of N, such as 2^3 * 3^2 * 5",
+        run: |n| Ok(vec![factorization(&n[0])]),
+    },
+    Command {
+        name: "sqrt",
+        operands: "N",
+        about: "the integer square root of N",
+        run: |n| {
+            if n[0].is_negative() {
+                return Err("square root of a negative number".to_string());
+            }
+            Ok(vec![n[0].isqrt().to_string()])
+        },
+    },
+];
+
+impl Command {
+    /// Returns the command called `name`.
+    pub fn find(name: &str) -> Option<&'static Command> {
+        COMMANDS.iter().find(|command| command.name == name)
+    }
+
+    /// Returns the number of arguments the command takes.
+    pub fn arity(&self) -> usize {
+        self.operands.split_whitespace().count()
+    }
+
+    /// Parses `args` as the command's operands and returns the lines of its
+    /// output.
+    pub fn run(&self, args: &[&str]) -> Result<Vec<String>, CommandError> {
+        if args.len() != self.arity() {
+            return Err(CommandError::Usage);
+        }
+        let numbers = args
+            .iter()
+            .map(|arg| {
+                BigNumber::parse_literal(arg).map_err(|error| CommandError::InvalidNumber {
+                    argument: arg.to_string(),
+                    error,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        (self.run)(&numbers).map_err(CommandError::Failed)
+    }
+}
+
+fn exponent(n: &BigNumber) -> Result<&BigNumber, String> {
+    if n.is_negative() {
+        return Err("negative exponent".to_string());
+    }
+    Ok(n)
+}
+
+/// The error returned by [`Reduction::reduce`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReduceError {
+    /// A line is not a number.
+    InvalidNumber {
+        /// The line number, counting from 1.
+        line: usize,
+        /// The line, without surrounding whitespace.
+        input: String,
+        /// Why it did not parse.
+        error: ParseBigNumberError,
+    },
+    /// There were no numbers to reduce, and the reduction has no value for
+    /// none.
+    Empty,
+}
+
+impl fmt::Display for ReduceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReduceError::InvalidNumber { line, input, error } => {
+                write!(f, "line {}: invalid number {:?}: {}", line, input, error)
+            }
+            ReduceError::Empty => f.write_str("no numbers on standard input"),
+        }
+    }
+}
+
+impl Error for ReduceError {}
+
+/// A reduction of the binary over one number per line: its flag and a
+/// summary for the usage text.
+pub struct Reduction {
+    /// The flag the reduction is invoked by, such as `"--sum"`.
+    pub flag: &'static str,
+    /// What the reduction prints.
+    pub about: &'static str,
+    // The starting value if it has one, and the step folding in one number
+    start: Option<u32>,
+    step: fn(&mut BigNumber, BigNumber),
+}
+
+/// Every reduction, in the order the usage text lists them.
+pub const REDUCTIONS: &[Reduction] = &[
+    Reduction {
+        flag: "--sum",
+        about: "the sum of the numbers",
+        start: Some(0),
+        step: |total, n| *total += n,
+    },
+    Reduction {
+        flag: "--product",
+        about: "the product of the numbers",
+        start: Some(1),
+        step: |total, n| *total *= n,
+    },
+    Reduction {
+        flag: "--max",
+        about: "the largest number",
+        start: None,
+        step: |max, n| {
+            if n > *max {
+                *max = n;
+            }
+        },
+    },
+    Reduction {
+        flag: "--gcd",
+        about: "the greatest common divisor of the numbers",
+        start: Some(0),
+        step: |gcd, n| *gcd = gcd.gcd(&n),
+    },
+];
+
+impl Reduction {
+    /// Returns the reduction invoked by `flag`.
+    pub fn find(flag: &str) -> Option<&'static Reduction> {
+        REDUCTIONS.iter().find(|reduction| reduction.flag == flag)
+    }
+
+    /// Folds the numbers in `lines`, one per line, skipping blank lines.
+    /// Lines are consumed as they are folded in, so they can come from a
+    /// stream far larger than memory.
+    pub fn reduce<I, S>(&self, lines: I) -> Result<BigNumber, ReduceError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        // Reductions with a starting value fold every number into it,
+        // which also makes the gcd of a single negative number positive
+        let mut result = self.start.map(BigNumber::from);
+        for (index, line) in lines.into_iter().enumerate() {
+            let word = line.as_ref().trim();
+            if word.is_empty() {
+                continue;
+            }
+            let n = BigNumber::parse_literal(word).map_err(|error| ReduceError::InvalidNumber {
+                line: index + 1,
+                input: word.to_string(),
+                error,
+            })?;
+            match &mut result {
+                Some(result) => (self.step)(result, n),
+                None => result = Some(n),
+            }
+        }
+        result.ok_or(ReduceError::Empty)
+    }
+}
+
+/// Writes the prime factorization of `n` the way the calculator reads it
+/// back, such as `-1 * 2^2 * 3` for -12. Zero and ±1 are written as is.
+pub fn factorization(n: &BigNumber) -> String {
+    let factors = n.factorize();
+    if factors.is_empty() {
+        return n.to_string();
+    }
+    let mut parts: Vec<String> = factors
+        .iter()
+        .map(|(p, e)| if *e == 1 { p.to_string() } else { format!("{}^{}", p, e) })
+        .collect();
+    if n.is_negative() {
+        parts.insert(0, "-1".to_string());
+    }
+    parts.join(" * ")
+}
+
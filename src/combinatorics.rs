@@ -0,0 +1,235 @@
+use alloc::vec::Vec;
+
+use crate::mul::product;
+use crate::sieve::primes_up_to;
+use crate::{BigNumber, BigUint};
+
+// Arguments from which factorials are assembled from their prime factorization
+const FACTORIAL_SPLIT_THRESHOLD: u32 = 3000;
+
+impl BigNumber {
+    /// Returns the primorial `n#`, the product of all primes up to and
+    /// including `n`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::primorial(30).to_string(), "6469693230");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` does not fit in a `u32`.
+    pub fn primorial(n: u64) -> BigNumber {
+        let n = u32::try_from(n).expect("primorial bound must fit in a u32");
+        BigNumber::from(product(&primes_up_to(n)))
+    }
+
+    /// Returns `n!`, the product of the integers from `1` to `n`.
+    ///
+    /// Small factorials are a running product with word-sized factors. Large
+    /// ones are assembled from their prime factorization with balanced
+    /// multiplications, whose product trees are built in parallel when the
+    /// `rayon` feature is enabled.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::factorial(20).to_string(), "2432902008176640000");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` does not fit in a `u32`.
+    pub fn factorial(n: u64) -> BigNumber {
+        let n = u32::try_from(n).expect("factorial argument must fit in a u32");
+        if n >= FACTORIAL_SPLIT_THRESHOLD {
+            return BigNumber::from(factorial_by_primes(n));
+        }
+        let mut result = BigUint::one();
+
+        // Pack as many consecutive factors into one limb as fit, so that each
+        // pass over the result multiplies in several of them
+        let mut packed = 1u64;
+        for i in 2..=n as u64 {
+            if packed * i > u32::MAX as u64 {
+                result.mul_small(packed as u32);
+                packed = 1;
+            }
+            packed *= i;
+        }
+        result.mul_small(packed as u32);
+
+        BigNumber::from(result)
+    }
+
+    /// Returns the double factorial `n!!`, the product of the integers from `1`
+    /// to `n` with the same parity as `n`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::double_factorial(9).to_string(), "945");
+    /// assert_eq!(BigNumber::double_factorial(10).to_string(), "3840");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` does not fit in a `u32`.
+    pub fn double_factorial(n: u64) -> BigNumber {
+        let n = u32::try_from(n).expect("double factorial argument must fit in a u32");
+        let factors: Vec<u32> = (1..=n).rev().step_by(2).collect();
+        BigNumber::from(product(&factors))
+    }
+
+    /// Returns the falling factorial `self (self - 1) ... (self - k + 1)`, which
+    /// is `1` for `k = 0`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("10").falling_factorial(3).to_string(), "720");
+    /// ```
+    pub fn falling_factorial(&self, k: u64) -> BigNumber {
+        let factors: Vec<BigNumber> = (0..k).map(|i| self - &BigNumber::from(BigUint::from(i))).collect();
+        balanced_product(&factors)
+    }
+
+    /// Returns the rising factorial `self (self + 1) ... (self + k - 1)`, which
+    /// is `1` for `k = 0`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("10").rising_factorial(3).to_string(), "1320");
+    /// ```
+    pub fn rising_factorial(&self, k: u64) -> BigNumber {
+        let factors: Vec<BigNumber> = (0..k).map(|i| self + &BigNumber::from(BigUint::from(i))).collect();
+        balanced_product(&factors)
+    }
+
+    /// Returns the binomial coefficient `n choose k`, which is zero for `k > n`.
+    ///
+    /// The coefficient is built up as `C(n - k + i, i)` for `i = 1..=k`, each step
+    /// multiplying in one factor and dividing exactly by another after both are
+    /// reduced by their gcd, so no factorials are ever formed.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::binomial(52, 5).to_string(), "2598960");
+    /// ```
+    pub fn binomial(n: u64, k: u64) -> BigNumber {
+        if k > n {
+            return BigNumber::zero();
+        }
+        let k = k.min(n - k);
+
+        let mut result = BigUint::one();
+        for i in 1..=k {
+            let numerator = n - k + i;
+            let g = gcd_u64(numerator, i);
+            result = &result * &BigUint::from(numerator / g);
+            result = &result / &BigUint::from(i / g);
+        }
+        BigNumber::from(result)
+    }
+
+    /// Returns the multinomial coefficient `(k1 + k2 + ...)! / (k1! k2! ...)`
+    /// for the given parts, which is `1` for an empty slice.
+    ///
+    /// It is the product of the binomials `C(k1 + ... + ki, ki)`, so the
+    /// intermediate values never exceed the result.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// // Arrangements of the letters of MISSISSIPPI
+    /// assert_eq!(BigNumber::multinomial(&[1, 4, 4, 2]).to_string(), "34650");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts add up to more than `u64::MAX`.
+    pub fn multinomial(parts: &[u64]) -> BigNumber {
+        let mut total = 0u64;
+        let mut result = BigNumber::one();
+        for &part in parts {
+            total = total.checked_add(part).expect("multinomial parts must sum to a u64");
+            result *= &BigNumber::binomial(total, part);
+        }
+        result
+    }
+
+    /// Returns the Catalan number `C(2n, n) / (n + 1)`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::catalan(10).to_string(), "16796");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `2n` does not fit in a `u64`.
+    pub fn catalan(n: u64) -> BigNumber {
+        let twice = n.checked_mul(2).expect("catalan argument must be at most u64::MAX / 2");
+        let binomial = BigNumber::binomial(twice, n);
+        BigNumber::from(&binomial.magnitude / &BigUint::from(n + 1))
+    }
+}
+
+// Computes n! from its prime factorization. The exponent of p is
+// sum(n / p^i) by Legendre's formula, and the odd part is built by binary
+// exponentiation on all exponents at once: for each bit from the top, square
+// the result and multiply in the product of the primes whose exponent has that
+// bit set. Every multiplication is then between numbers of similar size.
+fn factorial_by_primes(n: u32) -> BigUint {
+    let primes = primes_up_to(n);
+    let exponent = |p: u32| {
+        let mut total = 0;
+        let mut quotient = n;
+        while quotient >= p {
+            quotient /= p;
+            total += quotient;
+        }
+        total
+    };
+    let exponents: Vec<u32> = primes.iter().map(|&p| exponent(p)).collect();
+
+    let mut result = BigUint::one();
+    let top_bit = 31 - exponents[1].leading_zeros();
+    for bit in (0..=top_bit).rev() {
+        result = &result * &result;
+        let selected: Vec<u32> = primes[1..]
+            .iter()
+            .zip(&exponents[1..])
+            .filter(|&(_, &e)| e >> bit & 1 == 1)
+            .map(|(&p, _)| p)
+            .collect();
+        result = &result * &product(&selected);
+    }
+
+    // The power of two is a shift
+    result.shl_bits(exponents[0] as u64);
+    result
+}
+
+// Multiplies arbitrary factors pairwise from the leaves up, like `product`
+fn balanced_product(factors: &[BigNumber]) -> BigNumber {
+    match factors {
+        [] => BigNumber::one(),
+        [factor] => factor.clone(),
+        _ => {
+            let (low, high) = factors.split_at(factors.len() / 2);
+            &balanced_product(low) * &balanced_product(high)
+        }
+    }
+}
+
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
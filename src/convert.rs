@@ -0,0 +1,285 @@
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::biguint::LIMB_BITS;
+use crate::{BigNumber, BigUint, Sign};
+
+/// The error returned when a number does not fit in the primitive integer type
+/// it is converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromBigNumberError;
+
+impl fmt::Display for TryFromBigNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("number out of range for the target integer type")
+    }
+}
+
+impl Error for TryFromBigNumberError {}
+
+/// The error returned when converting an `f64` that is not a finite integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromF64Error;
+
+impl fmt::Display for TryFromF64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("float is not a finite integer")
+    }
+}
+
+impl Error for TryFromF64Error {}
+
+// Implements `From` for unsigned primitives, splitting the value into limbs
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigUint {
+            #[allow(clippy::unnecessary_cast)]
+            fn from(value: $t) -> BigUint {
+                let mut value = value as u128;
+                let mut limbs = Vec::new();
+                while value > 0 {
+                    limbs.push(value as u32);
+                    value >>= LIMB_BITS;
+                }
+                if limbs.is_empty() {
+                    return BigUint::zero();
+                }
+                BigUint::from_limbs(limbs)
+            }
+        }
+
+        impl From<$t> for BigNumber {
+            fn from(value: $t) -> BigNumber {
+                BigNumber::from(BigUint::from(value))
+            }
+        }
+    )*};
+}
+
+// Implements `From` for signed primitives through their unsigned magnitude
+macro_rules! from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigNumber {
+            fn from(value: $t) -> BigNumber {
+                let sign = if value < 0 { Sign::Negative } else { Sign::Positive };
+                BigNumber::from_biguint(sign, BigUint::from(value.unsigned_abs()))
+            }
+        }
+    )*};
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl BigUint {
+    // The value as a u128, if it has at most four limbs
+    pub(crate) fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(self.limbs.iter().rev().fold(0u128, |value, &limb| (value << LIMB_BITS) | limb as u128))
+    }
+}
+
+impl BigUint {
+    /// Returns the nearest `f64` to the number, rounding ties to even, or
+    /// infinity if it is beyond the range of `f64`.
+    pub fn to_f64(&self) -> f64 {
+        let bits = self.bits();
+        if bits <= 64 {
+            return self.to_u128().unwrap() as f64;
+        }
+        if bits > f64::MAX_EXP as u64 {
+            return f64::INFINITY;
+        }
+
+        // Keep the top 64 bits and fold everything below into the lowest one,
+        // which still sits well below the 53 bits that survive rounding
+        let shift = bits - 64;
+        let mut top = self >> shift as usize;
+        if self.trailing_zeros() < shift {
+            top.set_bit(0);
+        }
+        let scale = f64::from_bits((f64::MAX_EXP as u64 - 1 + shift) << 52);
+        top.to_u128().unwrap() as f64 * scale
+    }
+}
+
+impl BigNumber {
+    /// Returns the nearest `f64` to the number, rounding ties to even, or
+    /// an infinity of the right sign if it is beyond the range of `f64`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("-9007199254740993").to_f64(), -9007199254740992.0);
+    /// assert_eq!(BigNumber::from(10u32).pow(400).to_f64(), f64::INFINITY);
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        let value = self.magnitude.to_f64();
+        if self.is_negative() {
+            -value
+        } else {
+            value
+        }
+    }
+
+    /// Converts the integer part of a finite `f64`, rounding towards zero, or
+    /// returns `None` for infinities and NaN.
+    ///
+    /// The `TryFrom<f64>` implementation is the strict form, which also
+    /// rejects values with a fractional part.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_f64_truncated(-2.75).unwrap().to_string(), "-2");
+    /// assert_eq!(BigNumber::from_f64_truncated(1e20).unwrap().to_string(), "100000000000000000000");
+    /// assert!(BigNumber::from_f64_truncated(f64::NAN).is_none());
+    /// ```
+    pub fn from_f64_truncated(value: f64) -> Option<BigNumber> {
+        split_f64(value).map(|(number, _)| number)
+    }
+
+    /// Returns the number as a `u64`, or `None` if it is negative or too large.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("18446744073709551615").to_u64(), Some(u64::MAX));
+    /// assert_eq!(BigNumber::from_string("18446744073709551616").to_u64(), None);
+    /// assert_eq!(BigNumber::from_string("-1").to_u64(), None);
+    /// ```
+    pub fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self).ok()
+    }
+
+    /// Returns the number as an `i64`, or `None` if it is out of range.
+    pub fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self).ok()
+    }
+
+    /// Returns the number as a `u128`, or `None` if it is negative or too large.
+    pub fn to_u128(&self) -> Option<u128> {
+        u128::try_from(self).ok()
+    }
+
+    /// Returns the number as an `i128`, or `None` if it is out of range.
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude.to_u128()?;
+        if self.is_negative() {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+}
+
+// Implements `TryFrom<&BigUint>`, `TryFrom<&BigNumber>` and their owned forms
+// for unsigned primitives
+macro_rules! try_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl TryFrom<&BigUint> for $t {
+            type Error = TryFromBigNumberError;
+
+            fn try_from(value: &BigUint) -> Result<$t, TryFromBigNumberError> {
+                value.to_u128().and_then(|value| <$t>::try_from(value).ok()).ok_or(TryFromBigNumberError)
+            }
+        }
+
+        impl TryFrom<BigUint> for $t {
+            type Error = TryFromBigNumberError;
+
+            fn try_from(value: BigUint) -> Result<$t, TryFromBigNumberError> {
+                <$t>::try_from(&value)
+            }
+        }
+
+        impl TryFrom<&BigNumber> for $t {
+            type Error = TryFromBigNumberError;
+
+            fn try_from(value: &BigNumber) -> Result<$t, TryFromBigNumberError> {
+                if value.is_negative() {
+                    return Err(TryFromBigNumberError);
+                }
+                <$t>::try_from(&value.magnitude)
+            }
+        }
+
+        impl TryFrom<BigNumber> for $t {
+            type Error = TryFromBigNumberError;
+
+            fn try_from(value: BigNumber) -> Result<$t, TryFromBigNumberError> {
+                <$t>::try_from(&value)
+            }
+        }
+    )*};
+}
+
+// Implements `TryFrom<&BigNumber>` and the owned form for signed primitives
+macro_rules! try_from_signed {
+    ($($t:ty),*) => {$(
+        impl TryFrom<&BigNumber> for $t {
+            type Error = TryFromBigNumberError;
+
+            fn try_from(value: &BigNumber) -> Result<$t, TryFromBigNumberError> {
+                value.to_i128().and_then(|value| <$t>::try_from(value).ok()).ok_or(TryFromBigNumberError)
+            }
+        }
+
+        impl TryFrom<BigNumber> for $t {
+            type Error = TryFromBigNumberError;
+
+            fn try_from(value: BigNumber) -> Result<$t, TryFromBigNumberError> {
+                <$t>::try_from(&value)
+            }
+        }
+    )*};
+}
+
+try_from_unsigned!(u8, u16, u32, u64, u128, usize);
+try_from_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Accepts only finite values without a fractional part, which convert
+/// exactly. See [`BigNumber::from_f64_truncated`] for a rounding conversion.
+impl TryFrom<f64> for BigNumber {
+    type Error = TryFromF64Error;
+
+    fn try_from(value: f64) -> Result<BigNumber, TryFromF64Error> {
+        match split_f64(value) {
+            Some((number, false)) => Ok(number),
+            _ => Err(TryFromF64Error),
+        }
+    }
+}
+
+// Splits a finite double into its integer part, rounded towards zero, and
+// whether a fractional part was dropped. The value is `mantissa * 2^exponent`
+// with a 53-bit mantissa, so the integer part is a shift of the mantissa.
+fn split_f64(value: f64) -> Option<(BigNumber, bool)> {
+    if !value.is_finite() {
+        return None;
+    }
+
+    let bits = value.to_bits();
+    let biased_exponent = (bits >> 52 & 0x7ff) as i64;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, biased_exponent - 1075)
+    };
+
+    let (magnitude, dropped) = if exponent >= 0 {
+        (&BigUint::from(mantissa) << exponent as usize, false)
+    } else if exponent > -64 {
+        let shift = -exponent as u32;
+        (BigUint::from(mantissa >> shift), mantissa & ((1 << shift) - 1) != 0)
+    } else {
+        (BigUint::zero(), mantissa != 0)
+    };
+
+    let sign = if value.is_sign_negative() { Sign::Negative } else { Sign::Positive };
+    Some((BigNumber::from_biguint(sign, magnitude), dropped))
+}
@@ -0,0 +1,172 @@
+//! Finite field Diffie-Hellman key exchange, behind the `rand` feature.
+//!
+//! Both parties share a [`Group`], a safe prime `p = 2q + 1` and a generator
+//! of the subgroup of order `q`. Each picks a [`PrivateKey`], publishes its
+//! public value, and combines the other's public value with its own secret to
+//! reach the same shared secret. Like [`rsa`](crate::rsa), this demonstrates
+//! the modular arithmetic and is not hardened: nothing runs in constant time.
+//!
+//! ```
+//! use bignumber::dh::{Group, PrivateKey};
+//!
+//! let group = Group::modp_2048();
+//! let mut rng = rand::thread_rng();
+//! let alice = PrivateKey::generate(&mut rng, &group);
+//! let bob = PrivateKey::generate(&mut rng, &group);
+//!
+//! let alice_secret = alice.shared_secret(bob.public_key()).unwrap();
+//! let bob_secret = bob.shared_secret(alice.public_key()).unwrap();
+//! assert_eq!(alice_secret, bob_secret);
+//! ```
+
+use core::fmt;
+
+use rand::RngCore;
+
+use crate::BigNumber;
+
+// The 2048-bit MODP group of RFC 3526, 2^2048 - 2^1984 - 1 + 2^64 * ([2^1918 pi] + 124476)
+const MODP_2048: &str = concat!(
+    "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74",
+    "020BBEA63B139B22514A08798E3404DDEF9519B3CD3A431B302B0A6DF25F1437",
+    "4FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B0BFF5CB6F406B7ED",
+    "EE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3DC2007CB8A163BF05",
+    "98DA48361C55D39A69163FA8FD24CF5F83655D23DCA3AD961C62F356208552BB",
+    "9ED529077096966D670C354E4ABC9804F1746C08CA18217C32905E462E36CE3B",
+    "E39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF695581718",
+    "3995497CEA956AE515D2261898FA051015728E5A8AACAA68FFFFFFFFFFFFFFFF",
+);
+
+/// Diffie-Hellman parameters: a safe prime `p = 2q + 1` and a generator of
+/// the subgroup of order `q`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    prime: BigNumber,
+    generator: BigNumber,
+    order: BigNumber,
+}
+
+impl Group {
+    /// Builds a group from a safe prime and a generator of its subgroup of
+    /// order `(prime - 1) / 2`.
+    ///
+    /// Neither property is checked, as testing a large prime for safety is
+    /// slow; [`Group::is_valid`] does that on request.
+    pub fn new(prime: BigNumber, generator: BigNumber) -> Group {
+        let mut order = &prime - &BigNumber::one();
+        order >>= 1;
+        Group { prime, generator, order }
+    }
+
+    /// Returns the 2048-bit group 14 from RFC 3526, with generator 2.
+    pub fn modp_2048() -> Group {
+        let prime = BigNumber::from_str_radix(MODP_2048, 16).unwrap();
+        Group::new(prime, BigNumber::from(2u32))
+    }
+
+    /// Generates a group with a fresh safe prime of exactly `bits` binary
+    /// digits and the generator 4, which as a square always lies in the
+    /// subgroup of order `q`.
+    ///
+    /// Safe primes are rare, so this takes many prime searches and gets slow
+    /// well before the sizes used in practice; prefer [`Group::modp_2048`]
+    /// there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is below 3.
+    pub fn generate<R: RngCore + ?Sized>(rng: &mut R, bits: u64) -> Group {
+        assert!(bits >= 3, "safe primes need at least 3 bits");
+        loop {
+            let q = BigNumber::random_prime(rng, bits - 1);
+            let mut prime = &q << 1;
+            prime.add_in_place(&BigNumber::one());
+            if prime.is_prime() {
+                return Group::new(prime, BigNumber::from(4u32));
+            }
+        }
+    }
+
+    /// Returns the prime modulus `p`.
+    pub fn prime(&self) -> &BigNumber {
+        &self.prime
+    }
+
+    /// Returns the generator `g`.
+    pub fn generator(&self) -> &BigNumber {
+        &self.generator
+    }
+
+    /// Returns the order `q = (p - 1) / 2` of the subgroup `g` generates.
+    pub fn order(&self) -> &BigNumber {
+        &self.order
+    }
+
+    /// Returns `true` if `p` is a safe prime and `g` generates the subgroup of
+    /// order `q`.
+    pub fn is_valid(&self) -> bool {
+        self.prime.is_prime() && self.order.is_prime() && self.contains(&self.generator)
+    }
+
+    // Whether `value` is an element of the order q subgroup other than one,
+    // which is what a peer's public value has to be
+    fn contains(&self, value: &BigNumber) -> bool {
+        let one = BigNumber::one();
+        *value > one && *value < &self.prime - &one && value.modpow(&self.order, &self.prime) == one
+    }
+}
+
+/// A Diffie-Hellman private key: a secret exponent `x` in `2..q` and the
+/// public value `g^x mod p`.
+///
+/// With the `zeroize` feature the exponent is cleared when the key is dropped.
+/// The `Debug` output leaves it out.
+#[derive(Clone)]
+pub struct PrivateKey {
+    group: Group,
+    exponent: BigNumber,
+    public: BigNumber,
+}
+
+impl PrivateKey {
+    /// Draws a secret exponent from `rng` and computes the public value.
+    pub fn generate<R: RngCore + ?Sized>(rng: &mut R, group: &Group) -> PrivateKey {
+        let exponent = BigNumber::random_range(rng, BigNumber::from(2u32)..group.order.clone());
+        let public = group.generator.modpow(&exponent, &group.prime);
+        PrivateKey {
+            group: group.clone(),
+            exponent,
+            public,
+        }
+    }
+
+    /// Returns the group the key belongs to.
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+
+    /// Returns the public value `g^x mod p` to send to the other party.
+    pub fn public_key(&self) -> &BigNumber {
+        &self.public
+    }
+
+    /// Returns the shared secret `y^x mod p` for the other party's public
+    /// value `y`, or `None` if `y` is not in the subgroup of order `q`.
+    ///
+    /// Rejecting such values stops a peer from steering the secret into a
+    /// small subgroup, where it could be guessed.
+    pub fn shared_secret(&self, other: &BigNumber) -> Option<BigNumber> {
+        self.group
+            .contains(other)
+            .then(|| other.modpow(&self.exponent, &self.group.prime))
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("group", &self.group)
+            .field("public", &self.public)
+            .finish_non_exhaustive()
+    }
+}
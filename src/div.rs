@@ -0,0 +1,282 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::biguint::LIMB_BITS;
+use crate::{BigNumber, BigUint, Sign};
+
+/// The error returned by the checked division methods when the divisor is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivisionByZero;
+
+impl fmt::Display for DivisionByZero {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("division by zero")
+    }
+}
+
+impl Error for DivisionByZero {}
+
+impl BigUint {
+    /// Returns the quotient and remainder of `self / other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_rem(&self, other: &BigUint) -> (BigUint, BigUint) {
+        match self.checked_div_rem(other) {
+            Ok(result) => result,
+            Err(_) => panic!("Division by zero"),
+        }
+    }
+
+    /// Returns the quotient and remainder of `self / other`, or an error if
+    /// `other` is zero.
+    pub fn checked_div_rem(&self, other: &BigUint) -> Result<(BigUint, BigUint), DivisionByZero> {
+        if other.is_zero() {
+            return Err(DivisionByZero);
+        }
+
+        if other.limbs.len() == 1 {
+            let mut quotient = self.clone();
+            let remainder = quotient.div_rem_small(other.limbs[0]);
+            return Ok((quotient, BigUint::from_limbs(vec![remainder])));
+        }
+
+        if *self < *other {
+            return Ok((BigUint::zero(), self.clone()));
+        }
+
+        let (quotient, remainder) = div_rem_knuth(&self.limbs, &other.limbs);
+        Ok((BigUint::from_limbs(quotient), BigUint::from_limbs(remainder)))
+    }
+}
+
+// Knuth's Algorithm D (TAOCP 4.3.1) on base 2^32 limbs. The divisor has at least
+// two limbs and the dividend is at least as long as the divisor.
+fn div_rem_knuth(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    const BASE: u64 = 1 << LIMB_BITS;
+
+    // D1: normalize so the top limb of the divisor has its high bit set, which
+    // keeps each quotient estimate at most two too large
+    let shift = v.last().unwrap().leading_zeros();
+    let v = shl_limbs(v, shift);
+    let mut u = shl_limbs(u, shift);
+    u.push(0);
+
+    let n = v.len();
+    let m = u.len() - n - 1;
+    let v_top = v[n - 1] as u64;
+    let v_next = v[n - 2] as u64;
+    let mut quotient = vec![0u32; m + 1];
+
+    for j in (0..=m).rev() {
+        // D3: estimate the quotient limb from the top two limbs of the window
+        let top = ((u[j + n] as u64) << LIMB_BITS) | u[j + n - 1] as u64;
+        let mut q_hat = top / v_top;
+        let mut r_hat = top % v_top;
+        while q_hat >= BASE || q_hat * v_next > ((r_hat << LIMB_BITS) | u[j + n - 2] as u64) {
+            q_hat -= 1;
+            r_hat += v_top;
+            if r_hat >= BASE {
+                break;
+            }
+        }
+
+        // D4: multiply and subtract q_hat * v from the window
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let product = q_hat * v[i] as u64 + carry;
+            carry = product >> LIMB_BITS;
+            let diff = u[i + j] as i64 - (product as u32) as i64 + borrow;
+            u[i + j] = diff as u32;
+            borrow = diff >> LIMB_BITS;
+        }
+        let diff = u[j + n] as i64 - carry as i64 + borrow;
+        u[j + n] = diff as u32;
+
+        // D6: the estimate was one too large, so add the divisor back
+        if diff < 0 {
+            q_hat -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = sum as u32;
+                carry = sum >> LIMB_BITS;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+
+        quotient[j] = q_hat as u32;
+    }
+
+    // D8: the remainder is the low part of the window, shifted back
+    u.truncate(n);
+    (quotient, shr_limbs(&u, shift))
+}
+
+fn shl_limbs(limbs: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+
+    let mut result = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for &limb in limbs {
+        result.push((limb << shift) | carry);
+        carry = limb >> (LIMB_BITS - shift);
+    }
+    if carry > 0 {
+        result.push(carry);
+    }
+    result
+}
+
+fn shr_limbs(limbs: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+
+    let mut result = vec![0u32; limbs.len()];
+    for i in 0..limbs.len() {
+        let high = limbs.get(i + 1).map_or(0, |&next| next << (LIMB_BITS - shift));
+        result[i] = (limbs[i] >> shift) | high;
+    }
+    result
+}
+
+impl BigNumber {
+    /// Returns the truncated quotient and the remainder of `self / other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_rem(&self, other: &BigNumber) -> (BigNumber, BigNumber) {
+        match self.checked_div_rem(other) {
+            Ok(result) => result,
+            Err(_) => panic!("Division by zero"),
+        }
+    }
+
+    /// Returns the quotient of `self / other` rounded towards negative infinity.
+    ///
+    /// `-7 div_floor 2` is `-4`, where truncating division gives `-3`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_floor(&self, other: &BigNumber) -> BigNumber {
+        let (quotient, remainder) = self.div_rem(other);
+        if !remainder.is_zero() && remainder.sign != other.sign {
+            &quotient - &BigNumber::one()
+        } else {
+            quotient
+        }
+    }
+
+    /// Returns the remainder of floored division, which has the sign of `other`.
+    ///
+    /// `-7 mod_floor 2` is `1` and `7 mod_floor -2` is `-1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn mod_floor(&self, other: &BigNumber) -> BigNumber {
+        let remainder = self % other;
+        if !remainder.is_zero() && remainder.sign != other.sign {
+            &remainder + other
+        } else {
+            remainder
+        }
+    }
+
+    /// Returns the quotient of `self / other` rounded towards positive infinity.
+    ///
+    /// `7 div_ceil 2` is `4` and `-7 div_ceil 2` is `-3`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_ceil(&self, other: &BigNumber) -> BigNumber {
+        let (quotient, remainder) = self.div_rem(other);
+        if !remainder.is_zero() && remainder.sign == other.sign {
+            &quotient + &BigNumber::one()
+        } else {
+            quotient
+        }
+    }
+
+    /// Returns the Euclidean quotient, the `q` for which `self = q * other + r`
+    /// with `0 <= r < |other|`.
+    ///
+    /// `-7 div_euclid 2` is `-4` and `-7 div_euclid -2` is `4`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_euclid(&self, other: &BigNumber) -> BigNumber {
+        let (quotient, remainder) = self.div_rem(other);
+        if remainder.is_negative() {
+            match other.sign {
+                Sign::Positive => &quotient - &BigNumber::one(),
+                Sign::Negative => &quotient + &BigNumber::one(),
+            }
+        } else {
+            quotient
+        }
+    }
+
+    /// Returns the Euclidean remainder, which is never negative.
+    ///
+    /// `-7 rem_euclid 2` and `-7 rem_euclid -2` are both `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn rem_euclid(&self, other: &BigNumber) -> BigNumber {
+        let remainder = self % other;
+        if remainder.is_negative() {
+            BigNumber::from(&other.magnitude - &remainder.magnitude)
+        } else {
+            remainder
+        }
+    }
+
+    /// Truncating division, returning `None` if `other` is zero.
+    pub fn checked_div(&self, other: &BigNumber) -> Option<BigNumber> {
+        self.checked_div_rem(other).ok().map(|(quotient, _)| quotient)
+    }
+
+    /// Remainder of truncating division, returning `None` if `other` is zero.
+    ///
+    /// The remainder has the sign of `self`.
+    pub fn checked_rem(&self, other: &BigNumber) -> Option<BigNumber> {
+        self.checked_div_rem(other).ok().map(|(_, remainder)| remainder)
+    }
+
+    /// Returns the truncated quotient and the remainder of `self / other`.
+    ///
+    /// ```
+    /// use bignumber::{BigNumber, DivisionByZero};
+    ///
+    /// let (q, r) = BigNumber::from_string("-7")
+    ///     .checked_div_rem(&BigNumber::from_string("2"))
+    ///     .unwrap();
+    /// assert_eq!((q.to_string(), r.to_string()), ("-3".to_string(), "-1".to_string()));
+    ///
+    /// let zero = BigNumber::from_string("0");
+    /// assert_eq!(BigNumber::from_string("1").checked_div_rem(&zero), Err(DivisionByZero));
+    /// ```
+    pub fn checked_div_rem(
+        &self,
+        other: &BigNumber,
+    ) -> Result<(BigNumber, BigNumber), DivisionByZero> {
+        let (quotient, remainder) = self.magnitude.checked_div_rem(&other.magnitude)?;
+        Ok((
+            BigNumber::from_biguint(self.sign.product(other.sign), quotient),
+            BigNumber::from_biguint(self.sign, remainder),
+        ))
+    }
+}
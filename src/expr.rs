@@ -0,0 +1,421 @@
+//! Arithmetic expressions over big integers.
+//!
+//! [`Expr::parse`] turns a formula such as `(3! + 2^100) % 97` into a syntax
+//! tree, which [`Expr::eval`] or [`Expr::eval_with`] then evaluates. The
+//! operators, from loosest to tightest binding, are:
+//!
+//! - `+` and `-`;
+//! - `*`, `/` and `%`, with division truncating towards zero as for
+//!   [`BigNumber`]'s `/` and `%` operators;
+//! - unary `-`;
+//! - `^` for powers, which groups to the right, so `2^3^2` is `2^9` and
+//!   `-2^2` is `-4`;
+//! - postfix `!` for factorials.
+//!
+//! Numbers are written as in [`BigNumber::parse_literal`], so `0xff` and
+//! `1_000_000` are accepted, and names such as `x` or `_tmp` stand for
+//! variables supplied at evaluation time. Expressions may nest up to
+//! [`MAX_DEPTH`] levels deep, and `^` and `!` refuse to build results of more
+//! than [`MAX_RESULT_BITS`] bits.
+//!
+//! ```
+//! use bignumber::expr::Expr;
+//!
+//! let expr = Expr::parse("(3! + 2^100) % 97").unwrap();
+//! assert_eq!(expr.eval().unwrap().to_string(), "22");
+//! ```
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{BigNumber, ParseBigNumberError};
+
+/// The deepest nesting of parentheses and operators [`Expr::parse`] accepts.
+///
+/// Parsing, evaluating and dropping an expression all recurse through its
+/// tree, so the limit keeps hostile input such as thousands of `(` from
+/// overflowing the stack.
+pub const MAX_DEPTH: usize = 500;
+
+/// The largest result in bits that `^` and `!` may produce, about 1.26
+/// million decimal digits.
+///
+/// Both operators grow their results far faster than their inputs, so a
+/// short formula such as `2^4294967295` could otherwise run for minutes.
+/// The check uses a quick upper bound on the result size, `e` times the bit
+/// length of the base for a power and `n` times the bit length of `n` for
+/// `n!`, so results just under the limit may be rejected too.
+pub const MAX_RESULT_BITS: u64 = 1 << 22;
+
+/// A binary operator of an [`Expr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+/// The syntax tree of an arithmetic expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// A number literal.
+    Number(BigNumber),
+    /// A variable, looked up when the expression is evaluated.
+    Variable(String),
+    /// Unary minus.
+    Neg(Box<Expr>),
+    /// Postfix `!`.
+    Factorial(Box<Expr>),
+    /// An operator applied to two operands.
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+/// The error returned when a string is not a valid expression.
+///
+/// Positions are byte offsets into the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseExprError {
+    /// The input ended where an operand or a `)` was expected.
+    UnexpectedEnd,
+    /// The input contained a character that no token starts with.
+    UnexpectedCharacter {
+        /// Byte offset of the character.
+        position: usize,
+        /// The offending character.
+        character: char,
+    },
+    /// A token appeared where it cannot, such as a second operator in a row
+    /// or a `)` without a matching `(`.
+    UnexpectedToken {
+        /// Byte offset of the token.
+        position: usize,
+    },
+    /// A `(` was never closed.
+    UnclosedParenthesis {
+        /// Byte offset of the `(`.
+        position: usize,
+    },
+    /// The expression nests parentheses or operators more than
+    /// [`MAX_DEPTH`] levels deep.
+    TooDeep {
+        /// Byte offset of the token that went past the limit.
+        position: usize,
+    },
+    /// A token starting with a digit is not a valid number.
+    InvalidNumber {
+        /// Byte offset of the number.
+        position: usize,
+        /// Why the number did not parse.
+        error: ParseBigNumberError,
+    },
+}
+
+impl fmt::Display for ParseExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseExprError::UnexpectedEnd => f.write_str("unexpected end of expression"),
+            ParseExprError::UnexpectedCharacter { position, character } => {
+                write!(f, "unexpected character {:?} at position {}", character, position)
+            }
+            ParseExprError::UnexpectedToken { position } => {
+                write!(f, "unexpected token at position {}", position)
+            }
+            ParseExprError::UnclosedParenthesis { position } => {
+                write!(f, "unclosed parenthesis at position {}", position)
+            }
+            ParseExprError::TooDeep { position } => {
+                write!(f, "expression nested too deeply at position {}", position)
+            }
+            ParseExprError::InvalidNumber { position, error } => {
+                write!(f, "invalid number at position {}: {}", position, error)
+            }
+        }
+    }
+}
+
+impl Error for ParseExprError {}
+
+/// The error returned when an expression cannot be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    /// A `/` or `%` had a zero divisor.
+    DivisionByZero,
+    /// A `^` had a negative exponent.
+    NegativeExponent,
+    /// A `^` could give a result of more than [`MAX_RESULT_BITS`] bits.
+    ExponentTooLarge,
+    /// A `!` was applied to a negative number.
+    NegativeFactorial,
+    /// A `!` could give a result of more than [`MAX_RESULT_BITS`] bits.
+    FactorialTooLarge,
+    /// The expression used a variable with no value.
+    UnknownVariable(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::DivisionByZero => f.write_str("division by zero"),
+            EvalError::NegativeExponent => f.write_str("negative exponent"),
+            EvalError::ExponentTooLarge => f.write_str("exponent too large"),
+            EvalError::NegativeFactorial => f.write_str("factorial of a negative number"),
+            EvalError::FactorialTooLarge => f.write_str("factorial argument too large"),
+            EvalError::UnknownVariable(name) => write!(f, "unknown variable {}", name),
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+impl Expr {
+    /// Parses an expression.
+    ///
+    /// ```
+    /// use bignumber::expr::{BinaryOp, Expr, ParseExprError};
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(
+    ///     Expr::parse("x * 2").unwrap(),
+    ///     Expr::Binary(
+    ///         BinaryOp::Mul,
+    ///         Box::new(Expr::Variable("x".to_string())),
+    ///         Box::new(Expr::Number(BigNumber::from(2))),
+    ///     )
+    /// );
+    /// assert_eq!(Expr::parse("1 + * 2"), Err(ParseExprError::UnexpectedToken { position: 4 }));
+    /// ```
+    pub fn parse(input: &str) -> Result<Expr, ParseExprError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens: &tokens, next: 0 };
+        let (expr, _) = parser.expr(0, 0)?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some((position, _)) => Err(ParseExprError::UnexpectedToken { position }),
+        }
+    }
+
+    /// Evaluates an expression without variables.
+    ///
+    /// ```
+    /// use bignumber::expr::{EvalError, Expr};
+    ///
+    /// assert_eq!(Expr::parse("-7 / 2").unwrap().eval().unwrap().to_string(), "-3");
+    /// assert_eq!(Expr::parse("1 % 0").unwrap().eval(), Err(EvalError::DivisionByZero));
+    /// ```
+    pub fn eval(&self) -> Result<BigNumber, EvalError> {
+        self.eval_with(|_| None)
+    }
+
+    /// Evaluates an expression, taking the value of each variable from
+    /// `variables`, which returns `None` for names without one.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use bignumber::expr::Expr;
+    /// use bignumber::BigNumber;
+    ///
+    /// let mut variables = HashMap::new();
+    /// variables.insert("x", BigNumber::from(10));
+    ///
+    /// let expr = Expr::parse("x^2 - x").unwrap();
+    /// let value = expr.eval_with(|name| variables.get(name).cloned()).unwrap();
+    /// assert_eq!(value.to_string(), "90");
+    /// ```
+    pub fn eval_with<F: Fn(&str) -> Option<BigNumber>>(&self, variables: F) -> Result<BigNumber, EvalError> {
+        self.evaluate(&variables)
+    }
+
+    fn evaluate(&self, variables: &dyn Fn(&str) -> Option<BigNumber>) -> Result<BigNumber, EvalError> {
+        match self {
+            Expr::Number(value) => Ok(value.clone()),
+            Expr::Variable(name) => variables(name).ok_or_else(|| EvalError::UnknownVariable(name.clone())),
+            Expr::Neg(operand) => Ok(-operand.evaluate(variables)?),
+            Expr::Factorial(operand) => {
+                let n = operand.evaluate(variables)?;
+                if n.is_negative() {
+                    return Err(EvalError::NegativeFactorial);
+                }
+                // n! < n^n, which has at most n times the bits of n
+                match n.to_u64().filter(|&n| n.saturating_mul(bit_length(n)) <= MAX_RESULT_BITS) {
+                    Some(n) => Ok(BigNumber::factorial(n)),
+                    None => Err(EvalError::FactorialTooLarge),
+                }
+            }
+            Expr::Binary(op, left, right) => {
+                let left = left.evaluate(variables)?;
+                let right = right.evaluate(variables)?;
+                match op {
+                    BinaryOp::Add => Ok(&left + &right),
+                    BinaryOp::Sub => Ok(&left - &right),
+                    BinaryOp::Mul => Ok(&left * &right),
+                    BinaryOp::Div => left.checked_div(&right).ok_or(EvalError::DivisionByZero),
+                    BinaryOp::Rem => left.checked_rem(&right).ok_or(EvalError::DivisionByZero),
+                    BinaryOp::Pow => {
+                        if right.is_negative() {
+                            return Err(EvalError::NegativeExponent);
+                        }
+                        // 0, 1 and -1 stay small under any power, and only the
+                        // parity of the exponent matters for them
+                        if left.magnitude().bits() <= 1 {
+                            let exponent = if right.is_zero() { 0 } else { 2 - right.bit(0) as u64 };
+                            return Ok(left.pow(exponent));
+                        }
+                        // |left|^e has at most e times the bits of |left|
+                        let exponent = right
+                            .to_u64()
+                            .filter(|&e| e.saturating_mul(left.bits()) <= MAX_RESULT_BITS)
+                            .ok_or(EvalError::ExponentTooLarge)?;
+                        Ok(left.pow(exponent))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseExprError;
+
+    fn from_str(input: &str) -> Result<Expr, ParseExprError> {
+        Expr::parse(input)
+    }
+}
+
+// The number of bits in n, which is zero for zero
+fn bit_length(n: u64) -> u64 {
+    (u64::BITS - n.leading_zeros()) as u64
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(BigNumber),
+    Name(String),
+    Symbol(char),
+}
+
+// Splits the input into tokens paired with their byte offsets
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, ParseExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            let mut end = start;
+            while let Some((i, c)) = chars.next_if(|&(_, c)| c.is_ascii_alphanumeric() || c == '_') {
+                end = i + c.len_utf8();
+            }
+            let word = &input[start..end];
+            let token = if c.is_ascii_digit() {
+                let number = BigNumber::parse_literal(word)
+                    .map_err(|error| ParseExprError::InvalidNumber { position: start, error })?;
+                Token::Number(number)
+            } else {
+                Token::Name(word.to_string())
+            };
+            tokens.push((start, token));
+        } else if "+-*/%^!()".contains(c) {
+            tokens.push((start, Token::Symbol(c)));
+            chars.next();
+        } else {
+            return Err(ParseExprError::UnexpectedCharacter { position: start, character: c });
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Binding powers for the Pratt parser; an infix operator's right operand is
+// parsed at its own power, or one above for left associative operators
+const SUM_POWER: u8 = 1;
+const PRODUCT_POWER: u8 = 2;
+const NEG_POWER: u8 = 3;
+const POW_POWER: u8 = 4;
+const FACTORIAL_POWER: u8 = 5;
+
+struct Parser<'a> {
+    tokens: &'a [(usize, Token)],
+    next: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<(usize, &Token)> {
+        self.tokens.get(self.next).map(|(position, token)| (*position, token))
+    }
+
+    // Parses an expression whose operators all bind at least as tightly as
+    // `min_power`, returning it with the height of its tree. `depth` counts the
+    // enclosing parentheses and operators, so that both the recursion here and
+    // the trees built stay within MAX_DEPTH.
+    fn expr(&mut self, min_power: u8, depth: usize) -> Result<(Expr, usize), ParseExprError> {
+        let (position, token) = self.tokens.get(self.next).ok_or(ParseExprError::UnexpectedEnd)?;
+        let position = *position;
+        if depth >= MAX_DEPTH {
+            return Err(ParseExprError::TooDeep { position });
+        }
+        self.next += 1;
+        let (mut left, mut height) = match token {
+            Token::Number(value) => (Expr::Number(value.clone()), 1),
+            Token::Name(name) => (Expr::Variable(name.clone()), 1),
+            Token::Symbol('-') => {
+                let (operand, height) = self.expr(NEG_POWER, depth + 1)?;
+                (Expr::Neg(Box::new(operand)), height + 1)
+            }
+            Token::Symbol('(') => {
+                let inner = self.expr(0, depth + 1)?;
+                match self.peek() {
+                    Some((_, Token::Symbol(')'))) => self.next += 1,
+                    _ => return Err(ParseExprError::UnclosedParenthesis { position }),
+                }
+                inner
+            }
+            Token::Symbol(_) => return Err(ParseExprError::UnexpectedToken { position }),
+        };
+
+        while let Some((position, Token::Symbol(symbol))) = self.peek() {
+            let (op, power, right_power) = match symbol {
+                '+' => (BinaryOp::Add, SUM_POWER, SUM_POWER + 1),
+                '-' => (BinaryOp::Sub, SUM_POWER, SUM_POWER + 1),
+                '*' => (BinaryOp::Mul, PRODUCT_POWER, PRODUCT_POWER + 1),
+                '/' => (BinaryOp::Div, PRODUCT_POWER, PRODUCT_POWER + 1),
+                '%' => (BinaryOp::Rem, PRODUCT_POWER, PRODUCT_POWER + 1),
+                // The exponent may start with a unary minus, which the error
+                // for negative exponents then reports
+                '^' => (BinaryOp::Pow, POW_POWER, NEG_POWER),
+                '!' if FACTORIAL_POWER >= min_power => {
+                    self.next += 1;
+                    left = Expr::Factorial(Box::new(left));
+                    height += 1;
+                    if depth + height > MAX_DEPTH {
+                        return Err(ParseExprError::TooDeep { position });
+                    }
+                    continue;
+                }
+                _ => break,
+            };
+            if power < min_power {
+                break;
+            }
+            self.next += 1;
+            let (right, right_height) = self.expr(right_power, depth + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+            // Long chains such as 1 + 1 + ... + 1 nest to the left without
+            // recursing here, so their height is checked as well
+            height = height.max(right_height) + 1;
+            if depth + height > MAX_DEPTH {
+                return Err(ParseExprError::TooDeep { position });
+            }
+        }
+
+        Ok((left, height))
+    }
+}
@@ -0,0 +1,441 @@
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "rand")]
+use rand::RngCore;
+
+use crate::root::exact_prime_root;
+use crate::sieve::{small_primes, SIEVE_LIMIT};
+use crate::siqs::siqs;
+use crate::{BigNumber, BigUint, MontgomeryContext};
+#[cfg(feature = "rand")]
+use crate::random::random_below;
+
+// Factors multiplied together before each gcd, in rho and in stage two of p - 1
+const GCD_BATCH: usize = 128;
+// Polynomial constants tried before rho gives up
+const RHO_ATTEMPTS: u32 = 20;
+// Numbers with more limbs than this get a short run of rho before the
+// quadratic sieve takes over
+const RHO_ONLY_LIMBS: usize = 3;
+// Cycle length rho is allowed to reach when the quadratic sieve comes next
+const RHO_SHORT_RUN: usize = 1 << 16;
+
+/// Limits for [`BigNumber::factorize_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FactorOptions {
+    /// Gives up once this much time has passed; `None` means no limit.
+    /// Needs the `std` feature for a clock.
+    #[cfg(feature = "std")]
+    pub time_limit: Option<Duration>,
+    /// Stage one and stage two bounds for Pollard's `p - 1` method.
+    pub pm1_bounds: (u64, u64),
+}
+
+impl Default for FactorOptions {
+    fn default() -> FactorOptions {
+        FactorOptions {
+            #[cfg(feature = "std")]
+            time_limit: None,
+            pm1_bounds: (10_000, 500_000),
+        }
+    }
+}
+
+// How far a factoring method may go before giving up
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Budget {
+    pub(crate) deadline: Deadline,
+    pub(crate) max_steps: Option<usize>,
+}
+
+impl Budget {
+    pub(crate) const UNLIMITED: Budget = Budget {
+        deadline: Deadline::NONE,
+        max_steps: None,
+    };
+
+    pub(crate) fn exhausted(&self, steps: usize) -> bool {
+        self.max_steps.is_some_and(|max| steps > max)
+            || self.deadline.passed()
+    }
+}
+
+// The point in time a factorization gives up at, if any. Without `std` there is
+// no clock, and the deadline never passes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Deadline {
+    #[cfg(feature = "std")]
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    pub(crate) const NONE: Deadline = Deadline {
+        #[cfg(feature = "std")]
+        at: None,
+    };
+
+    #[cfg(feature = "std")]
+    fn after(limit: Option<Duration>) -> Deadline {
+        Deadline {
+            at: limit.map(|limit| Instant::now() + limit),
+        }
+    }
+
+    pub(crate) fn passed(&self) -> bool {
+        #[cfg(feature = "std")]
+        return self.at.is_some_and(|at| Instant::now() >= at);
+        #[cfg(not(feature = "std"))]
+        false
+    }
+}
+
+impl BigNumber {
+    /// Returns the prime factorization of the number as `(prime, exponent)`
+    /// pairs in increasing order of the primes.
+    ///
+    /// The sign is ignored, and `0` and `±1` have no factors. Small factors are
+    /// found by trial division and the rest with Pollard's `p - 1` and rho
+    /// methods, moving on to the quadratic sieve for large cofactors. Every
+    /// returned factor passes [`is_prime`](BigNumber::is_prime).
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let factors = BigNumber::from_string("-360").factorize();
+    /// let factors: Vec<(String, u32)> = factors.iter().map(|(p, e)| (p.to_string(), *e)).collect();
+    /// assert_eq!(factors, [("2".to_string(), 3), ("3".to_string(), 2), ("5".to_string(), 1)]);
+    /// ```
+    pub fn factorize(&self) -> Vec<(BigNumber, u32)> {
+        // Without a time limit `split` keeps trying until it finds a factor
+        match self.factorize_with(&FactorOptions::default()) {
+            Some(factors) => factors,
+            None => unreachable!("factorization without a time limit gave up"),
+        }
+    }
+
+    /// Like [`factorize`](BigNumber::factorize), but returns `None` if the
+    /// time limit in `options` runs out first.
+    pub fn factorize_with(&self, options: &FactorOptions) -> Option<Vec<(BigNumber, u32)>> {
+        #[cfg(feature = "std")]
+        let deadline = Deadline::after(options.time_limit);
+        #[cfg(not(feature = "std"))]
+        let deadline = Deadline::NONE;
+        let mut primes = Vec::new();
+        let mut composites = Vec::new();
+
+        let remaining = trial_divide(&self.magnitude, &mut primes);
+        if remaining != BigUint::one() {
+            composites.push(remaining);
+        }
+
+        while let Some(n) = composites.pop() {
+            if BigNumber::from(n.clone()).is_prime() {
+                primes.push(n);
+                continue;
+            }
+
+            let factor = split(&n, options.pm1_bounds, deadline)?;
+            composites.push(&n / &factor);
+            composites.push(factor);
+        }
+
+        primes.sort();
+        let mut factors: Vec<(BigNumber, u32)> = Vec::new();
+        for prime in primes {
+            match factors.last_mut() {
+                Some((last, exponent)) if last.magnitude == prime => *exponent += 1,
+                _ => factors.push((BigNumber::from(prime), 1)),
+            }
+        }
+        Some(factors)
+    }
+
+    /// Looks for a non-trivial factor of the number with Pollard's rho method,
+    /// using Brent's cycle detection.
+    ///
+    /// Returns `None` for primes, for `0` and `±1`, and if every attempt fails.
+    /// The factor found is positive but not necessarily prime. Rho needs about
+    /// `sqrt(p)` steps to find a prime factor `p`, so it is practical for
+    /// numbers of up to about 30 digits or with a small factor.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("10403");
+    /// let factor = n.factor_rho().unwrap();
+    /// assert!(factor.to_string() == "101" || factor.to_string() == "103");
+    /// ```
+    pub fn factor_rho(&self) -> Option<BigNumber> {
+        let n = &self.magnitude;
+        if *n <= BigUint::one() || self.is_prime() {
+            return None;
+        }
+        if n.is_even() {
+            return Some(BigNumber::from_small(2));
+        }
+
+        (1..=RHO_ATTEMPTS)
+            .find_map(|c| brent_rho(n, &BigUint::from(c), &BigUint::from(2u32), Budget::UNLIMITED))
+            .map(BigNumber::from)
+    }
+
+    /// Like [`factor_rho`](BigNumber::factor_rho), but draws the polynomial
+    /// constants and starting points from `rng` instead of trying a fixed
+    /// sequence, so that repeated runs can take different paths. A seeded
+    /// generator makes the result reproducible.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let n = BigNumber::from_string("10403");
+    /// let factor = n.factor_rho_with(&mut StdRng::seed_from_u64(1)).unwrap();
+    /// assert_eq!(Some(factor), n.factor_rho_with(&mut StdRng::seed_from_u64(1)));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn factor_rho_with<R: RngCore + ?Sized>(&self, rng: &mut R) -> Option<BigNumber> {
+        let n = &self.magnitude;
+        if *n <= BigUint::one() || self.is_prime() {
+            return None;
+        }
+        if n.is_even() {
+            return Some(BigNumber::from_small(2));
+        }
+
+        // Constants are drawn from 1..=n-3, avoiding the degenerate 0 and -2
+        let constants = n - &BigUint::from(3u32);
+        (0..RHO_ATTEMPTS)
+            .find_map(|_| {
+                let mut c = random_below(rng, &constants);
+                c.add_small(1);
+                let start = random_below(rng, n);
+                brent_rho(n, &c, &start, Budget::UNLIMITED)
+            })
+            .map(BigNumber::from)
+    }
+
+    /// Looks for a non-trivial factor of the number with Pollard's `p - 1` method.
+    ///
+    /// This finds a prime factor `p` when `p - 1` is a product of prime powers up
+    /// to `b1` with at most one extra prime up to `b2`, no matter how large `p`
+    /// is. Both bounds are capped at the size of the internal prime table, 10^6.
+    /// Returns `None` for `0`, `±1`, primes, and when no factor is found within
+    /// the bounds.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// // 1000003 - 1 = 2 * 3 * 166667, so the second stage needs to reach 166667
+    /// let n = BigNumber::from_string("1000003000000000000000000103000309");
+    /// assert_eq!(n.factor_pm1(1000, 1000), None);
+    /// assert_eq!(n.factor_pm1(1000, 200_000).unwrap().to_string(), "1000003");
+    /// ```
+    pub fn factor_pm1(&self, b1: u64, b2: u64) -> Option<BigNumber> {
+        let n = &self.magnitude;
+        if *n <= BigUint::one() || self.is_prime() {
+            return None;
+        }
+        if n.is_even() {
+            return Some(BigNumber::from_small(2));
+        }
+
+        pollard_pm1(n, b1, b2.max(b1)).map(BigNumber::from)
+    }
+}
+
+// Pollard's p - 1 on odd composite n. Stage one raises 2 to every prime power up
+// to b1; stage two then tries each prime q in (b1, b2] as the one large factor,
+// stepping between consecutive primes with cached powers of the gaps.
+fn pollard_pm1(n: &BigUint, b1: u64, b2: u64) -> Option<BigUint> {
+    // Only tabulated primes are used, and keeping b1 small keeps the prime
+    // powers below it from overflowing
+    let b1 = b1.min(SIEVE_LIMIT as u64);
+    let b2 = b2.min(SIEVE_LIMIT as u64);
+    let ctx = MontgomeryContext::new(n).unwrap();
+    let one = ctx.to_montgomery(&BigUint::one());
+    // x - 1 for x in Montgomery form, which is (x - 1) R and has the same gcd with n
+    let minus_one = |x: &BigUint| if *x >= one { x - &one } else { &(x + n) - &one };
+
+    let mut a = ctx.to_montgomery(&BigUint::from_limbs(vec![2]));
+    let primes = small_primes();
+    let stage_one = primes.partition_point(|&p| (p as u64) <= b1);
+    for &p in &primes[..stage_one] {
+        let mut power = p as u64;
+        while power * p as u64 <= b1 {
+            power *= p as u64;
+        }
+        a = ctx.pow(&a, &BigUint::from_limbs(vec![power as u32, (power >> 32) as u32]));
+    }
+
+    let g = minus_one(&a).gcd(n);
+    if g != BigUint::one() {
+        return (g != *n).then_some(g);
+    }
+
+    let stage_two = primes.partition_point(|&p| (p as u64) <= b2);
+    let &first = primes[stage_one..stage_two].first()?;
+
+    // a^gap for every even gap seen so far, indexed by gap / 2
+    let mut gap_powers: Vec<BigUint> = Vec::new();
+    let mut x = ctx.pow(&a, &BigUint::from_limbs(vec![first]));
+    let mut product = one.clone();
+    let mut previous = first;
+
+    for (i, &q) in primes[stage_one..stage_two].iter().enumerate() {
+        if q != first {
+            let half_gap = ((q - previous) / 2) as usize;
+            while gap_powers.len() < half_gap {
+                let exponent = 2 * (gap_powers.len() as u32 + 1);
+                gap_powers.push(ctx.pow(&a, &BigUint::from_limbs(vec![exponent])));
+            }
+            x = ctx.mul(&x, &gap_powers[half_gap - 1]);
+            previous = q;
+        }
+
+        product = ctx.mul(&product, &minus_one(&x));
+        if (i + 1) % GCD_BATCH == 0 || q == primes[stage_two - 1] {
+            let g = product.gcd(n);
+            if g != BigUint::one() {
+                return (g != *n).then_some(g);
+            }
+        }
+    }
+
+    None
+}
+
+// One run of Brent's rho on odd composite n with x -> x^2 + c from `start`,
+// evaluated in Montgomery form. The constant factors R that form introduces are
+// coprime to n and leave the gcds unchanged.
+fn brent_rho(n: &BigUint, c: &BigUint, start: &BigUint, budget: Budget) -> Option<BigUint> {
+    let ctx = MontgomeryContext::new(n).unwrap();
+    let c = ctx.to_montgomery(c);
+    let step = |x: &BigUint| {
+        let mut next = ctx.square(x);
+        next.add_assign_magnitude(&c);
+        if next >= *n {
+            next.sub_assign_unchecked(n);
+        }
+        next
+    };
+    let distance = |a: &BigUint, b: &BigUint| if a >= b { a - b } else { b - a };
+
+    let mut y = ctx.to_montgomery(start);
+    let mut x = y.clone();
+    let mut saved = y.clone();
+    let mut product = ctx.to_montgomery(&BigUint::one());
+    let mut g = BigUint::one();
+    let mut r = 1;
+
+    while g == BigUint::one() {
+        if budget.exhausted(r) {
+            return None;
+        }
+        x = y.clone();
+        for _ in 0..r {
+            y = step(&y);
+        }
+
+        let mut k = 0;
+        while k < r && g == BigUint::one() {
+            saved = y.clone();
+            for _ in 0..GCD_BATCH.min(r - k) {
+                y = step(&y);
+                product = ctx.mul(&product, &distance(&x, &y));
+            }
+            g = product.gcd(n);
+            k += GCD_BATCH;
+        }
+        r *= 2;
+    }
+
+    // The batch overshot into the cycle, so replay it one step at a time
+    if g == *n {
+        loop {
+            saved = step(&saved);
+            g = distance(&x, &saved).gcd(n);
+            if g != BigUint::one() {
+                break;
+            }
+        }
+    }
+
+    if g == *n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+// Divides out every prime from the sieve table, pushing one entry per prime
+// factor, and returns the cofactor
+fn trial_divide(n: &BigUint, primes: &mut Vec<BigUint>) -> BigUint {
+    let mut remaining = n.clone();
+    if remaining.is_zero() {
+        return BigUint::one();
+    }
+
+    for &p in small_primes() {
+        // Stop once remaining < 2^(2 floor(log2 p)) <= p^2
+        if remaining.bits() <= 2 * (31 - p.leading_zeros() as u64) {
+            break;
+        }
+        while remaining.rem_small(p) == 0 {
+            remaining.div_rem_small(p);
+            primes.push(BigUint::from_limbs(vec![p]));
+        }
+    }
+
+    remaining
+}
+
+// Finds a non-trivial factor of a composite with no factors in the sieve
+// table. Returns `None` only if the deadline passes first; without one, rho
+// keeps trying new constants until a factor turns up.
+fn split(n: &BigUint, pm1_bounds: (u64, u64), deadline: Deadline) -> Option<BigUint> {
+    let max_steps = (n.limbs.len() > RHO_ONLY_LIMBS).then_some(RHO_SHORT_RUN);
+    let budget = Budget { deadline, max_steps };
+    if budget.exhausted(0) {
+        return None;
+    }
+
+    // Prime powers defeat both rho, whose cycles modulo each prime factor
+    // coincide, and the quadratic sieve, so take exact roots first
+    if let Some((root, _)) = exact_prime_root(n) {
+        return Some(root);
+    }
+
+    if let Some(factor) = pollard_pm1(n, pm1_bounds.0, pm1_bounds.1) {
+        return Some(factor);
+    }
+
+    // A capped run that fails has most likely hit its cap, which another
+    // constant would hit as well
+    let attempts = if max_steps.is_some() { 1 } else { RHO_ATTEMPTS };
+    let start = BigUint::from(2u32);
+    if let Some(factor) = (1..=attempts).find_map(|c| brent_rho(n, &BigUint::from(c), &start, budget)) {
+        return Some(factor);
+    }
+
+    if let Some(factor) = siqs(n, deadline) {
+        return Some(factor);
+    }
+
+    // Every quicker route failed, so run rho without a step cap on fresh
+    // constants until one splits n or the deadline passes
+    let budget = Budget { deadline, max_steps: None };
+    let mut c = attempts + 1;
+    loop {
+        if budget.exhausted(0) {
+            return None;
+        }
+        if let Some(factor) = brent_rho(n, &BigUint::from(c), &start, budget) {
+            return Some(factor);
+        }
+        c += 1;
+    }
+}
@@ -0,0 +1,251 @@
+//! A C interface, behind the `ffi` feature.
+//!
+//! Numbers are handed out as opaque `bignumber_t *` handles that the caller
+//! releases with [`bignumber_free`], and strings as `char *` released with
+//! [`bignumber_string_free`]. Fallible functions return one of the
+//! `BIGNUMBER_*` status codes and write their result through an out pointer,
+//! which is left untouched on failure. `include/bignumber.h` declares the
+//! same functions for C and C++, and
+//! `cargo rustc --release --lib --features ffi --crate-type cdylib` builds the
+//! shared library.
+
+#![allow(non_camel_case_types)]
+
+use core::cmp::Ordering;
+use core::ffi::{c_char, c_int};
+use std::ffi::{CStr, CString};
+use std::panic::{catch_unwind, UnwindSafe};
+
+use crate::BigNumber;
+
+/// The call succeeded.
+pub const BIGNUMBER_OK: c_int = 0;
+/// A required pointer argument was null.
+pub const BIGNUMBER_NULL_POINTER: c_int = 1;
+/// The input string is not valid UTF-8 or not a number in the given radix.
+pub const BIGNUMBER_PARSE_ERROR: c_int = 2;
+/// The divisor is zero.
+pub const BIGNUMBER_DIVISION_BY_ZERO: c_int = 3;
+/// The radix is not between 2 and 36.
+pub const BIGNUMBER_INVALID_RADIX: c_int = 4;
+/// The operation panicked, which should not happen.
+pub const BIGNUMBER_INTERNAL_ERROR: c_int = 5;
+
+/// The opaque number type behind a `bignumber_t *` handle.
+pub struct bignumber_t(BigNumber);
+
+// Runs `f`, turning a panic into a status code so it cannot unwind into C
+fn guard(f: impl FnOnce() -> c_int + UnwindSafe) -> c_int {
+    catch_unwind(f).unwrap_or(BIGNUMBER_INTERNAL_ERROR)
+}
+
+// Boxes `value` into a handle and writes it to `out`
+unsafe fn store(out: *mut *mut bignumber_t, value: BigNumber) -> c_int {
+    *out = Box::into_raw(Box::new(bignumber_t(value)));
+    BIGNUMBER_OK
+}
+
+// Applies a binary operation to two handles, storing the result in `out`
+unsafe fn binary(
+    a: *const bignumber_t,
+    b: *const bignumber_t,
+    out: *mut *mut bignumber_t,
+    op: impl FnOnce(&BigNumber, &BigNumber) -> Result<BigNumber, c_int> + UnwindSafe,
+) -> c_int {
+    if a.is_null() || b.is_null() || out.is_null() {
+        return BIGNUMBER_NULL_POINTER;
+    }
+    let (a, b) = (&(*a).0, &(*b).0);
+    guard(|| match op(a, b) {
+        Ok(value) => store(out, value),
+        Err(status) => status,
+    })
+}
+
+/// Returns a new handle holding `value`. Never returns null.
+#[no_mangle]
+pub extern "C" fn bignumber_from_i64(value: i64) -> *mut bignumber_t {
+    Box::into_raw(Box::new(bignumber_t(BigNumber::from(value))))
+}
+
+/// Returns a new handle holding the same value as `n`, or null if `n` is null.
+///
+/// # Safety
+///
+/// `n` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_clone(n: *const bignumber_t) -> *mut bignumber_t {
+    if n.is_null() {
+        return core::ptr::null_mut();
+    }
+    Box::into_raw(Box::new(bignumber_t((*n).0.clone())))
+}
+
+/// Releases a handle; null is ignored.
+///
+/// # Safety
+///
+/// `n` must be null or a live handle, which is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_free(n: *mut bignumber_t) {
+    if !n.is_null() {
+        drop(Box::from_raw(n));
+    }
+}
+
+/// Parses the NUL-terminated string `input` in `radix`, with an optional
+/// leading `-`, into a new handle written to `out`.
+///
+/// # Safety
+///
+/// `input` must be null or a NUL-terminated string, and `out` null or valid
+/// for writing a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_parse(
+    input: *const c_char,
+    radix: u32,
+    out: *mut *mut bignumber_t,
+) -> c_int {
+    if input.is_null() || out.is_null() {
+        return BIGNUMBER_NULL_POINTER;
+    }
+    if !(2..=36).contains(&radix) {
+        return BIGNUMBER_INVALID_RADIX;
+    }
+    let input = CStr::from_ptr(input);
+    guard(|| match input.to_str().ok().and_then(|s| BigNumber::from_str_radix(s, radix).ok()) {
+        Some(value) => store(out, value),
+        None => BIGNUMBER_PARSE_ERROR,
+    })
+}
+
+/// Formats `n` in `radix` with lowercase letters, writing a new NUL-terminated
+/// string to `out`; release it with [`bignumber_string_free`].
+///
+/// # Safety
+///
+/// `n` must be null or a live handle, and `out` null or valid for writing a
+/// pointer.
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_to_string(
+    n: *const bignumber_t,
+    radix: u32,
+    out: *mut *mut c_char,
+) -> c_int {
+    if n.is_null() || out.is_null() {
+        return BIGNUMBER_NULL_POINTER;
+    }
+    if !(2..=36).contains(&radix) {
+        return BIGNUMBER_INVALID_RADIX;
+    }
+    let n = &(*n).0;
+    guard(|| {
+        // Digits and `-` never contain a NUL byte
+        *out = CString::new(n.to_str_radix(radix)).unwrap().into_raw();
+        BIGNUMBER_OK
+    })
+}
+
+/// Releases a string returned by [`bignumber_to_string`]; null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string from [`bignumber_to_string`] that has not been
+/// released yet.
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Writes a new handle holding `a + b` to `out`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` null or valid for
+/// writing a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_add(
+    a: *const bignumber_t,
+    b: *const bignumber_t,
+    out: *mut *mut bignumber_t,
+) -> c_int {
+    binary(a, b, out, |a, b| Ok(a + b))
+}
+
+/// Writes a new handle holding `a - b` to `out`.
+///
+/// # Safety
+///
+/// As for [`bignumber_add`].
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_sub(
+    a: *const bignumber_t,
+    b: *const bignumber_t,
+    out: *mut *mut bignumber_t,
+) -> c_int {
+    binary(a, b, out, |a, b| Ok(a - b))
+}
+
+/// Writes a new handle holding `a * b` to `out`.
+///
+/// # Safety
+///
+/// As for [`bignumber_add`].
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_mul(
+    a: *const bignumber_t,
+    b: *const bignumber_t,
+    out: *mut *mut bignumber_t,
+) -> c_int {
+    binary(a, b, out, |a, b| Ok(a * b))
+}
+
+/// Writes a new handle holding `a / b`, rounded towards zero as in C, to `out`.
+///
+/// # Safety
+///
+/// As for [`bignumber_add`].
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_div(
+    a: *const bignumber_t,
+    b: *const bignumber_t,
+    out: *mut *mut bignumber_t,
+) -> c_int {
+    binary(a, b, out, |a, b| a.checked_div(b).ok_or(BIGNUMBER_DIVISION_BY_ZERO))
+}
+
+/// Writes a new handle holding `a % b`, with the sign of `a` as in C, to `out`.
+///
+/// # Safety
+///
+/// As for [`bignumber_add`].
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_rem(
+    a: *const bignumber_t,
+    b: *const bignumber_t,
+    out: *mut *mut bignumber_t,
+) -> c_int {
+    binary(a, b, out, |a, b| a.checked_rem(b).ok_or(BIGNUMBER_DIVISION_BY_ZERO))
+}
+
+/// Writes -1, 0 or 1 to `out` as `a` is less than, equal to or greater than
+/// `b`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` null or valid for
+/// writing an `int`.
+#[no_mangle]
+pub unsafe extern "C" fn bignumber_cmp(a: *const bignumber_t, b: *const bignumber_t, out: *mut c_int) -> c_int {
+    if a.is_null() || b.is_null() || out.is_null() {
+        return BIGNUMBER_NULL_POINTER;
+    }
+    *out = match (*a).0.cmp(&(*b).0) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    };
+    BIGNUMBER_OK
+}
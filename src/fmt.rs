@@ -0,0 +1,444 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::parse::{check_radix, digits_per_limb};
+use crate::{BarrettContext, BigNumber, BigUint};
+
+impl BigUint {
+    /// Returns the digits of the number in the given radix, using lowercase
+    /// letters for the digits from ten upwards.
+    ///
+    /// ```
+    /// use bignumber::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(255u32).to_str_radix(16), "ff");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        self.display_radix(radix).to_string()
+    }
+
+    /// Returns a wrapper that formats the number in the given radix with
+    /// [`Display`](fmt::Display); see [`DisplayRadix`] for when it allocates.
+    ///
+    /// ```
+    /// use bignumber::BigUint;
+    ///
+    /// let n = BigUint::from(48879u32);
+    /// assert_eq!(format!("0x{} = {}", n.display_radix(16), n), "0xbeef = 48879");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    pub fn display_radix(&self, radix: u32) -> DisplayRadix<'_> {
+        check_radix(radix);
+        DisplayRadix {
+            negative: false,
+            magnitude: self,
+            radix,
+        }
+    }
+
+    /// Returns the decimal digits with `separator` inserted between every
+    /// `group` digits, counting from the right.
+    ///
+    /// ```
+    /// use bignumber::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(1234567u32).to_grouped_string(',', 3), "1,234,567");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `group` is zero.
+    pub fn to_grouped_string(&self, separator: char, group: usize) -> String {
+        assert!(group > 0, "digit groups need at least one digit");
+
+        let digits = self.to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / group * separator.len_utf8());
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(group) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+}
+
+impl BigNumber {
+    /// Returns the digits of the number in the given radix with a leading `-`
+    /// if it is negative, using lowercase letters for the digits from ten
+    /// upwards.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from(-255).to_str_radix(2), "-11111111");
+    /// assert_eq!(BigNumber::from(35).to_str_radix(36), "z");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        self.display_radix(radix).to_string()
+    }
+
+    /// Returns a wrapper that formats the number in the given radix with
+    /// [`Display`](fmt::Display); see [`DisplayRadix`] for when it allocates.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from(-1295);
+    /// assert_eq!(format!("[{:>6}]", n.display_radix(36)), "[   -zz]");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    pub fn display_radix(&self, radix: u32) -> DisplayRadix<'_> {
+        DisplayRadix {
+            negative: self.is_negative(),
+            ..self.magnitude.display_radix(radix)
+        }
+    }
+
+    /// Returns the decimal digits with `separator` inserted between every
+    /// `group` digits, counting from the right, and a leading `-` if the number
+    /// is negative.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("-12345678901");
+    /// assert_eq!(n.to_grouped_string('_', 3), "-12_345_678_901");
+    /// assert_eq!(n.to_grouped_string(' ', 4), "-123 4567 8901");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `group` is zero.
+    pub fn to_grouped_string(&self, separator: char, group: usize) -> String {
+        let digits = self.magnitude.to_grouped_string(separator, group);
+        if self.is_negative() {
+            format!("-{}", digits)
+        } else {
+            digits
+        }
+    }
+}
+
+/// Formats a number in an arbitrary radix; see [`BigNumber::display_radix`].
+///
+/// Digits from ten upwards are lowercase letters. Width, fill, alignment and
+/// `+` are honoured like for the primitive integers.
+///
+/// Numbers of up to 128 bits are converted in a buffer on the stack and never
+/// allocate. Larger numbers in a power-of-two radix are written straight from
+/// their bits, while in other radices the conversion works on a copy of the
+/// number, split in halves with precomputed powers of the radix once it is
+/// very large. Padding a number larger than 128 bits also goes through a
+/// temporary string.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayRadix<'a> {
+    negative: bool,
+    magnitude: &'a BigUint,
+    radix: u32,
+}
+
+impl DisplayRadix<'_> {
+    fn write_digits(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let radix = self.radix;
+        if radix.is_power_of_two() {
+            // Read each digit straight out of the bits
+            let digit_bits = radix.trailing_zeros() as u64;
+            let digits = self.magnitude.bits().max(1).div_ceil(digit_bits);
+            let mut buffer = [0u8; 64];
+            let mut len = 0;
+            for i in (0..digits).rev() {
+                let digit = (0..digit_bits)
+                    .filter(|&j| self.magnitude.bit(i * digit_bits + j))
+                    .fold(0, |acc, j| acc | 1 << j);
+                buffer[len] = core::char::from_digit(digit, radix).unwrap() as u8;
+                len += 1;
+                if len == buffer.len() || i == 0 {
+                    out.write_str(core::str::from_utf8(&buffer[..len]).unwrap())?;
+                    len = 0;
+                }
+            }
+            return Ok(());
+        }
+
+        if self.magnitude.limbs.len() < SPLIT_DIGITS_LIMBS {
+            return write_chunks(self.magnitude, radix, None, out);
+        }
+
+        // Split around the powers base^(2^i) of the chunk base, up to the
+        // first one past the number, so each half converts separately
+        let mut powers = vec![BigUint::from(radix.pow(digits_per_limb(radix) as u32))];
+        while powers.last().unwrap() <= self.magnitude {
+            let power = powers.last().unwrap();
+            powers.push(power * power);
+        }
+        powers.pop();
+        let powers: Vec<_> = powers.iter().map(|power| BarrettContext::new(power).unwrap()).collect();
+        write_split(self.magnitude, &powers, radix, None, out)
+    }
+}
+
+// Numbers with at least this many limbs are converted by splitting them in half
+// recursively, which turns the quadratic cost of peeling off one chunk at a
+// time into that of a few large multiplications
+const SPLIT_DIGITS_LIMBS: usize = 256;
+
+// Writes the digits of `n < powers.last()^2`, zero padded to `width` digits if
+// given
+fn write_split(
+    n: &BigUint,
+    powers: &[BarrettContext],
+    radix: u32,
+    width: Option<usize>,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    let Some((divisor, lower)) = powers.split_last() else {
+        return write_chunks(n, radix, width, out);
+    };
+    if n.limbs.len() < SPLIT_DIGITS_LIMBS {
+        return write_chunks(n, radix, width, out);
+    }
+
+    // The divisor is radix^half_width, so the low half fills exactly that many
+    // digits
+    let half_width = digits_per_limb(radix) << lower.len();
+    let (high, low) = divisor.div_rem(n);
+    if width.is_some() || !high.is_zero() {
+        write_split(&high, lower, radix, width.map(|width| width - half_width), out)?;
+        write_split(&low, lower, radix, Some(half_width), out)
+    } else {
+        write_split(&low, lower, radix, None, out)
+    }
+}
+
+// Writes the digits of `n`, zero padded to `width` digits if given, by peeling
+// off the largest power of the radix that fits in a limb at a time, least
+// significant chunk first
+fn write_chunks(n: &BigUint, radix: u32, width: Option<usize>, out: &mut impl fmt::Write) -> fmt::Result {
+    let chunk_digits = digits_per_limb(radix);
+    let chunk_base = radix.pow(chunk_digits as u32);
+    let mut chunks = Vec::new();
+    let mut rest = n.clone();
+    loop {
+        chunks.push(rest.div_rem_small(chunk_base));
+        if rest.is_zero() && width.is_none_or(|width| chunks.len() * chunk_digits >= width) {
+            break;
+        }
+    }
+
+    let mut buffer = [0u8; 32];
+    let mut chunks = chunks.iter().rev();
+    let first_width = match width {
+        Some(width) => width - (chunks.len() - 1) * chunk_digits,
+        None => 1,
+    };
+    out.write_str(chunk_str(*chunks.next().unwrap(), radix, first_width, &mut buffer))?;
+    for &chunk in chunks {
+        out.write_str(chunk_str(chunk, radix, chunk_digits, &mut buffer))?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for DisplayRadix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(value) = self.magnitude.to_u128() {
+            let mut buffer = [0u8; 128];
+            return f.pad_integral(!self.negative, "", small_digits(value, self.radix, &mut buffer));
+        }
+
+        if f.width().is_none() && !f.sign_plus() {
+            if self.negative {
+                f.write_str("-")?;
+            }
+            return self.write_digits(f);
+        }
+
+        let mut digits = String::new();
+        self.write_digits(&mut digits)?;
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+// The digits of `value` in `radix`, written to the end of `buffer` one limb
+// sized chunk at a time; 128 bytes hold even the 128 binary digits of u128::MAX
+fn small_digits(mut value: u128, radix: u32, buffer: &mut [u8; 128]) -> &str {
+    let chunk_digits = digits_per_limb(radix);
+    let chunk_base = radix.pow(chunk_digits as u32) as u128;
+    let mut start = buffer.len();
+    loop {
+        let mut chunk = (value % chunk_base) as u32;
+        value /= chunk_base;
+        // Chunks below the leading one keep their leading zeros
+        let width = if value == 0 { 1 } else { chunk_digits };
+        let end = start;
+        while chunk > 0 || end - start < width {
+            start -= 1;
+            buffer[start] = core::char::from_digit(chunk % radix, radix).unwrap() as u8;
+            chunk /= radix;
+        }
+        if value == 0 {
+            return core::str::from_utf8(&buffer[start..]).unwrap();
+        }
+    }
+}
+
+// The digits of `value` in `radix`, zero padded to at least `width` digits
+fn chunk_str(mut value: u32, radix: u32, width: usize, buffer: &mut [u8; 32]) -> &str {
+    let mut start = buffer.len();
+    while value > 0 || buffer.len() - start < width {
+        start -= 1;
+        buffer[start] = core::char::from_digit(value % radix, radix).unwrap() as u8;
+        value /= radix;
+    }
+    core::str::from_utf8(&buffer[start..]).unwrap()
+}
+
+// Formats like the primitive integers, honouring width, fill, alignment, `+`
+// and zero padding
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.display_radix(10), f)
+    }
+}
+
+impl fmt::Display for BigNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.display_radix(10), f)
+    }
+}
+
+// Implements a radix formatting trait for both types in terms of
+// `to_str_radix`, printing negative numbers with a leading `-` rather than in
+// two's complement
+macro_rules! radix_fmt {
+    (impl $imp:ident, $radix:expr, $prefix:expr, $upper:expr) => {
+        impl fmt::$imp for BigUint {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut digits = self.to_str_radix($radix);
+                if $upper {
+                    digits.make_ascii_uppercase();
+                }
+                f.pad_integral(true, $prefix, &digits)
+            }
+        }
+
+        impl fmt::$imp for BigNumber {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut digits = self.magnitude.to_str_radix($radix);
+                if $upper {
+                    digits.make_ascii_uppercase();
+                }
+                f.pad_integral(!self.is_negative(), $prefix, &digits)
+            }
+        }
+    };
+}
+
+radix_fmt!(impl Binary, 2, "0b", false);
+radix_fmt!(impl Octal, 8, "0o", false);
+radix_fmt!(impl LowerHex, 16, "0x", false);
+radix_fmt!(impl UpperHex, 16, "0x", true);
+
+// Implements a scientific notation formatting trait for both types. The
+// precision flag sets the number of digits after the point, rounding half to
+// even like the primitive integers; without it the mantissa is exact.
+macro_rules! exp_fmt {
+    (impl $imp:ident, $marker:expr) => {
+        impl fmt::$imp for BigUint {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.pad_integral(true, "", &scientific(self, f.precision(), $marker))
+            }
+        }
+
+        impl fmt::$imp for BigNumber {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let digits = scientific(&self.magnitude, f.precision(), $marker);
+                f.pad_integral(!self.is_negative(), "", &digits)
+            }
+        }
+    };
+}
+
+exp_fmt!(impl LowerExp, 'e');
+exp_fmt!(impl UpperExp, 'E');
+
+// Decimal digits kept beyond the requested precision before rounding
+const EXP_GUARD_DIGITS: usize = 4;
+
+// Formats `n` as `d.ddde<exponent>`, with `precision` digits after the point
+// or as many as needed to be exact
+fn scientific(n: &BigUint, precision: Option<usize>, marker: char) -> String {
+    // Only the leading digits matter when the precision is given, so divide
+    // off a power of ten that leaves a few guard digits and remember whether
+    // anything nonzero was dropped. 2^(bits - 1) <= n puts a lower bound on the
+    // number of digits.
+    let (mut digits, mut exponent, sticky) = match precision {
+        Some(precision) if n.bits() > 64 => {
+            let min_digits = ((n.bits() - 1) as f64 * core::f64::consts::LOG10_2) as usize + 1;
+            let dropped = min_digits.saturating_sub(precision + 1 + EXP_GUARD_DIGITS);
+            let (leading, rest) = n.div_rem(&BigUint::pow10(dropped));
+            (leading.to_string().into_bytes(), dropped, !rest.is_zero())
+        }
+        _ => (n.to_string().into_bytes(), 0, false),
+    };
+    exponent += digits.len() - 1;
+
+    match precision {
+        Some(precision) if digits.len() > precision + 1 => {
+            let kept = precision + 1;
+            let round_up = match digits[kept] {
+                b'0'..=b'4' => false,
+                b'6'..=b'9' => true,
+                _ => sticky || digits[kept + 1..].iter().any(|&d| d != b'0') || (digits[kept - 1] - b'0') % 2 == 1,
+            };
+            digits.truncate(kept);
+            if round_up {
+                // Propagate the carry; all nines become a one with the
+                // exponent bumped
+                match digits.iter().rposition(|&d| d != b'9') {
+                    Some(i) => {
+                        digits[i] += 1;
+                        digits[i + 1..].fill(b'0');
+                    }
+                    None => {
+                        digits.fill(b'0');
+                        digits[0] = b'1';
+                        exponent += 1;
+                    }
+                }
+            }
+        }
+        Some(precision) => digits.resize(precision + 1, b'0'),
+        None => {
+            let significant = digits.iter().rposition(|&d| d != b'0').unwrap_or(0);
+            digits.truncate(significant + 1);
+        }
+    }
+
+    let mut out = String::with_capacity(digits.len() + 24);
+    out.push(digits[0] as char);
+    if digits.len() > 1 {
+        out.push('.');
+        out.extend(digits[1..].iter().map(|&d| d as char));
+    }
+    out.push(marker);
+    out.push_str(&exponent.to_string());
+    out
+}
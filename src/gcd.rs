@@ -0,0 +1,242 @@
+use alloc::vec::Vec;
+
+use crate::biguint::LIMB_BITS;
+use crate::{BigNumber, BigUint};
+
+// Operand size, in limbs of the smaller operand, from which Lehmer's algorithm
+// beats the binary algorithm
+const LEHMER_THRESHOLD: usize = 16;
+
+impl BigUint {
+    /// Returns the greatest common divisor of `self` and `other`.
+    ///
+    /// `gcd(n, 0)` is `n`, so `gcd(0, 0)` is `0`.
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let short = self.limbs.len().min(other.limbs.len());
+        if self.limbs.len() == 1 && other.limbs.len() == 1 {
+            euclid_gcd(self.clone(), other.clone())
+        } else if short < LEHMER_THRESHOLD {
+            binary_gcd(self.clone(), other.clone())
+        } else {
+            lehmer_gcd(self.clone(), other.clone())
+        }
+    }
+
+    /// Returns the least common multiple of `self` and `other`.
+    ///
+    /// The lcm with zero is zero.
+    pub fn lcm(&self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+
+        // Dividing first keeps the intermediate no larger than the result
+        &(self / &self.gcd(other)) * other
+    }
+}
+
+impl BigNumber {
+    /// Returns the greatest common divisor of `self` and `other`, which is never
+    /// negative.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let a = BigNumber::from_string("-84");
+    /// let b = BigNumber::from_string("36");
+    /// assert_eq!(a.gcd(&b).to_string(), "12");
+    /// ```
+    pub fn gcd(&self, other: &BigNumber) -> BigNumber {
+        BigNumber::from(self.magnitude.gcd(&other.magnitude))
+    }
+
+    /// Returns `(g, x, y)` where `g` is the gcd of `self` and `other` and `x`,
+    /// `y` are Bézout coefficients with `self * x + other * y == g`.
+    ///
+    /// `g` is never negative. The coefficients come from the extended Euclidean
+    /// algorithm, so `|x| <= |other / g|` and `|y| <= |self / g|`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let a = BigNumber::from_string("240");
+    /// let b = BigNumber::from_string("46");
+    /// let (g, x, y) = a.extended_gcd(&b);
+    /// assert_eq!(g.to_string(), "2");
+    /// assert_eq!(&(&a * &x) + &(&b * &y), g);
+    /// ```
+    pub fn extended_gcd(&self, other: &BigNumber) -> (BigNumber, BigNumber, BigNumber) {
+        let (mut old_r, mut r) = (
+            BigNumber::from(self.magnitude.clone()),
+            BigNumber::from(other.magnitude.clone()),
+        );
+        let (mut old_x, mut x) = (BigNumber::one(), BigNumber::zero());
+        let (mut old_y, mut y) = (BigNumber::zero(), BigNumber::one());
+
+        while !r.is_zero() {
+            let (q, remainder) = old_r.div_rem(&r);
+            old_r = core::mem::replace(&mut r, remainder);
+            let next_x = &old_x - &(&q * &x);
+            old_x = core::mem::replace(&mut x, next_x);
+            let next_y = &old_y - &(&q * &y);
+            old_y = core::mem::replace(&mut y, next_y);
+        }
+
+        // The loop ran on absolute values, so fold the signs into the coefficients
+        if self.is_negative() {
+            old_x = -old_x;
+        }
+        if other.is_negative() {
+            old_y = -old_y;
+        }
+
+        (old_r, old_x, old_y)
+    }
+
+    /// Returns the inverse of the number modulo `modulus`, the `x` in
+    /// `0..|modulus|` with `self * x = 1 (mod modulus)`, or `None` if the two
+    /// are not coprime.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let m = BigNumber::from_string("3120");
+    /// assert_eq!(BigNumber::from_string("17").mod_inverse(&m).unwrap().to_string(), "2753");
+    /// assert_eq!(BigNumber::from_string("-17").mod_inverse(&m).unwrap().to_string(), "367");
+    /// assert_eq!(BigNumber::from_string("15").mod_inverse(&m), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn mod_inverse(&self, modulus: &BigNumber) -> Option<BigNumber> {
+        let (g, x, _) = self.rem_euclid(modulus).extended_gcd(modulus);
+        (g == BigNumber::one()).then(|| x.rem_euclid(modulus))
+    }
+
+    /// Returns the least common multiple of `self` and `other`, which is never
+    /// negative.
+    ///
+    /// The lcm with zero is zero.
+    pub fn lcm(&self, other: &BigNumber) -> BigNumber {
+        BigNumber::from(self.magnitude.lcm(&other.magnitude))
+    }
+
+    /// Returns the least common multiple of all `values`, or one for an empty slice.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let values: Vec<BigNumber> = ["4", "6", "10"].iter().map(|s| s.parse().unwrap()).collect();
+    /// assert_eq!(BigNumber::lcm_all(&values).to_string(), "60");
+    /// ```
+    pub fn lcm_all(values: &[BigNumber]) -> BigNumber {
+        values.iter().fold(BigNumber::one(), |acc, value| acc.lcm(value))
+    }
+}
+
+// The Euclidean algorithm: gcd(a, b) = gcd(b, a mod b)
+fn euclid_gcd(mut a: BigUint, mut b: BigUint) -> BigUint {
+    while !b.is_zero() {
+        let remainder = &a % &b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+// Stein's algorithm: strips common factors of two, then repeatedly subtracts the
+// smaller odd value from the larger, using only shifts and subtraction
+pub(crate) fn binary_gcd(mut a: BigUint, mut b: BigUint) -> BigUint {
+    if a.is_zero() {
+        return b;
+    }
+    if b.is_zero() {
+        return a;
+    }
+
+    let shift = a.trailing_zeros().min(b.trailing_zeros());
+    a.shr_bits(a.trailing_zeros());
+
+    loop {
+        b.shr_bits(b.trailing_zeros());
+        if a > b {
+            core::mem::swap(&mut a, &mut b);
+        }
+        b.sub_assign_unchecked(&a);
+        if b.is_zero() {
+            break;
+        }
+    }
+
+    a.shl_bits(shift);
+    a
+}
+
+// Lehmer's algorithm (Knuth's Algorithm L): runs the Euclidean algorithm on the
+// leading 32 bits of both operands for as long as the quotients provably match
+// the full ones, then applies the accumulated cofactors in a single pass
+pub(crate) fn lehmer_gcd(mut a: BigUint, mut b: BigUint) -> BigUint {
+    if a < b {
+        core::mem::swap(&mut a, &mut b);
+    }
+
+    while b.limbs.len() > 1 {
+        let shift = a.bits().saturating_sub(LIMB_BITS as u64);
+        let mut a_high = leading_bits(&a, shift);
+        let mut b_high = leading_bits(&b, shift);
+
+        // Cofactors such that the true values are a' = x0 a + y0 b, b' = x1 a + y1 b
+        let (mut x0, mut y0, mut x1, mut y1) = (1i64, 0i64, 0i64, 1i64);
+        while b_high + x1 != 0 && b_high + y1 != 0 {
+            let q = (a_high + x0) / (b_high + x1);
+            if q != (a_high + y0) / (b_high + y1) {
+                break;
+            }
+            (x0, x1) = (x1, x0 - q * x1);
+            (y0, y1) = (y1, y0 - q * y1);
+            (a_high, b_high) = (b_high, a_high - q * b_high);
+        }
+
+        if y0 == 0 {
+            // No quotient could be trusted, so take a full Euclidean step
+            let remainder = &a % &b;
+            a = b;
+            b = remainder;
+        } else {
+            let next_a = linear_combination(&a, x0, &b, y0);
+            b = linear_combination(&a, x1, &b, y1);
+            a = next_a;
+        }
+    }
+
+    euclid_gcd(a, b)
+}
+
+// The 32 bits of `n` starting at bit `shift`
+fn leading_bits(n: &BigUint, shift: u64) -> i64 {
+    let index = (shift / LIMB_BITS as u64) as usize;
+    let offset = shift % LIMB_BITS as u64;
+    let low = n.limbs.get(index).copied().unwrap_or(0) as u64;
+    let high = n.limbs.get(index + 1).copied().unwrap_or(0) as u64;
+    ((((high << LIMB_BITS) | low) >> offset) as u32) as i64
+}
+
+// Returns `x a + y b`; the caller guarantees the result is non-negative
+fn linear_combination(a: &BigUint, x: i64, b: &BigUint, y: i64) -> BigUint {
+    let len = a.limbs.len().max(b.limbs.len());
+    let mut limbs = Vec::with_capacity(len + 1);
+    let mut carry = 0i128;
+
+    for i in 0..len {
+        let a_limb = a.limbs.get(i).copied().unwrap_or(0) as i128;
+        let b_limb = b.limbs.get(i).copied().unwrap_or(0) as i128;
+        let value = a_limb * x as i128 + b_limb * y as i128 + carry;
+        limbs.push(value as u32);
+        carry = value >> LIMB_BITS;
+    }
+
+    debug_assert!(carry >= 0);
+    limbs.push(carry as u32);
+    BigUint::from_limbs(limbs)
+}
@@ -0,0 +1,370 @@
+//! Arbitrary precision integers.
+//!
+//! [`BigUint`] is an unsigned magnitude that implements the digit level
+//! algorithms, and [`BigNumber`] (also available as [`BigInt`]) pairs a
+//! magnitude with a [`Sign`].
+//!
+//! ```
+//! use bignumber::BigNumber;
+//!
+//! let a: BigNumber = "99999999999999999999".parse().unwrap();
+//! let b = BigNumber::from_string("1");
+//! let sum = &a + &b;
+//! assert_eq!(sum.to_string(), "100000000000000000000");
+//! ```
+//!
+//! The default `std` feature can be turned off to build with only `alloc`,
+//! which leaves out the time limit of [`FactorOptions`] and reading numbers
+//! from a `std::io::Read` with `BigNumber::read_decimal`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec;
+use core::cmp::Ordering;
+
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+mod arbitrary;
+#[cfg(feature = "rkyv")]
+mod archive;
+mod barrett;
+mod biguint;
+mod bitwise;
+mod bytes;
+pub mod cli;
+mod cmp;
+mod convert;
+mod combinatorics;
+#[cfg(feature = "rand")]
+pub mod dh;
+mod div;
+pub mod expr;
+mod factor;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fmt;
+mod gcd;
+mod montgomery;
+mod mul;
+mod multiplicative;
+mod ntt;
+#[cfg(feature = "num-traits")]
+mod num;
+mod ops;
+mod parse;
+mod pell;
+mod pow;
+mod prime;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "rand")]
+mod random;
+mod residue;
+mod root;
+#[cfg(feature = "rand")]
+pub mod rsa;
+mod sequence;
+#[cfg(feature = "serde")]
+mod serde;
+mod sieve;
+mod siqs;
+#[cfg(feature = "zeroize")]
+mod zeroize;
+
+#[cfg(test)]
+mod tests;
+
+pub use barrett::BarrettContext;
+pub use biguint::BigUint;
+#[cfg(feature = "rkyv")]
+pub use biguint::ArchivedBigUint;
+pub use bytes::FromBinError;
+pub use convert::{TryFromBigNumberError, TryFromF64Error};
+pub use div::DivisionByZero;
+pub use factor::FactorOptions;
+pub use fmt::DisplayRadix;
+pub use montgomery::MontgomeryContext;
+pub use parse::ParseBigNumberError;
+
+/// The sign of a [`BigNumber`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, PartialEq, Eq))
+)]
+pub enum Sign {
+    Positive,
+    Negative,
+}
+
+impl Sign {
+    pub(crate) fn negated(self) -> Sign {
+        match self {
+            Sign::Positive => Sign::Negative,
+            Sign::Negative => Sign::Positive,
+        }
+    }
+
+    // The sign of a product or quotient of values with signs `self` and `other`
+    pub(crate) fn product(self, other: Sign) -> Sign {
+        if self == other {
+            Sign::Positive
+        } else {
+            Sign::Negative
+        }
+    }
+}
+
+/// An arbitrary precision signed integer.
+///
+/// The value is stored as a [`BigUint`] magnitude together with a [`Sign`].
+/// Zero is always positive, so every value has exactly one representation.
+///
+/// The bitwise operators `&`, `|` and `^` treat the number as two's complement
+/// of unbounded width, like the primitive signed integers: negative numbers have
+/// infinitely many leading one bits. In the same way `<<` and `>>` are binary
+/// shifts, with `>>` rounding towards negative infinity. The `{:b}`, `{:o}`,
+/// `{:x}` and `{:X}` formats, on the other hand, print negative numbers as a
+/// `-` followed by the digits of the magnitude.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize), rkyv(derive(Debug)))]
+pub struct BigNumber {
+    sign: Sign,
+    magnitude: BigUint,
+}
+
+/// An alias for [`BigNumber`], mirroring the naming of [`BigUint`].
+pub type BigInt = BigNumber;
+
+impl BigNumber {
+    /// Returns the number zero.
+    pub fn zero() -> BigNumber {
+        BigNumber::from_biguint(Sign::Positive, BigUint::zero())
+    }
+
+    /// Returns the number one.
+    pub fn one() -> BigNumber {
+        BigNumber::from_biguint(Sign::Positive, BigUint::one())
+    }
+
+    /// Builds a number from a sign and a magnitude.
+    ///
+    /// A zero magnitude always produces a positive zero.
+    pub fn from_biguint(sign: Sign, magnitude: BigUint) -> BigNumber {
+        let mut number = BigNumber { sign, magnitude };
+        number.normalize();
+        number
+    }
+
+    // Builds a non-negative number from a single limb
+    pub(crate) fn from_small(value: u32) -> BigNumber {
+        BigNumber::from(BigUint::from_limbs(vec![value]))
+    }
+
+    /// Returns the sign of the number; zero is always [`Sign::Positive`].
+    pub fn sign(&self) -> Sign {
+        self.sign
+    }
+
+    /// Returns the absolute value of the number as a [`BigUint`].
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    /// Makes the number non-negative in place.
+    pub fn make_abs(&mut self) {
+        self.sign = Sign::Positive;
+    }
+
+    /// Multiplies the number by `10^n` in place.
+    pub fn shift_left(&mut self, n: usize) {
+        self.magnitude.shift_left_digits(n);
+    }
+
+    /// Divides the number by `10^n` in place, discarding the dropped digits.
+    pub fn shift_right(&mut self, n: usize) {
+        self.magnitude.shift_right_digits(n);
+        self.normalize();
+    }
+
+    /// Parses a decimal string with an optional leading `-`.
+    ///
+    /// This is a panicking shorthand for [`BigNumber::parse`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input is not a valid decimal integer.
+    pub fn from_string(input: &str) -> Self {
+        match BigNumber::parse(input) {
+            Ok(number) => number,
+            Err(err) => panic!("invalid number {:?}: {}", input, err),
+        }
+    }
+
+    /// Returns `true` if `self >= other`.
+    pub fn is_greater_than_or_equal_to(&self, other: &BigNumber) -> bool {
+        self.cmp(other) != Ordering::Less
+    }
+
+    /// Returns `self - other`.
+    #[must_use]
+    pub fn subtract(&self, other: &BigNumber) -> BigNumber {
+        let mut result = self.clone();
+        result.subtract_in_place(other);
+        result
+    }
+
+    /// Subtracts `other` from the number in place.
+    pub fn subtract_in_place(&mut self, other: &BigNumber) {
+        self.add_signed(other.sign.negated(), &other.magnitude);
+    }
+
+    /// Returns `self + other`.
+    ///
+    /// Note that while [`core::ops::Add`] is in scope, `a.add(&b)` on an owned `a`
+    /// resolves to the operator trait and consumes `a`; write `&a + &b` instead.
+    #[must_use]
+    pub fn add(&self, other: &BigNumber) -> BigNumber {
+        let mut result = self.clone();
+        result.add_in_place(other);
+        result
+    }
+
+    /// Adds `other` to the number in place.
+    pub fn add_in_place(&mut self, other: &BigNumber) {
+        self.add_signed(other.sign, &other.magnitude);
+    }
+
+    // Adds the value `sign * magnitude` to the number in place
+    fn add_signed(&mut self, sign: Sign, magnitude: &BigUint) {
+        if self.sign == sign {
+            self.magnitude.add_assign_magnitude(magnitude);
+        } else if self.magnitude < *magnitude {
+            let mut result = magnitude.clone();
+            result.sub_assign_unchecked(&self.magnitude);
+            self.magnitude = result;
+            self.sign = sign;
+        } else {
+            self.magnitude.sub_assign_unchecked(magnitude);
+        }
+
+        self.normalize();
+    }
+
+    /// Multiplies the number by a small integer in place.
+    pub fn multiply_by_int(&mut self, other: i32) {
+        self.sign = self.sign.product(if other < 0 { Sign::Negative } else { Sign::Positive });
+        self.magnitude.mul_small(other.unsigned_abs());
+        self.normalize();
+    }
+
+    /// Returns `self * other`.
+    #[must_use]
+    pub fn multiply(&self, other: &BigNumber) -> BigNumber {
+        BigNumber::from_biguint(
+            self.sign.product(other.sign),
+            self.magnitude.mul_magnitude(&other.magnitude),
+        )
+    }
+
+    /// Multiplies the number by `other` in place.
+    pub fn multiply_in_place(&mut self, other: &BigNumber) {
+        *self = self.multiply(other);
+    }
+
+    // Restores the canonical form by making zero positive
+    fn normalize(&mut self) {
+        if self.magnitude.is_zero() {
+            self.sign = Sign::Positive;
+        }
+    }
+
+    /// Returns the number of binary digits in the magnitude, so that
+    /// `2^(bits - 1) <= |self| < 2^bits`; zero has none.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("-255").bits(), 8);
+    /// assert_eq!(BigNumber::from_string("256").bits(), 9);
+    /// assert_eq!(BigNumber::zero().bits(), 0);
+    /// ```
+    pub fn bits(&self) -> u64 {
+        self.magnitude.bits()
+    }
+
+    /// Returns `true` if the number is divisible by `divisor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn is_divisible_by(&self, divisor: &BigNumber) -> bool {
+        if divisor.is_zero() {
+            panic!("Division by zero");
+        }
+
+        (self % divisor).is_zero()
+    }
+
+    /// Returns `true` if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    /// Returns `true` if the sign is positive.
+    pub fn is_positive(&self) -> bool {
+        self.sign == Sign::Positive
+    }
+
+    /// Returns `true` if the sign is negative.
+    pub fn is_negative(&self) -> bool {
+        self.sign == Sign::Negative
+    }
+
+    /// Returns `true` if both numbers have the same value.
+    pub fn is_equal_to(&self, other: &BigNumber) -> bool {
+        self == other
+    }
+
+    /// Returns `true` if `self <= other`.
+    pub fn is_less_than_or_equal_to(&self, other: &BigNumber) -> bool {
+        self.cmp(other) != Ordering::Greater
+    }
+
+    /// Returns `true` if `self < other`.
+    pub fn is_less_than(&self, other: &BigNumber) -> bool {
+        self.cmp(other) == Ordering::Less
+    }
+
+    /// Returns the quotient of the number divided by `divisor`, rounded towards zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    #[must_use]
+    pub fn divide(&self, divisor: &BigNumber) -> BigNumber {
+        self / divisor
+    }
+
+    /// Returns the remainder of the number divided by `divisor`, with the sign of
+    /// the number, so that `self == self.divide(divisor) * divisor + self.rem(divisor)`.
+    ///
+    /// See [`BigNumber::mod_floor`] and [`BigNumber::rem_euclid`] for the other
+    /// sign conventions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    #[must_use]
+    pub fn rem(&self, divisor: &BigNumber) -> BigNumber {
+        self % divisor
+    }
+}
+
+impl From<BigUint> for BigNumber {
+    fn from(magnitude: BigUint) -> BigNumber {
+        BigNumber::from_biguint(Sign::Positive, magnitude)
+    }
+}
@@ -0,0 +1,155 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::biguint::LIMB_BITS;
+use crate::BigUint;
+
+/// Precomputed parameters for Montgomery arithmetic modulo a fixed odd number.
+///
+/// Values are kept in Montgomery form, `x * R mod m` with `R = 2^(32 * limbs)`,
+/// where a product can be reduced with shifts and single-limb multiplies instead
+/// of a full division. Convert in and out with [`to_montgomery`] and
+/// [`from_montgomery`]. The other methods take values in Montgomery form, which
+/// are always below the modulus; they do not reduce their inputs, so anything
+/// larger gives a wrong result, and panics in debug builds.
+///
+/// ```
+/// use bignumber::{BigUint, MontgomeryContext};
+///
+/// let m: BigUint = "1000000007".parse().unwrap();
+/// let ctx = MontgomeryContext::new(&m).unwrap();
+/// let a = ctx.to_montgomery(&"123456789".parse().unwrap());
+/// let b = ctx.to_montgomery(&"987654321".parse().unwrap());
+/// assert_eq!(ctx.from_montgomery(&ctx.mul(&a, &b)).to_string(), "259106859");
+/// ```
+///
+/// [`to_montgomery`]: MontgomeryContext::to_montgomery
+/// [`from_montgomery`]: MontgomeryContext::from_montgomery
+#[derive(Debug, Clone)]
+pub struct MontgomeryContext {
+    modulus: BigUint,
+    // -m^-1 mod 2^32
+    neg_inverse: u32,
+    // R^2 mod m, for converting into Montgomery form
+    r_squared: BigUint,
+}
+
+impl MontgomeryContext {
+    /// Creates a context for `modulus`, or `None` if it is even.
+    pub fn new(modulus: &BigUint) -> Option<MontgomeryContext> {
+        if modulus.is_even() {
+            return None;
+        }
+        let low = modulus.limbs[0];
+
+        // Newton's iteration doubles the number of correct low bits each step
+        let mut inverse = 1u32;
+        for _ in 0..5 {
+            inverse = inverse.wrapping_mul(2u32.wrapping_sub(low.wrapping_mul(inverse)));
+        }
+
+        let mut r_squared = vec![0u32; 2 * modulus.limbs.len()];
+        r_squared.push(1);
+        let r_squared = &BigUint::from_limbs(r_squared) % modulus;
+
+        Some(MontgomeryContext {
+            modulus: modulus.clone(),
+            neg_inverse: inverse.wrapping_neg(),
+            r_squared,
+        })
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Converts `x` into Montgomery form, reducing it first if needed.
+    pub fn to_montgomery(&self, x: &BigUint) -> BigUint {
+        let reduced = x % &self.modulus;
+        self.mul(&reduced, &self.r_squared)
+    }
+
+    /// Converts a value in Montgomery form back to an ordinary residue.
+    ///
+    /// `x` must be below the modulus.
+    pub fn from_montgomery(&self, x: &BigUint) -> BigUint {
+        debug_assert!(*x < self.modulus, "Montgomery input not below the modulus");
+        BigUint::from_limbs(self.redc_mul(&x.limbs, &[1]))
+    }
+
+    /// Multiplies two values in Montgomery form.
+    ///
+    /// `a` and `b` must be below the modulus.
+    pub fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        debug_assert!(
+            *a < self.modulus && *b < self.modulus,
+            "Montgomery input not below the modulus"
+        );
+        BigUint::from_limbs(self.redc_mul(&a.limbs, &b.limbs))
+    }
+
+    /// Squares a value in Montgomery form.
+    ///
+    /// `a` must be below the modulus.
+    pub fn square(&self, a: &BigUint) -> BigUint {
+        self.mul(a, a)
+    }
+
+    /// Raises a value in Montgomery form to `exponent`, returning the result in
+    /// Montgomery form.
+    ///
+    /// `base` must be below the modulus; `exponent` can be any size.
+    pub fn pow(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        let mut result = self.to_montgomery(&BigUint::one());
+        for i in (0..exponent.bits()).rev() {
+            result = self.square(&result);
+            if exponent.bit(i) {
+                result = self.mul(&result, base);
+            }
+        }
+        result
+    }
+
+    // Computes a * b / R mod m with interleaved multiplication and reduction
+    // (the CIOS method); both inputs must be below the modulus
+    fn redc_mul(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        let m = &self.modulus.limbs;
+        let n = m.len();
+        let mut t = vec![0u32; n + 2];
+
+        for i in 0..n {
+            let b_limb = b.get(i).copied().unwrap_or(0) as u64;
+
+            let mut carry = 0u64;
+            for (j, t_limb) in t.iter_mut().enumerate().take(n) {
+                let a_limb = a.get(j).copied().unwrap_or(0) as u64;
+                let sum = *t_limb as u64 + a_limb * b_limb + carry;
+                *t_limb = sum as u32;
+                carry = sum >> LIMB_BITS;
+            }
+            let sum = t[n] as u64 + carry;
+            t[n] = sum as u32;
+            t[n + 1] = (sum >> LIMB_BITS) as u32;
+
+            // Add a multiple of m that clears the low limb, then drop it
+            let factor = t[0].wrapping_mul(self.neg_inverse) as u64;
+            let mut carry = (t[0] as u64 + factor * m[0] as u64) >> LIMB_BITS;
+            for j in 1..n {
+                let sum = t[j] as u64 + factor * m[j] as u64 + carry;
+                t[j - 1] = sum as u32;
+                carry = sum >> LIMB_BITS;
+            }
+            let sum = t[n] as u64 + carry;
+            t[n - 1] = sum as u32;
+            t[n] = t[n + 1] + (sum >> LIMB_BITS) as u32;
+        }
+
+        t.truncate(n + 1);
+        let mut result = BigUint::from_limbs(t);
+        if result >= self.modulus {
+            result.sub_assign_unchecked(&self.modulus);
+        }
+        core::mem::take(&mut result.limbs)
+    }
+}
@@ -0,0 +1,209 @@
+//! The multiplication algorithm ladder.
+//!
+//! Products are computed on raw limb slices. Small operands use schoolbook
+//! multiplication, medium ones Karatsuba, large ones Toom-Cook 3-way, and very
+//! large ones (around 100k decimal digits and up) a number-theoretic transform.
+//! The recursive tiers call back into [`mul_limbs`] for their sub-products.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::biguint::LIMB_BITS;
+use crate::ntt::ntt_mul;
+use crate::{BigNumber, BigUint};
+
+// Operand sizes, in limbs of the shorter operand, at which each tier takes over
+const KARATSUBA_THRESHOLD: usize = 32;
+const TOOM3_THRESHOLD: usize = 128;
+const NTT_THRESHOLD: usize = 10_000;
+// Factors multiplied one at a time at the bottom of a product tree
+const PRODUCT_LEAF: usize = 16;
+// Factors in a product tree at which its halves are built on separate threads
+#[cfg(feature = "rayon")]
+const PARALLEL_PRODUCT: usize = 4096;
+
+// Multiplies two limb slices, returning exactly `a.len() + b.len()` limbs
+pub(crate) fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+
+    if short.len() < KARATSUBA_THRESHOLD {
+        schoolbook_mul(short, long)
+    } else if long.len() >= 2 * short.len() {
+        unbalanced_mul(short, long)
+    } else if short.len() < TOOM3_THRESHOLD {
+        karatsuba_mul(short, long)
+    } else if short.len() < NTT_THRESHOLD {
+        toom3_mul(short, long)
+    } else {
+        ntt_mul(short, long)
+    }
+}
+
+// Multiplies small factors with a balanced product tree, so that the large
+// products at the top have operands of similar size
+pub(crate) fn product(values: &[u32]) -> BigUint {
+    if values.len() <= PRODUCT_LEAF {
+        let mut result = BigUint::one();
+        for &value in values {
+            result.mul_small(value);
+        }
+        return result;
+    }
+
+    let (low, high) = values.split_at(values.len() / 2);
+    #[cfg(feature = "rayon")]
+    if values.len() >= PARALLEL_PRODUCT {
+        let (low, high) = rayon::join(|| product(low), || product(high));
+        return &low * &high;
+    }
+    &product(low) * &product(high)
+}
+
+pub(crate) fn schoolbook_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut limbs = vec![0u32; a.len() + b.len()];
+
+    for (i, &a_limb) in a.iter().enumerate() {
+        let mut carry = 0u64;
+
+        for (j, &b_limb) in b.iter().enumerate() {
+            let product = a_limb as u64 * b_limb as u64 + limbs[i + j] as u64 + carry;
+            limbs[i + j] = product as u32;
+            carry = product >> LIMB_BITS;
+        }
+
+        limbs[i + b.len()] = carry as u32;
+    }
+
+    limbs
+}
+
+// Splits the longer operand into pieces the size of the shorter one, so that
+// every sub-product is balanced
+fn unbalanced_mul(short: &[u32], long: &[u32]) -> Vec<u32> {
+    let mut limbs = vec![0u32; short.len() + long.len()];
+
+    for (i, chunk) in long.chunks(short.len()).enumerate() {
+        let product = mul_limbs(chunk, short);
+        add_at(&mut limbs, &product, i * short.len());
+    }
+
+    limbs
+}
+
+// a * b = z2 * B^2m + ((a0 + a1)(b0 + b1) - z0 - z2) * B^m + z0
+pub(crate) fn karatsuba_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let m = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(m.min(a.len()));
+    let (b0, b1) = b.split_at(m.min(b.len()));
+
+    let z0 = mul_limbs(a0, b0);
+    let z2 = mul_limbs(a1, b1);
+
+    let a_sum = add_limbs(a0, a1);
+    let b_sum = add_limbs(b0, b1);
+    let mut z1 = mul_limbs(&a_sum, &b_sum);
+    sub_in_place(&mut z1, &z0);
+    sub_in_place(&mut z1, &z2);
+
+    let mut limbs = vec![0u32; a.len() + b.len()];
+    add_at(&mut limbs, &z0, 0);
+    add_at(&mut limbs, &z1, m);
+    add_at(&mut limbs, &z2, 2 * m);
+    limbs
+}
+
+// Toom-Cook 3-way: evaluates both operands as quadratics at 0, 1, -1, -2 and
+// infinity, multiplies pointwise and interpolates with Bodrato's sequence
+pub(crate) fn toom3_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let k = a.len().max(b.len()).div_ceil(3);
+    let [a0, a1, a2] = split3(a, k);
+    let [b0, b1, b2] = split3(b, k);
+
+    let [pa0, pa1, pa_1, pa_2, pa_inf] = toom3_evaluate(&a0, &a1, &a2);
+    let [pb0, pb1, pb_1, pb_2, pb_inf] = toom3_evaluate(&b0, &b1, &b2);
+
+    let r0 = &pa0 * &pb0;
+    let r1 = &pa1 * &pb1;
+    let r_1 = &pa_1 * &pb_1;
+    let r_2 = &pa_2 * &pb_2;
+    let r_inf = &pa_inf * &pb_inf;
+
+    let mut c3 = div_exact_small(&(&r_2 - &r1), 3);
+    let mut c1 = div_exact_small(&(&r1 - &r_1), 2);
+    let mut c2 = &r_1 - &r0;
+    c3 = &div_exact_small(&(&c2 - &c3), 2) + &(&r_inf + &r_inf);
+    c2 = &(&c2 + &c1) - &r_inf;
+    c1 = &c1 - &c3;
+
+    let mut limbs = vec![0u32; a.len() + b.len()];
+    for (i, coefficient) in [r0, c1, c2, c3, r_inf].iter().enumerate() {
+        debug_assert!(!coefficient.is_negative());
+        add_at(&mut limbs, &coefficient.magnitude().limbs, i * k);
+    }
+    limbs
+}
+
+fn split3(limbs: &[u32], k: usize) -> [BigNumber; 3] {
+    let piece = |start: usize| {
+        let start = start.min(limbs.len());
+        let end = (start + k).min(limbs.len());
+        BigNumber::from(BigUint::from_limbs(limbs[start..end].to_vec()))
+    };
+    [piece(0), piece(k), piece(2 * k)]
+}
+
+// Values of `x0 + x1 t + x2 t^2` at t = 0, 1, -1, -2 and infinity
+fn toom3_evaluate(x0: &BigNumber, x1: &BigNumber, x2: &BigNumber) -> [BigNumber; 5] {
+    let p = x0 + x2;
+    let at_1 = &p + x1;
+    let at_minus_1 = &p - x1;
+    let doubled = &at_minus_1 + x2;
+    let at_minus_2 = &(&doubled + &doubled) - x0;
+    [x0.clone(), at_1, at_minus_1, at_minus_2, x2.clone()]
+}
+
+// Divides by a small number known to divide `n` exactly
+fn div_exact_small(n: &BigNumber, divisor: u32) -> BigNumber {
+    let mut magnitude = n.magnitude().clone();
+    let remainder = magnitude.div_rem_small(divisor);
+    debug_assert_eq!(remainder, 0);
+    BigNumber::from_biguint(n.sign(), magnitude)
+}
+
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut limbs = vec![0u32; a.len().max(b.len()) + 1];
+    add_at(&mut limbs, a, 0);
+    add_at(&mut limbs, b, 0);
+    limbs
+}
+
+// Adds `value * B^offset` into `acc`; the caller guarantees the sum fits
+fn add_at(acc: &mut [u32], value: &[u32], offset: usize) {
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < value.len() || carry > 0 {
+        let value_limb = value.get(i).copied().unwrap_or(0);
+        if offset + i >= acc.len() {
+            debug_assert!(value_limb == 0 && carry == 0);
+            break;
+        }
+        let sum = acc[offset + i] as u64 + value_limb as u64 + carry;
+        acc[offset + i] = sum as u32;
+        carry = sum >> LIMB_BITS;
+        i += 1;
+    }
+}
+
+// Subtracts `value` from `acc`; the caller guarantees the result is non-negative
+fn sub_in_place(acc: &mut [u32], value: &[u32]) {
+    let mut borrow = false;
+    let mut i = 0;
+    while i < value.len() || borrow {
+        let value_limb = value.get(i).copied().unwrap_or(0);
+        let (diff, borrow1) = acc[i].overflowing_sub(value_limb);
+        let (diff, borrow2) = diff.overflowing_sub(borrow as u32);
+        acc[i] = diff;
+        borrow = borrow1 || borrow2;
+        i += 1;
+    }
+}
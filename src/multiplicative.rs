@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+
+use crate::BigNumber;
+
+impl BigNumber {
+    /// Returns Euler's totient of the number, the count of integers in
+    /// `1..=self` that are coprime to it.
+    ///
+    /// This factorizes the number first; use
+    /// [`euler_phi_from_factors`](BigNumber::euler_phi_from_factors) when the
+    /// factorization is already known.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("36").euler_phi().to_string(), "12");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is not positive.
+    pub fn euler_phi(&self) -> BigNumber {
+        assert_positive(self, "Euler's totient");
+        BigNumber::euler_phi_from_factors(&self.factorize())
+    }
+
+    /// Returns Euler's totient of the number whose prime factorization is
+    /// `factors`, given as `(prime, exponent)` pairs like the output of
+    /// [`factorize`](BigNumber::factorize).
+    ///
+    /// The primes are not checked, and an empty slice stands for `1`.
+    pub fn euler_phi_from_factors(factors: &[(BigNumber, u32)]) -> BigNumber {
+        let mut phi = BigNumber::one();
+        for (p, exponent) in factors.iter().filter(|(_, exponent)| *exponent > 0) {
+            phi *= &p.pow(*exponent as u64 - 1);
+            phi *= &(p - &BigNumber::one());
+        }
+        phi
+    }
+
+    /// Returns the number of positive divisors of the number, `sigma_0`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("360").num_divisors().to_string(), "24");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is not positive.
+    pub fn num_divisors(&self) -> BigNumber {
+        assert_positive(self, "Divisor count");
+        let mut count = BigNumber::one();
+        for (_, exponent) in self.factorize() {
+            count.multiply_by_int(exponent as i32 + 1);
+        }
+        count
+    }
+
+    /// Returns the sum of the positive divisors of the number, `sigma_1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is not positive.
+    pub fn sum_divisors(&self) -> BigNumber {
+        assert_positive(self, "Divisor sum");
+        let mut sum = BigNumber::one();
+        for (p, exponent) in self.factorize() {
+            // 1 + p + ... + p^e = (p^(e + 1) - 1) / (p - 1)
+            let numerator = &p.pow(exponent as u64 + 1) - &BigNumber::one();
+            sum *= &(&numerator / &(&p - &BigNumber::one()));
+        }
+        sum
+    }
+
+    /// Returns the Carmichael function of the number, the smallest `m` with
+    /// `a^m = 1 (mod self)` for every `a` coprime to it.
+    ///
+    /// This divides [`euler_phi`](BigNumber::euler_phi), and is the exponent
+    /// modulus to use for RSA-style key arithmetic.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("561").carmichael_lambda().to_string(), "80");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is not positive.
+    pub fn carmichael_lambda(&self) -> BigNumber {
+        assert_positive(self, "Carmichael function");
+        let two = BigNumber::from_small(2);
+        let orders: Vec<BigNumber> = self
+            .factorize()
+            .into_iter()
+            .map(|(p, exponent)| {
+                // The units modulo 2^e for e >= 3 are not cyclic
+                if p == two && exponent >= 3 {
+                    two.pow(exponent as u64 - 2)
+                } else {
+                    BigNumber::euler_phi_from_factors(&[(p, exponent)])
+                }
+            })
+            .collect();
+        BigNumber::lcm_all(&orders)
+    }
+
+    /// Returns the Möbius function of the number: `0` if a square greater than
+    /// one divides it, otherwise `1` or `-1` for an even or odd number of prime
+    /// factors.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("30").moebius(), -1);
+    /// assert_eq!(BigNumber::from_string("12").moebius(), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is not positive.
+    pub fn moebius(&self) -> i8 {
+        assert_positive(self, "Möbius function");
+        let factors = self.factorize();
+        if factors.iter().any(|&(_, exponent)| exponent > 1) {
+            0
+        } else if factors.len().is_multiple_of(2) {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
+fn assert_positive(n: &BigNumber, function: &str) {
+    if n.is_zero() || n.is_negative() {
+        panic!("{} needs a positive argument", function);
+    }
+}
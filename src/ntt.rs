@@ -0,0 +1,159 @@
+//! Number-theoretic transform multiplication.
+//!
+//! Operands are split into 16-bit chunks and convolved modulo the prime
+//! `p = 2^64 - 2^32 + 1`. Every coefficient of the exact convolution is below
+//! `len * 2^32`, so the result is exact for inputs of up to `2^31` chunks.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::biguint::LIMB_BITS;
+
+const P: u64 = 0xffff_ffff_0000_0001;
+// 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+// A generator of the multiplicative group modulo p
+const GENERATOR: u64 = 7;
+
+const CHUNK_BITS: u32 = 16;
+const CHUNK_MASK: u32 = (1 << CHUNK_BITS) - 1;
+
+fn add_mod(a: u64, b: u64) -> u64 {
+    // On overflow the dropped 2^64 is worth EPSILON, and the sum stays below p
+    let (sum, overflow) = a.overflowing_add(b);
+    let sum = if overflow { sum + EPSILON } else { sum };
+    if sum >= P {
+        sum - P
+    } else {
+        sum
+    }
+}
+
+fn sub_mod(a: u64, b: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + (P - b)
+    }
+}
+
+// Reduces a 128-bit value using 2^64 = 2^32 - 1 and 2^96 = -1 (mod p)
+fn reduce(x: u128) -> u64 {
+    let low = x as u64;
+    let high = (x >> 64) as u64;
+    let high_high = high >> 32;
+    let high_low = high & EPSILON;
+
+    let (mut t0, borrow) = low.overflowing_sub(high_high);
+    if borrow {
+        t0 = t0.wrapping_sub(EPSILON);
+    }
+    let t1 = high_low * EPSILON;
+    let (sum, carry) = t0.overflowing_add(t1);
+    let sum = sum.wrapping_add(EPSILON * carry as u64);
+    if sum >= P {
+        sum - P
+    } else {
+        sum
+    }
+}
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    reduce(a as u128 * b as u128)
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64) -> u64 {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base);
+        }
+        base = mul_mod(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+// In-place iterative Cooley-Tukey transform; `values.len()` must be a power of two
+fn transform(values: &mut [u64], inverse: bool) {
+    let n = values.len();
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let mut root = pow_mod(GENERATOR, (P - 1) / len as u64);
+        if inverse {
+            root = pow_mod(root, P - 2);
+        }
+
+        for start in (0..n).step_by(len) {
+            let mut w = 1;
+            for k in 0..len / 2 {
+                let u = values[start + k];
+                let v = mul_mod(values[start + k + len / 2], w);
+                values[start + k] = add_mod(u, v);
+                values[start + k + len / 2] = sub_mod(u, v);
+                w = mul_mod(w, root);
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let n_inverse = pow_mod(n as u64, P - 2);
+        for value in values.iter_mut() {
+            *value = mul_mod(*value, n_inverse);
+        }
+    }
+}
+
+fn to_chunks(limbs: &[u32], size: usize) -> Vec<u64> {
+    let mut chunks = Vec::with_capacity(size);
+    for &limb in limbs {
+        chunks.push((limb & CHUNK_MASK) as u64);
+        chunks.push((limb >> CHUNK_BITS) as u64);
+    }
+    chunks.resize(size, 0);
+    chunks
+}
+
+// Multiplies two limb slices, returning exactly `a.len() + b.len()` limbs
+pub(crate) fn ntt_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let result_len = a.len() + b.len();
+    let size = (2 * result_len).next_power_of_two();
+
+    let mut fa = to_chunks(a, size);
+    let mut fb = to_chunks(b, size);
+    transform(&mut fa, false);
+    transform(&mut fb, false);
+    for (x, &y) in fa.iter_mut().zip(&fb) {
+        *x = mul_mod(*x, y);
+    }
+    transform(&mut fa, true);
+
+    // Propagate carries through the 16-bit chunks and pack pairs into limbs
+    let mut limbs = vec![0u32; result_len];
+    let mut carry = 0u128;
+    for (i, &coefficient) in fa.iter().enumerate().take(2 * result_len) {
+        let value = coefficient as u128 + carry;
+        let chunk = (value as u32) & CHUNK_MASK;
+        carry = value >> CHUNK_BITS;
+        limbs[i / 2] |= chunk << (CHUNK_BITS * (i as u32 % 2));
+    }
+    debug_assert_eq!(carry, 0);
+    debug_assert_eq!(LIMB_BITS, 2 * CHUNK_BITS);
+
+    limbs
+}
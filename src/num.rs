@@ -0,0 +1,313 @@
+//! Implementations of the `num-traits` traits, behind the `num-traits`
+//! feature, so both types plug into code written against those traits. The
+//! `num-integer` feature adds `Integer`, and the `num-bigint` feature adds
+//! conversions to and from the `num-bigint` types.
+
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, One, Pow, Signed, Unsigned, Zero};
+
+use crate::{BigNumber, BigUint, ParseBigNumberError, Sign};
+
+impl Zero for BigUint {
+    fn zero() -> BigUint {
+        BigUint::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        BigUint::is_zero(self)
+    }
+}
+
+impl One for BigUint {
+    fn one() -> BigUint {
+        BigUint::one()
+    }
+}
+
+impl Num for BigUint {
+    type FromStrRadixErr = ParseBigNumberError;
+
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    fn from_str_radix(input: &str, radix: u32) -> Result<BigUint, ParseBigNumberError> {
+        BigUint::from_str_radix(input, radix)
+    }
+}
+
+impl Unsigned for BigUint {}
+
+impl CheckedAdd for BigUint {
+    fn checked_add(&self, other: &BigUint) -> Option<BigUint> {
+        Some(self + other)
+    }
+}
+
+impl CheckedSub for BigUint {
+    fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        BigUint::checked_sub(self, other)
+    }
+}
+
+impl CheckedMul for BigUint {
+    fn checked_mul(&self, other: &BigUint) -> Option<BigUint> {
+        Some(self * other)
+    }
+}
+
+impl CheckedDiv for BigUint {
+    fn checked_div(&self, other: &BigUint) -> Option<BigUint> {
+        self.checked_div_rem(other).ok().map(|(quotient, _)| quotient)
+    }
+}
+
+impl Zero for BigNumber {
+    fn zero() -> BigNumber {
+        BigNumber::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        BigNumber::is_zero(self)
+    }
+}
+
+impl One for BigNumber {
+    fn one() -> BigNumber {
+        BigNumber::one()
+    }
+}
+
+impl Num for BigNumber {
+    type FromStrRadixErr = ParseBigNumberError;
+
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    fn from_str_radix(input: &str, radix: u32) -> Result<BigNumber, ParseBigNumberError> {
+        BigNumber::from_str_radix(input, radix)
+    }
+}
+
+// Unlike the inherent `is_positive`, which only looks at the sign, the trait
+// methods treat zero as neither positive nor negative
+impl Signed for BigNumber {
+    fn abs(&self) -> BigNumber {
+        BigNumber::from(self.magnitude.clone())
+    }
+
+    fn abs_sub(&self, other: &BigNumber) -> BigNumber {
+        if self <= other {
+            BigNumber::zero()
+        } else {
+            self - other
+        }
+    }
+
+    fn signum(&self) -> BigNumber {
+        if self.is_zero() {
+            BigNumber::zero()
+        } else {
+            BigNumber::from_biguint(self.sign, BigUint::one())
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        self.sign == Sign::Positive && !self.is_zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign == Sign::Negative
+    }
+}
+
+impl CheckedAdd for BigNumber {
+    fn checked_add(&self, other: &BigNumber) -> Option<BigNumber> {
+        Some(self + other)
+    }
+}
+
+impl CheckedSub for BigNumber {
+    fn checked_sub(&self, other: &BigNumber) -> Option<BigNumber> {
+        Some(self - other)
+    }
+}
+
+impl CheckedMul for BigNumber {
+    fn checked_mul(&self, other: &BigNumber) -> Option<BigNumber> {
+        Some(self * other)
+    }
+}
+
+impl CheckedDiv for BigNumber {
+    fn checked_div(&self, other: &BigNumber) -> Option<BigNumber> {
+        BigNumber::checked_div(self, other)
+    }
+}
+
+// Implements `Pow` with the given unsigned exponent types for the owned and
+// borrowed forms of `$t` in terms of the inherent `pow`
+macro_rules! pow_impl {
+    ($t:ty; $($exponent:ty),*) => {$(
+        impl Pow<$exponent> for &$t {
+            type Output = $t;
+
+            fn pow(self, exponent: $exponent) -> $t {
+                <$t>::pow(self, exponent as u64)
+            }
+        }
+
+        impl Pow<$exponent> for $t {
+            type Output = $t;
+
+            fn pow(self, exponent: $exponent) -> $t {
+                <$t>::pow(&self, exponent as u64)
+            }
+        }
+    )*};
+}
+
+pow_impl!(BigUint; u8, u16, u32, u64, usize);
+pow_impl!(BigNumber; u8, u16, u32, u64, usize);
+
+#[cfg(feature = "num-integer")]
+impl num_integer::Integer for BigUint {
+    fn div_floor(&self, other: &BigUint) -> BigUint {
+        self / other
+    }
+
+    fn mod_floor(&self, other: &BigUint) -> BigUint {
+        self % other
+    }
+
+    fn gcd(&self, other: &BigUint) -> BigUint {
+        BigUint::gcd(self, other)
+    }
+
+    fn lcm(&self, other: &BigUint) -> BigUint {
+        BigUint::lcm(self, other)
+    }
+
+    // Zero is only a multiple of zero
+    fn is_multiple_of(&self, other: &BigUint) -> bool {
+        if other.is_zero() {
+            return self.is_zero();
+        }
+        (self % other).is_zero()
+    }
+
+    fn is_even(&self) -> bool {
+        BigUint::is_even(self)
+    }
+
+    fn is_odd(&self) -> bool {
+        !BigUint::is_even(self)
+    }
+
+    fn div_rem(&self, other: &BigUint) -> (BigUint, BigUint) {
+        BigUint::div_rem(self, other)
+    }
+}
+
+#[cfg(feature = "num-integer")]
+impl num_integer::Integer for BigNumber {
+    fn div_floor(&self, other: &BigNumber) -> BigNumber {
+        BigNumber::div_floor(self, other)
+    }
+
+    fn mod_floor(&self, other: &BigNumber) -> BigNumber {
+        BigNumber::mod_floor(self, other)
+    }
+
+    fn div_ceil(&self, other: &BigNumber) -> BigNumber {
+        BigNumber::div_ceil(self, other)
+    }
+
+    fn gcd(&self, other: &BigNumber) -> BigNumber {
+        BigNumber::gcd(self, other)
+    }
+
+    fn lcm(&self, other: &BigNumber) -> BigNumber {
+        BigNumber::lcm(self, other)
+    }
+
+    fn is_multiple_of(&self, other: &BigNumber) -> bool {
+        if other.is_zero() {
+            return self.is_zero();
+        }
+        self.is_divisible_by(other)
+    }
+
+    fn is_even(&self) -> bool {
+        self.magnitude.is_even()
+    }
+
+    fn is_odd(&self) -> bool {
+        !self.magnitude.is_even()
+    }
+
+    fn div_rem(&self, other: &BigNumber) -> (BigNumber, BigNumber) {
+        BigNumber::div_rem(self, other)
+    }
+}
+
+// Both crates store base 2^32 digits least significant first, so the
+// conversions copy the limbs across
+#[cfg(feature = "num-bigint")]
+mod bigint {
+    use crate::{BigNumber, BigUint, Sign};
+
+    impl From<&num_bigint::BigUint> for BigUint {
+        fn from(n: &num_bigint::BigUint) -> BigUint {
+            BigUint::from_limbs(n.to_u32_digits())
+        }
+    }
+
+    impl From<num_bigint::BigUint> for BigUint {
+        fn from(n: num_bigint::BigUint) -> BigUint {
+            BigUint::from(&n)
+        }
+    }
+
+    impl From<&BigUint> for num_bigint::BigUint {
+        fn from(n: &BigUint) -> num_bigint::BigUint {
+            num_bigint::BigUint::from_slice(&n.limbs)
+        }
+    }
+
+    impl From<BigUint> for num_bigint::BigUint {
+        fn from(n: BigUint) -> num_bigint::BigUint {
+            num_bigint::BigUint::from(&n)
+        }
+    }
+
+    impl From<&num_bigint::BigInt> for BigNumber {
+        fn from(n: &num_bigint::BigInt) -> BigNumber {
+            let sign = match n.sign() {
+                num_bigint::Sign::Minus => Sign::Negative,
+                num_bigint::Sign::NoSign | num_bigint::Sign::Plus => Sign::Positive,
+            };
+            BigNumber::from_biguint(sign, BigUint::from(n.magnitude()))
+        }
+    }
+
+    impl From<num_bigint::BigInt> for BigNumber {
+        fn from(n: num_bigint::BigInt) -> BigNumber {
+            BigNumber::from(&n)
+        }
+    }
+
+    impl From<&BigNumber> for num_bigint::BigInt {
+        fn from(n: &BigNumber) -> num_bigint::BigInt {
+            let sign = match n.sign {
+                Sign::Negative => num_bigint::Sign::Minus,
+                Sign::Positive => num_bigint::Sign::Plus,
+            };
+            num_bigint::BigInt::from_biguint(sign, num_bigint::BigUint::from(&n.magnitude))
+        }
+    }
+
+    impl From<BigNumber> for num_bigint::BigInt {
+        fn from(n: BigNumber) -> num_bigint::BigInt {
+            num_bigint::BigInt::from(&n)
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign, Mul,
+    MulAssign, Neg, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+
+use crate::{BigNumber, BigUint, Sign};
+
+// Implements the owned and mixed operand forms of a binary operator in terms of
+// the `&T op &T` implementation.
+macro_rules! forward_binop {
+    (impl $imp:ident for $t:ty, $method:ident) => {
+        impl $imp<$t> for $t {
+            type Output = $t;
+
+            fn $method(self, other: $t) -> $t {
+                $imp::$method(&self, &other)
+            }
+        }
+
+        impl $imp<&$t> for $t {
+            type Output = $t;
+
+            fn $method(self, other: &$t) -> $t {
+                $imp::$method(&self, other)
+            }
+        }
+
+        impl $imp<$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, other: $t) -> $t {
+                $imp::$method(self, &other)
+            }
+        }
+    };
+}
+
+// Implements the owned right-hand side form of a compound assignment operator in
+// terms of the `T op= &T` implementation.
+macro_rules! forward_assign_op {
+    (impl $imp:ident for $t:ty, $method:ident) => {
+        impl $imp<$t> for $t {
+            fn $method(&mut self, other: $t) {
+                $imp::$method(self, &other);
+            }
+        }
+    };
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut result = self.clone();
+        result.add_assign_magnitude(other);
+        result
+    }
+}
+
+/// # Panics
+///
+/// Panics if `other` is greater than `self`.
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        match self.checked_sub(other) {
+            Some(difference) => difference,
+            None => panic!("BigUint subtraction would be negative"),
+        }
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        self.mul_magnitude(other)
+    }
+}
+
+/// # Panics
+///
+/// Panics if `other` is zero.
+impl Div<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).0
+    }
+}
+
+/// # Panics
+///
+/// Panics if `other` is zero.
+impl Rem<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).1
+    }
+}
+
+forward_binop!(impl Add for BigUint, add);
+forward_binop!(impl Sub for BigUint, sub);
+forward_binop!(impl Mul for BigUint, mul);
+forward_binop!(impl Div for BigUint, div);
+forward_binop!(impl Rem for BigUint, rem);
+
+// Implements a bitwise operator and its compound assignment form for `$t` in
+// terms of the limb-wise `bitwise` helper.
+macro_rules! bitwise_op {
+    (impl $imp:ident, $assign_imp:ident for $t:ty, $method:ident, $assign_method:ident, $op:tt) => {
+        impl $imp<&$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, other: &$t) -> $t {
+                self.bitwise(other, |a, b| a $op b)
+            }
+        }
+
+        impl $assign_imp<&$t> for $t {
+            fn $assign_method(&mut self, other: &$t) {
+                *self = $imp::$method(&*self, other);
+            }
+        }
+
+        forward_binop!(impl $imp for $t, $method);
+        forward_assign_op!(impl $assign_imp for $t, $assign_method);
+    };
+}
+
+bitwise_op!(impl BitAnd, BitAndAssign for BigUint, bitand, bitand_assign, &);
+bitwise_op!(impl BitOr, BitOrAssign for BigUint, bitor, bitor_assign, |);
+bitwise_op!(impl BitXor, BitXorAssign for BigUint, bitxor, bitxor_assign, ^);
+
+// Implements the by-value and compound assignment forms of a shift operator in
+// terms of the `&T op usize` implementation.
+macro_rules! forward_shift_op {
+    (impl $imp:ident, $assign_imp:ident for $t:ty, $method:ident, $assign_method:ident) => {
+        impl $imp<usize> for $t {
+            type Output = $t;
+
+            fn $method(self, bits: usize) -> $t {
+                $imp::$method(&self, bits)
+            }
+        }
+
+        impl $assign_imp<usize> for $t {
+            fn $assign_method(&mut self, bits: usize) {
+                *self = $imp::$method(&*self, bits);
+            }
+        }
+    };
+}
+
+/// Binary left shift, multiplying by `2^bits`.
+impl Shl<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: usize) -> BigUint {
+        let mut result = self.clone();
+        result.shl_bits(bits as u64);
+        result
+    }
+}
+
+/// Binary right shift, dividing by `2^bits` and rounding down.
+impl Shr<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: usize) -> BigUint {
+        let mut result = self.clone();
+        result.shr_bits(bits as u64);
+        result
+    }
+}
+
+forward_shift_op!(impl Shl, ShlAssign for BigUint, shl, shl_assign);
+forward_shift_op!(impl Shr, ShrAssign for BigUint, shr, shr_assign);
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        self.add_assign_magnitude(other);
+    }
+}
+
+impl SubAssign<&BigUint> for BigUint {
+    fn sub_assign(&mut self, other: &BigUint) {
+        *self = &*self - other;
+    }
+}
+
+impl MulAssign<&BigUint> for BigUint {
+    fn mul_assign(&mut self, other: &BigUint) {
+        *self = &*self * other;
+    }
+}
+
+impl DivAssign<&BigUint> for BigUint {
+    fn div_assign(&mut self, other: &BigUint) {
+        *self = &*self / other;
+    }
+}
+
+impl RemAssign<&BigUint> for BigUint {
+    fn rem_assign(&mut self, other: &BigUint) {
+        *self = &*self % other;
+    }
+}
+
+forward_assign_op!(impl AddAssign for BigUint, add_assign);
+forward_assign_op!(impl SubAssign for BigUint, sub_assign);
+forward_assign_op!(impl MulAssign for BigUint, mul_assign);
+forward_assign_op!(impl DivAssign for BigUint, div_assign);
+forward_assign_op!(impl RemAssign for BigUint, rem_assign);
+
+impl Add<&BigNumber> for &BigNumber {
+    type Output = BigNumber;
+
+    fn add(self, other: &BigNumber) -> BigNumber {
+        BigNumber::add(self, other)
+    }
+}
+
+impl Sub<&BigNumber> for &BigNumber {
+    type Output = BigNumber;
+
+    fn sub(self, other: &BigNumber) -> BigNumber {
+        BigNumber::subtract(self, other)
+    }
+}
+
+impl Mul<&BigNumber> for &BigNumber {
+    type Output = BigNumber;
+
+    fn mul(self, other: &BigNumber) -> BigNumber {
+        BigNumber::multiply(self, other)
+    }
+}
+
+/// Truncating division, rounding the quotient towards zero like the primitive integers.
+///
+/// # Panics
+///
+/// Panics if `other` is zero.
+impl Div<&BigNumber> for &BigNumber {
+    type Output = BigNumber;
+
+    fn div(self, other: &BigNumber) -> BigNumber {
+        match self.checked_div(other) {
+            Some(quotient) => quotient,
+            None => panic!("Division by zero"),
+        }
+    }
+}
+
+/// Remainder of truncating division; the result has the sign of `self`.
+///
+/// # Panics
+///
+/// Panics if `other` is zero.
+impl Rem<&BigNumber> for &BigNumber {
+    type Output = BigNumber;
+
+    fn rem(self, other: &BigNumber) -> BigNumber {
+        match self.checked_rem(other) {
+            Some(remainder) => remainder,
+            None => panic!("Division by zero"),
+        }
+    }
+}
+
+impl Neg for &BigNumber {
+    type Output = BigNumber;
+
+    fn neg(self) -> BigNumber {
+        BigNumber::from_biguint(self.sign.negated(), self.magnitude.clone())
+    }
+}
+
+impl Neg for BigNumber {
+    type Output = BigNumber;
+
+    fn neg(self) -> BigNumber {
+        BigNumber::from_biguint(self.sign.negated(), self.magnitude)
+    }
+}
+
+forward_binop!(impl Add for BigNumber, add);
+forward_binop!(impl Sub for BigNumber, sub);
+forward_binop!(impl Mul for BigNumber, mul);
+forward_binop!(impl Div for BigNumber, div);
+forward_binop!(impl Rem for BigNumber, rem);
+
+// The bitwise operators act on two's complement representations of unbounded
+// width, like the primitive signed integers; see the `bitwise` module.
+bitwise_op!(impl BitAnd, BitAndAssign for BigNumber, bitand, bitand_assign, &);
+bitwise_op!(impl BitOr, BitOrAssign for BigNumber, bitor, bitor_assign, |);
+bitwise_op!(impl BitXor, BitXorAssign for BigNumber, bitxor, bitxor_assign, ^);
+
+/// Binary left shift, multiplying by `2^bits`.
+///
+/// Unlike [`BigNumber::shift_left`], which works in decimal digits.
+impl Shl<usize> for &BigNumber {
+    type Output = BigNumber;
+
+    fn shl(self, bits: usize) -> BigNumber {
+        BigNumber::from_biguint(self.sign, &self.magnitude << bits)
+    }
+}
+
+/// Arithmetic right shift, dividing by `2^bits` and rounding towards negative
+/// infinity like the primitive signed integers, so `-1 >> n` stays `-1`.
+///
+/// Unlike [`BigNumber::shift_right`], which works in decimal digits.
+impl Shr<usize> for &BigNumber {
+    type Output = BigNumber;
+
+    fn shr(self, bits: usize) -> BigNumber {
+        if !self.is_negative() {
+            return BigNumber::from(&self.magnitude >> bits);
+        }
+
+        // floor(-m / 2^k) = -(floor((m - 1) / 2^k) + 1) for m > 0
+        let mut magnitude = &(&self.magnitude - &BigUint::one()) >> bits;
+        magnitude.add_small(1);
+        BigNumber::from_biguint(Sign::Negative, magnitude)
+    }
+}
+
+forward_shift_op!(impl Shl, ShlAssign for BigNumber, shl, shl_assign);
+forward_shift_op!(impl Shr, ShrAssign for BigNumber, shr, shr_assign);
+
+impl AddAssign<&BigNumber> for BigNumber {
+    fn add_assign(&mut self, other: &BigNumber) {
+        self.add_in_place(other);
+    }
+}
+
+impl SubAssign<&BigNumber> for BigNumber {
+    fn sub_assign(&mut self, other: &BigNumber) {
+        self.subtract_in_place(other);
+    }
+}
+
+impl MulAssign<&BigNumber> for BigNumber {
+    fn mul_assign(&mut self, other: &BigNumber) {
+        self.multiply_in_place(other);
+    }
+}
+
+impl DivAssign<&BigNumber> for BigNumber {
+    fn div_assign(&mut self, other: &BigNumber) {
+        *self = &*self / other;
+    }
+}
+
+impl RemAssign<&BigNumber> for BigNumber {
+    fn rem_assign(&mut self, other: &BigNumber) {
+        *self = &*self % other;
+    }
+}
+
+forward_assign_op!(impl AddAssign for BigNumber, add_assign);
+forward_assign_op!(impl SubAssign for BigNumber, sub_assign);
+forward_assign_op!(impl MulAssign for BigNumber, mul_assign);
+forward_assign_op!(impl DivAssign for BigNumber, div_assign);
+forward_assign_op!(impl RemAssign for BigNumber, rem_assign);
@@ -0,0 +1,659 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+use crate::{BigNumber, BigUint, Sign};
+
+/// The error returned when a string is not a valid integer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigNumberError {
+    /// The input was empty.
+    Empty,
+    /// The input consisted of a sign with no digits after it.
+    BareSign,
+    /// A literal had a radix prefix such as `0x` with no digits after it.
+    BarePrefix,
+    /// Strict parsing found a leading zero on a number other than `0`.
+    LeadingZero,
+    /// Strict parsing found `-0`.
+    NegativeZero,
+    /// Scientific notation had an `e` with no exponent digits after it.
+    MissingExponent,
+    /// Scientific notation had an exponent too large to expand.
+    ExponentTooLarge,
+    /// Scientific notation described a number with a fractional part.
+    NotAnInteger,
+    /// The input contained a character that is not a digit in the radix.
+    InvalidDigit {
+        /// Byte offset of the offending character in the input.
+        position: usize,
+        /// The offending character.
+        character: char,
+    },
+}
+
+impl fmt::Display for ParseBigNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBigNumberError::Empty => f.write_str("cannot parse integer from empty string"),
+            ParseBigNumberError::BareSign => f.write_str("sign without any digits"),
+            ParseBigNumberError::BarePrefix => f.write_str("radix prefix without any digits"),
+            ParseBigNumberError::LeadingZero => f.write_str("leading zero in strict mode"),
+            ParseBigNumberError::NegativeZero => f.write_str("negative zero in strict mode"),
+            ParseBigNumberError::MissingExponent => f.write_str("exponent without any digits"),
+            ParseBigNumberError::ExponentTooLarge => f.write_str("exponent too large"),
+            ParseBigNumberError::NotAnInteger => f.write_str("number is not an integer"),
+            ParseBigNumberError::InvalidDigit { position, character } => {
+                write!(f, "invalid digit {:?} at position {}", character, position)
+            }
+        }
+    }
+}
+
+impl Error for ParseBigNumberError {}
+
+impl BigUint {
+    /// Parses a string of decimal digits.
+    ///
+    /// ```
+    /// use bignumber::BigUint;
+    ///
+    /// assert_eq!(BigUint::parse("00120").unwrap().to_string(), "120");
+    /// assert!(BigUint::parse("-1").is_err());
+    /// ```
+    pub fn parse(input: &str) -> Result<BigUint, ParseBigNumberError> {
+        if input.is_empty() {
+            return Err(ParseBigNumberError::Empty);
+        }
+
+        parse_digits(input, 0, 10)
+    }
+
+    /// Parses a string of digits in the given radix, using the letters `a` to
+    /// `z` in either case for the digits from ten upwards.
+    ///
+    /// ```
+    /// use bignumber::BigUint;
+    ///
+    /// assert_eq!(BigUint::from_str_radix("ff", 16).unwrap().to_string(), "255");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    pub fn from_str_radix(input: &str, radix: u32) -> Result<BigUint, ParseBigNumberError> {
+        check_radix(radix);
+        if input.is_empty() {
+            return Err(ParseBigNumberError::Empty);
+        }
+
+        parse_digits(input, 0, radix)
+    }
+
+    /// Parses an integer written like a Rust literal: digits with an optional
+    /// `0x`, `0o` or `0b` prefix, and `_` separators anywhere after the first
+    /// digit or the prefix.
+    ///
+    /// ```
+    /// use bignumber::BigUint;
+    ///
+    /// assert_eq!(BigUint::parse_literal("0xDEAD_BEEF").unwrap().to_string(), "3735928559");
+    /// assert_eq!(BigUint::parse_literal("1_000_000").unwrap().to_string(), "1000000");
+    /// ```
+    pub fn parse_literal(input: &str) -> Result<BigUint, ParseBigNumberError> {
+        if input.is_empty() {
+            return Err(ParseBigNumberError::Empty);
+        }
+
+        parse_literal_digits(input, 0)
+    }
+
+    /// Parses a decimal string, ignoring surrounding whitespace and allowing a
+    /// leading `+`.
+    ///
+    /// ```
+    /// use bignumber::BigUint;
+    ///
+    /// assert_eq!(BigUint::parse_lenient(" +0042\n").unwrap().to_string(), "42");
+    /// ```
+    pub fn parse_lenient(input: &str) -> Result<BigUint, ParseBigNumberError> {
+        parse_lenient_parts(input, false).map(|(_, magnitude)| magnitude)
+    }
+
+    /// Parses a decimal string in canonical form only: digits with no sign,
+    /// whitespace or leading zeros.
+    ///
+    /// ```
+    /// use bignumber::{BigUint, ParseBigNumberError};
+    ///
+    /// assert_eq!(BigUint::parse_strict("120").unwrap().to_string(), "120");
+    /// assert_eq!(BigUint::parse_strict("0120"), Err(ParseBigNumberError::LeadingZero));
+    /// ```
+    pub fn parse_strict(input: &str) -> Result<BigUint, ParseBigNumberError> {
+        let magnitude = BigUint::parse(input)?;
+        check_no_leading_zero(input)?;
+        Ok(magnitude)
+    }
+}
+
+impl BigNumber {
+    /// Parses a decimal string with an optional leading `-`.
+    ///
+    /// ```
+    /// use bignumber::{BigNumber, ParseBigNumberError};
+    ///
+    /// assert_eq!(BigNumber::parse("-120").unwrap().to_string(), "-120");
+    /// assert_eq!(
+    ///     BigNumber::parse("12x"),
+    ///     Err(ParseBigNumberError::InvalidDigit { position: 2, character: 'x' })
+    /// );
+    /// ```
+    pub fn parse(input: &str) -> Result<BigNumber, ParseBigNumberError> {
+        BigNumber::from_str_radix(input, 10)
+    }
+
+    /// Parses a string of digits in the given radix with an optional leading
+    /// `-`, using the letters `a` to `z` in either case for the digits from ten
+    /// upwards.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_str_radix("-DEADBEEF", 16).unwrap();
+    /// assert_eq!(n.to_string(), "-3735928559");
+    /// assert_eq!(BigNumber::from_str_radix("1011", 2).unwrap().to_string(), "11");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    pub fn from_str_radix(input: &str, radix: u32) -> Result<BigNumber, ParseBigNumberError> {
+        check_radix(radix);
+        if input.is_empty() {
+            return Err(ParseBigNumberError::Empty);
+        }
+
+        let (sign, offset) = match input.as_bytes()[0] {
+            b'-' => (Sign::Negative, 1),
+            _ => (Sign::Positive, 0),
+        };
+
+        if input.len() == offset {
+            return Err(ParseBigNumberError::BareSign);
+        }
+
+        let magnitude = parse_digits(&input[offset..], offset, radix)?;
+        Ok(BigNumber::from_biguint(sign, magnitude))
+    }
+
+    /// Parses an integer written like a Rust literal with an optional leading
+    /// `-`: digits with an optional `0x`, `0o` or `0b` prefix, and `_`
+    /// separators anywhere after the first digit or the prefix.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::parse_literal("-0b1010").unwrap().to_string(), "-10");
+    /// assert_eq!(BigNumber::parse_literal("0o7_7").unwrap().to_string(), "63");
+    /// assert!(BigNumber::parse_literal("_1").is_err());
+    /// ```
+    pub fn parse_literal(input: &str) -> Result<BigNumber, ParseBigNumberError> {
+        if input.is_empty() {
+            return Err(ParseBigNumberError::Empty);
+        }
+
+        let offset = match input.as_bytes()[0] {
+            b'-' => 1,
+            _ => 0,
+        };
+        if input.len() == offset {
+            return Err(ParseBigNumberError::BareSign);
+        }
+
+        let magnitude = parse_literal_digits(&input[offset..], offset)?;
+        let sign = if offset == 1 { Sign::Negative } else { Sign::Positive };
+        Ok(BigNumber::from_biguint(sign, magnitude))
+    }
+
+    /// Parses a decimal string, ignoring surrounding whitespace and allowing a
+    /// leading `+` as well as `-`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::parse_lenient("  -007 ").unwrap().to_string(), "-7");
+    /// assert_eq!(BigNumber::parse_lenient("+12").unwrap().to_string(), "12");
+    /// ```
+    pub fn parse_lenient(input: &str) -> Result<BigNumber, ParseBigNumberError> {
+        let (sign, magnitude) = parse_lenient_parts(input, true)?;
+        Ok(BigNumber::from_biguint(sign, magnitude))
+    }
+
+    /// Parses a decimal string in canonical form only, as produced by the
+    /// `Display` implementation: an optional `-` followed by digits with no
+    /// leading zeros, and no `-0`.
+    ///
+    /// ```
+    /// use bignumber::{BigNumber, ParseBigNumberError};
+    ///
+    /// assert_eq!(BigNumber::parse_strict("-120").unwrap().to_string(), "-120");
+    /// assert_eq!(BigNumber::parse_strict("-0"), Err(ParseBigNumberError::NegativeZero));
+    /// assert!(BigNumber::parse_strict("+1").is_err());
+    /// ```
+    pub fn parse_strict(input: &str) -> Result<BigNumber, ParseBigNumberError> {
+        let number = BigNumber::parse(input)?;
+        let digits = input.strip_prefix('-');
+        check_no_leading_zero(digits.unwrap_or(input))?;
+        if digits.is_some() && number.is_zero() {
+            return Err(ParseBigNumberError::NegativeZero);
+        }
+        Ok(number)
+    }
+
+    /// Parses a decimal number in scientific notation, `<mantissa>e<exponent>`,
+    /// whose value is an integer.
+    ///
+    /// The mantissa may have a fractional part and the exponent a sign; both
+    /// `e` and `E` are accepted, and the exponent may be left out altogether.
+    /// Numbers with a fractional part are rejected rather than rounded, and an
+    /// exponent that would append more than a million zeros to the digits of
+    /// the mantissa gives [`ParseBigNumberError::ExponentTooLarge`].
+    ///
+    /// ```
+    /// use bignumber::{BigNumber, ParseBigNumberError};
+    ///
+    /// assert_eq!(BigNumber::parse_scientific("1.5e3").unwrap().to_string(), "1500");
+    /// assert_eq!(BigNumber::parse_scientific("-25E-1"), Err(ParseBigNumberError::NotAnInteger));
+    /// assert_eq!(BigNumber::parse_scientific("3e100").unwrap().to_string().len(), 101);
+    /// ```
+    pub fn parse_scientific(input: &str) -> Result<BigNumber, ParseBigNumberError> {
+        if input.is_empty() {
+            return Err(ParseBigNumberError::Empty);
+        }
+
+        let (sign, offset) = match input.as_bytes()[0] {
+            b'-' => (Sign::Negative, 1),
+            _ => (Sign::Positive, 0),
+        };
+        if input.len() == offset {
+            return Err(ParseBigNumberError::BareSign);
+        }
+
+        let rest = &input[offset..];
+        let (mantissa, exponent) = match rest.find(['e', 'E']) {
+            Some(i) => (&rest[..i], Some((&rest[i + 1..], offset + i + 1))),
+            None => (rest, None),
+        };
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(ParseBigNumberError::InvalidDigit {
+                position: offset,
+                character: rest.chars().next().unwrap(),
+            });
+        }
+        parse_digits(whole, offset, 10)?;
+        parse_digits(fraction, offset + whole.len() + 1, 10)?;
+
+        let exponent = match exponent {
+            Some((digits, position)) => parse_exponent(digits, position)?,
+            None => 0,
+        };
+
+        // The value is digits * 10^scale, with the digits taken as an integer
+        let digits = format!("{}{}", whole, fraction);
+        let magnitude = if digits.bytes().all(|b| b == b'0') {
+            BigUint::zero()
+        } else {
+            // A scale below i64::MIN would need more trailing zeros than any
+            // input can have
+            let scale = exponent
+                .checked_sub(fraction.len() as i64)
+                .ok_or(ParseBigNumberError::NotAnInteger)?;
+            if scale >= 0 {
+                let scale = usize::try_from(scale)
+                    .ok()
+                    .filter(|&scale| scale <= MAX_SCIENTIFIC_ZEROS)
+                    .ok_or(ParseBigNumberError::ExponentTooLarge)?;
+                let mut magnitude = parse_digits(&digits, 0, 10)?;
+                magnitude.shift_left_digits(scale);
+                magnitude
+            } else {
+                let trailing_zeros = digits.len() - digits.trim_end_matches('0').len();
+                if (trailing_zeros as u64) < scale.unsigned_abs() {
+                    return Err(ParseBigNumberError::NotAnInteger);
+                }
+                parse_digits(&digits[..digits.len() - scale.unsigned_abs() as usize], 0, 10)?
+            }
+        };
+
+        Ok(BigNumber::from_biguint(sign, magnitude))
+    }
+}
+
+#[cfg(feature = "std")]
+impl BigNumber {
+    /// Reads a decimal number of any length from `reader`, with an optional
+    /// leading `-` or `+`, skipping whitespace anywhere in the input.
+    ///
+    /// The input is consumed in blocks of digits that are parsed as they
+    /// arrive and then combined pairwise, so only the number itself is held
+    /// in memory and not its text, and the cost stays close to that of one
+    /// large multiplication.
+    ///
+    /// Input that is not a number is reported as an
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) error wrapping a
+    /// [`ParseBigNumberError`], whose positions count bytes from the start of
+    /// the input.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let input = "  -1234567890\n  1234567890\n";
+    /// let n = BigNumber::read_decimal(input.as_bytes()).unwrap();
+    /// assert_eq!(n.to_string(), "-12345678901234567890");
+    ///
+    /// assert!(BigNumber::read_decimal("12x".as_bytes()).is_err());
+    /// ```
+    pub fn read_decimal<R: Read>(mut reader: R) -> io::Result<BigNumber> {
+        let invalid = |error: ParseBigNumberError| io::Error::new(io::ErrorKind::InvalidData, error);
+
+        let mut buffer = vec![0u8; READ_BUFFER_BYTES];
+        let mut block = Vec::with_capacity(READ_BLOCK_DIGITS);
+        let mut blocks = DecimalBlocks::default();
+        let mut sign = None;
+        let mut position = 0;
+
+        loop {
+            let len = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(len) => len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            for &byte in &buffer[..len] {
+                match byte {
+                    b'0'..=b'9' => {
+                        sign.get_or_insert(Sign::Positive);
+                        block.push(byte);
+                        if block.len() == READ_BLOCK_DIGITS {
+                            blocks.push(parse_validated(&block, 10));
+                            block.clear();
+                        }
+                    }
+                    b'-' | b'+' if sign.is_none() => {
+                        sign = Some(if byte == b'-' { Sign::Negative } else { Sign::Positive });
+                    }
+                    _ if byte.is_ascii_whitespace() => {}
+                    _ => {
+                        // Only ASCII can be a digit, so other bytes need not be decoded
+                        let character = if byte.is_ascii() { byte as char } else { char::REPLACEMENT_CHARACTER };
+                        return Err(invalid(ParseBigNumberError::InvalidDigit { position, character }));
+                    }
+                }
+                position += 1;
+            }
+        }
+
+        let Some(sign) = sign else {
+            return Err(invalid(ParseBigNumberError::Empty));
+        };
+        if block.is_empty() && blocks.is_empty() {
+            return Err(invalid(ParseBigNumberError::BareSign));
+        }
+        Ok(BigNumber::from_biguint(sign, blocks.finish(&block)))
+    }
+}
+
+// Bytes requested from the reader at a time by `read_decimal`
+#[cfg(feature = "std")]
+const READ_BUFFER_BYTES: usize = 1 << 16;
+// Digits `read_decimal` collects before parsing them as one block
+#[cfg(feature = "std")]
+const READ_BLOCK_DIGITS: usize = 1 << 15;
+
+// The full blocks of digits read so far. Like a binary counter, the stack
+// holds values of READ_BLOCK_DIGITS * 2^level digits with strictly decreasing
+// levels, and two values on the same level are merged as soon as they meet, so
+// every merge multiplies operands of equal size.
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct DecimalBlocks {
+    stack: Vec<(BigUint, usize)>,
+    // powers[i] is 10^(READ_BLOCK_DIGITS * 2^i)
+    powers: Vec<BigUint>,
+}
+
+#[cfg(feature = "std")]
+impl DecimalBlocks {
+    fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    fn power(&mut self, level: usize) -> &BigUint {
+        while self.powers.len() <= level {
+            let power = match self.powers.last() {
+                Some(power) => power * power,
+                None => BigUint::from(10u32).pow(READ_BLOCK_DIGITS as u64),
+            };
+            self.powers.push(power);
+        }
+        &self.powers[level]
+    }
+
+    fn push(&mut self, mut value: BigUint) {
+        let mut level = 0;
+        while let Some((high, _)) = self.stack.pop_if(|(_, top)| *top == level) {
+            value = join(&high, self.power(level), &value);
+            level += 1;
+        }
+        self.stack.push((value, level));
+    }
+
+    // Combines the blocks with the digits of a final partial block
+    fn finish(mut self, tail: &[u8]) -> BigUint {
+        let stack = core::mem::take(&mut self.stack);
+        let mut number = BigUint::zero();
+        for (value, level) in stack {
+            number = join(&number, self.power(level), &value);
+        }
+        if tail.is_empty() {
+            return number;
+        }
+        let power = BigUint::from(10u32).pow(tail.len() as u64);
+        join(&number, &power, &parse_validated(tail, 10))
+    }
+}
+
+// high * power + low
+#[cfg(feature = "std")]
+fn join(high: &BigUint, power: &BigUint, low: &BigUint) -> BigUint {
+    let mut number = high * power;
+    number.add_assign_magnitude(low);
+    number
+}
+
+// The most zeros `parse_scientific` appends to the digits of the mantissa
+const MAX_SCIENTIFIC_ZEROS: usize = 1_000_000;
+
+// Parses the exponent of scientific notation, with an optional sign;
+// `position` is its offset within the original input
+fn parse_exponent(exponent: &str, position: usize) -> Result<i64, ParseBigNumberError> {
+    let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+    if digits.is_empty() {
+        return Err(ParseBigNumberError::MissingExponent);
+    }
+    let value = parse_digits(digits, position + exponent.len() - digits.len(), 10)?;
+
+    // Applying the sign first lets the exponent reach i64::MIN
+    let sign = if exponent.starts_with('-') { Sign::Negative } else { Sign::Positive };
+    i64::try_from(BigNumber::from_biguint(sign, value)).map_err(|_| ParseBigNumberError::ExponentTooLarge)
+}
+
+// Splits a lenient decimal input into its sign and magnitude, rejecting `-`
+// unless `signed` is set
+fn parse_lenient_parts(input: &str, signed: bool) -> Result<(Sign, BigUint), ParseBigNumberError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(ParseBigNumberError::Empty);
+    }
+    let start = input.len() - input.trim_start().len();
+
+    let (sign, offset) = match trimmed.as_bytes()[0] {
+        b'+' => (Sign::Positive, 1),
+        b'-' if signed => (Sign::Negative, 1),
+        _ => (Sign::Positive, 0),
+    };
+    if trimmed.len() == offset {
+        return Err(ParseBigNumberError::BareSign);
+    }
+
+    let magnitude = parse_digits(&trimmed[offset..], start + offset, 10)?;
+    Ok((sign, magnitude))
+}
+
+// Rejects a run of valid digits with a redundant leading zero
+fn check_no_leading_zero(digits: &str) -> Result<(), ParseBigNumberError> {
+    if digits.len() > 1 && digits.starts_with('0') {
+        return Err(ParseBigNumberError::LeadingZero);
+    }
+    Ok(())
+}
+
+// Parses a non-empty unsigned literal with an optional radix prefix and `_`
+// separators; `offset` is its position within the original input
+fn parse_literal_digits(literal: &str, offset: usize) -> Result<BigUint, ParseBigNumberError> {
+    let (radix, prefix_len) = match literal.get(..2) {
+        Some("0x") => (16, 2),
+        Some("0o") => (8, 2),
+        Some("0b") => (2, 2),
+        _ => (10, 0),
+    };
+    let body = &literal[prefix_len..];
+
+    // Without a prefix the literal has to start with a digit
+    if prefix_len == 0 && body.starts_with('_') {
+        return Err(ParseBigNumberError::InvalidDigit {
+            position: offset,
+            character: '_',
+        });
+    }
+    if let Some((i, c)) = body.char_indices().find(|&(_, c)| c != '_' && !c.is_digit(radix)) {
+        return Err(ParseBigNumberError::InvalidDigit {
+            position: offset + prefix_len + i,
+            character: c,
+        });
+    }
+
+    let digits: String = body.chars().filter(|&c| c != '_').collect();
+    if digits.is_empty() {
+        return Err(ParseBigNumberError::BarePrefix);
+    }
+    parse_digits(&digits, offset + prefix_len, radix)
+}
+
+// Parses a non-empty run of digits in `radix`; `offset` is the position of
+// `digits_str` within the original input, used for error reporting
+fn parse_digits(digits_str: &str, offset: usize, radix: u32) -> Result<BigUint, ParseBigNumberError> {
+    if let Some((i, c)) = digits_str.char_indices().find(|(_, c)| !c.is_digit(radix)) {
+        return Err(ParseBigNumberError::InvalidDigit {
+            position: offset + i,
+            character: c,
+        });
+    }
+
+    Ok(parse_validated(digits_str.as_bytes(), radix))
+}
+
+// Parses a non-empty run of bytes already checked to be digits in `radix`
+fn parse_validated(bytes: &[u8], radix: u32) -> BigUint {
+    if bytes.len() < SPLIT_PARSE_DIGITS {
+        return parse_chunks(bytes, radix);
+    }
+
+    // Split off low halves of chunk_digits * 2^i digits, whose weights are
+    // the repeated squares of the chunk base
+    let chunk_digits = digits_per_limb(radix);
+    let mut powers = vec![BigUint::from(radix.pow(chunk_digits as u32))];
+    while chunk_digits << powers.len() < bytes.len() {
+        let power = powers.last().unwrap();
+        powers.push(power * power);
+    }
+    parse_split(bytes, radix, &powers)
+}
+
+// Inputs with at least this many digits are parsed by splitting them in half
+// recursively and combining the halves with one large multiplication, instead
+// of folding in one chunk at a time at quadratic cost
+const SPLIT_PARSE_DIGITS: usize = 2048;
+
+// Parses validated digits, where `powers[i]` is radix^(chunk_digits * 2^i)
+fn parse_split(bytes: &[u8], radix: u32, powers: &[BigUint]) -> BigUint {
+    if bytes.len() < SPLIT_PARSE_DIGITS {
+        return parse_chunks(bytes, radix);
+    }
+
+    let chunk_digits = digits_per_limb(radix);
+    let level = (0..powers.len()).rev().find(|&i| chunk_digits << i < bytes.len()).unwrap();
+    let (high, low) = bytes.split_at(bytes.len() - (chunk_digits << level));
+    let mut number = &parse_split(high, radix, &powers[..level]) * &powers[level];
+    number.add_assign_magnitude(&parse_split(low, radix, &powers[..level]));
+    number
+}
+
+// Parses validated digits by folding in as many at a time as fit in a limb,
+// starting with a shorter leading chunk
+fn parse_chunks(bytes: &[u8], radix: u32) -> BigUint {
+    let chunk_digits = digits_per_limb(radix);
+    let first_len = match bytes.len() % chunk_digits {
+        0 => chunk_digits,
+        len => len,
+    };
+
+    let mut number = BigUint::zero();
+    let mut start = 0;
+    let mut end = first_len;
+    while start < bytes.len() {
+        let chunk = bytes[start..end]
+            .iter()
+            .fold(0u32, |acc, &b| acc * radix + (b as char).to_digit(radix).unwrap());
+        number.mul_small(radix.pow((end - start) as u32));
+        number.add_small(chunk);
+
+        start = end;
+        end += chunk_digits;
+    }
+
+    number
+}
+
+// The largest k with radix^k <= u32::MAX
+pub(crate) fn digits_per_limb(radix: u32) -> usize {
+    u32::MAX.ilog(radix) as usize
+}
+
+pub(crate) fn check_radix(radix: u32) {
+    if !(2..=36).contains(&radix) {
+        panic!("radix must be between 2 and 36, got {}", radix);
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigNumberError;
+
+    fn from_str(input: &str) -> Result<BigUint, ParseBigNumberError> {
+        BigUint::parse(input)
+    }
+}
+
+impl FromStr for BigNumber {
+    type Err = ParseBigNumberError;
+
+    fn from_str(input: &str) -> Result<BigNumber, ParseBigNumberError> {
+        BigNumber::parse(input)
+    }
+}
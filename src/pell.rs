@@ -0,0 +1,59 @@
+use crate::{BigNumber, BigUint};
+
+impl BigNumber {
+    /// Returns the fundamental solution `(x, y)` of Pell's equation
+    /// `x^2 - d y^2 = 1`, the one with the smallest positive `x` other than the
+    /// trivial `(1, 0)`, or `None` if `d` is a perfect square.
+    ///
+    /// The solution is read off the convergents of the continued fraction of
+    /// `sqrt(d)` at the end of its first period, or its second period when the
+    /// period length is odd.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let (x, y) = BigNumber::pell(61).unwrap();
+    /// assert_eq!((x.to_string(), y.to_string()), ("1766319049".to_string(), "226153980".to_string()));
+    /// assert!(BigNumber::pell(49).is_none());
+    /// ```
+    pub fn pell(d: u64) -> Option<(BigNumber, BigNumber)> {
+        let a0 = d.isqrt();
+        if a0 * a0 == d {
+            return None;
+        }
+
+        // sqrt(d) = [a0; a1, a2, ...] with the complete quotients (sqrt(d) + m) / q
+        let (mut m, mut q, mut a) = (0u64, 1u64, a0);
+        // Consecutive convergents h / k, starting from h_-1 / k_-1 = 1 / 0
+        let (mut h_prev, mut h) = (BigUint::one(), BigUint::from(a0));
+        let (mut k_prev, mut k) = (BigUint::zero(), BigUint::one());
+        let mut period = 0;
+
+        loop {
+            m = a * q - m;
+            q = (d - m * m) / q;
+            a = (a0 + m) / q;
+            period += 1;
+            if a == 2 * a0 {
+                break;
+            }
+
+            let a_big = BigUint::from(a);
+            h_prev = &(&a_big * &h) + &h_prev;
+            k_prev = &(&a_big * &k) + &k_prev;
+            core::mem::swap(&mut h, &mut h_prev);
+            core::mem::swap(&mut k, &mut k_prev);
+        }
+
+        // An odd period gives a solution of x^2 - d y^2 = -1, and squaring
+        // x + y sqrt(d) turns it into one for +1
+        if period % 2 == 1 {
+            let dk2 = &(&k * &k) * &BigUint::from(d);
+            let hk = &h * &k;
+            h = &(&h * &h) + &dk2;
+            k = &hk + &hk;
+        }
+
+        Some((BigNumber::from(h), BigNumber::from(k)))
+    }
+}
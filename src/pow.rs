@@ -0,0 +1,90 @@
+use crate::{BarrettContext, BigNumber, BigUint, MontgomeryContext, Sign};
+
+impl BigUint {
+    /// Returns `self^exponent`, computed by binary exponentiation.
+    ///
+    /// `0^0` is `1`.
+    pub fn pow(&self, exponent: u64) -> BigUint {
+        let mut result = BigUint::one();
+        let mut base = self.clone();
+        let mut exponent = exponent;
+
+        // Right-to-left: square the base for every bit and multiply it in for set bits
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    /// Returns `self^exponent mod modulus` by square-and-multiply, reducing
+    /// after every step so intermediates stay below `modulus^2`.
+    ///
+    /// Odd moduli go through a [`MontgomeryContext`] and even ones through a
+    /// [`BarrettContext`], so no step needs a full division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn modpow(&self, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+        if modulus.is_zero() {
+            panic!("Division by zero");
+        }
+
+        if let Some(ctx) = MontgomeryContext::new(modulus) {
+            let base = ctx.to_montgomery(self);
+            return ctx.from_montgomery(&ctx.pow(&base, exponent));
+        }
+
+        let ctx = BarrettContext::new(modulus).unwrap();
+        ctx.pow(self, exponent)
+    }
+}
+
+impl BigNumber {
+    /// Returns `self^exponent`, computed by binary exponentiation.
+    ///
+    /// `0^0` is `1`, and a negative base gives a negative result for odd exponents.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("-2").pow(65);
+    /// assert_eq!(n.to_string(), "-36893488147419103232");
+    /// ```
+    pub fn pow(&self, exponent: u64) -> BigNumber {
+        let sign = if exponent % 2 == 1 { self.sign } else { Sign::Positive };
+        BigNumber::from_biguint(sign, self.magnitude.pow(exponent))
+    }
+
+    /// Returns `self^exponent mod modulus`, in the range `0..|modulus|`.
+    ///
+    /// A negative base is reduced into that range first, so `(-2)^3 mod 5` is `2`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("4");
+    /// let e = BigNumber::from_string("13");
+    /// let m = BigNumber::from_string("497");
+    /// assert_eq!(n.modpow(&e, &m).to_string(), "445");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `exponent` is negative or `modulus` is zero.
+    pub fn modpow(&self, exponent: &BigNumber, modulus: &BigNumber) -> BigNumber {
+        if exponent.is_negative() {
+            panic!("modpow with a negative exponent");
+        }
+
+        let base = self.rem_euclid(modulus);
+        BigNumber::from(base.magnitude.modpow(&exponent.magnitude, &modulus.magnitude))
+    }
+}
@@ -0,0 +1,329 @@
+use alloc::vec;
+
+#[cfg(feature = "rand")]
+use rand::RngCore;
+
+use crate::biguint::LIMB_BITS;
+use crate::sequence::lucas_chain;
+use crate::sieve::{small_primes, SIEVE_LIMIT};
+use crate::{BigNumber, BigUint, MontgomeryContext};
+#[cfg(feature = "rand")]
+use crate::random::random_below;
+
+// The first thirteen primes. Every composite below 3 317 044 064 679 887 385 961 981
+// fails the strong probable prime test for at least one of them.
+const DETERMINISTIC_BASES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+// Residues modulo 30 that are coprime to 2, 3 and 5
+const WHEEL: [u32; 8] = [1, 7, 11, 13, 17, 19, 23, 29];
+const WHEEL_PRIMES: [u32; 4] = [2, 3, 5, 7];
+
+// How many of the sieved primes `is_prime` trial divides by before the
+// probable prime tests
+const TRIAL_DIVISION_PRIMES: usize = 300;
+
+// 3 317 044 064 679 887 385 961 981 as limbs
+const DETERMINISTIC_LIMIT: [u32; 3] = [0x2410_a5fd, 0x51ad_c5b2, 0x2_be69];
+
+impl BigNumber {
+    /// Returns `true` if the number is prime.
+    ///
+    /// Below 3.3·10^24 (about 81 bits) the answer is exact: Miller-Rabin with
+    /// the first thirteen primes as bases has no strong pseudoprimes there.
+    /// Larger numbers get the Baillie-PSW test, a base 2 Miller-Rabin round
+    /// followed by a strong Lucas test, which has no known counterexample.
+    pub fn is_prime(&self) -> bool {
+        if self.is_negative() {
+            return false;
+        }
+        let n = &self.magnitude;
+        if n.limbs.len() == 1 && n.limbs[0] < SIEVE_LIMIT {
+            return small_primes().binary_search(&n.limbs[0]).is_ok();
+        }
+        if small_primes()[..TRIAL_DIVISION_PRIMES].iter().any(|&p| n.rem_small(p) == 0) {
+            return false;
+        }
+
+        let test = MillerRabin::new(n);
+        if *n < BigUint::from_limbs(DETERMINISTIC_LIMIT.to_vec()) {
+            return DETERMINISTIC_BASES
+                .iter()
+                .take_while(|&&base| BigUint::from_limbs(vec![base]) < *n)
+                .all(|&base| test.is_strong_probable_prime(&BigUint::from_limbs(vec![base])));
+        }
+
+        test.is_strong_probable_prime(&BigUint::from_limbs(vec![2]))
+            && is_strong_lucas_probable_prime(n)
+    }
+
+    /// Returns the smallest prime greater than the number.
+    ///
+    /// Candidates are stepped along a mod 30 wheel, so multiples of 2, 3 and 5
+    /// are never tested.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("1000000000000");
+    /// assert_eq!(n.next_prime().to_string(), "1000000000039");
+    /// ```
+    pub fn next_prime(&self) -> BigNumber {
+        if let Some(&prime) = WHEEL_PRIMES.iter().find(|&&p| *self < BigNumber::from_small(p)) {
+            return BigNumber::from_small(prime);
+        }
+
+        let mut candidate = &self.magnitude + &BigUint::one();
+        loop {
+            let residue = candidate.rem_small(30);
+            let next = WHEEL.iter().find(|&&w| w >= residue).copied().unwrap_or(31);
+            candidate.add_small(next - residue);
+
+            let number = BigNumber::from(candidate);
+            if number.is_prime() {
+                return number;
+            }
+            candidate = number.magnitude;
+            candidate.add_small(1);
+        }
+    }
+
+    /// Returns the largest prime less than the number, or `None` if there is
+    /// none.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("1000000000000");
+    /// assert_eq!(n.prev_prime().unwrap().to_string(), "999999999989");
+    /// assert_eq!(BigNumber::from_string("2").prev_prime(), None);
+    /// ```
+    pub fn prev_prime(&self) -> Option<BigNumber> {
+        if *self <= BigNumber::from_small(8) {
+            return WHEEL_PRIMES
+                .iter()
+                .rev()
+                .find(|&&p| BigNumber::from_small(p) < *self)
+                .map(|&p| BigNumber::from_small(p));
+        }
+
+        let mut candidate = &self.magnitude - &BigUint::one();
+        loop {
+            let residue = candidate.rem_small(30);
+            match WHEEL.iter().rev().find(|&&w| w <= residue) {
+                Some(&w) => candidate.sub_assign_unchecked(&BigUint::from_limbs(vec![residue - w])),
+                None => {
+                    // Residue 0 sits just above the previous block's 29
+                    candidate.sub_assign_unchecked(&BigUint::one());
+                    continue;
+                }
+            }
+
+            let number = BigNumber::from(candidate);
+            if number.is_prime() {
+                return Some(number);
+            }
+            candidate = number.magnitude;
+            candidate.sub_assign_unchecked(&BigUint::one());
+        }
+    }
+
+    /// Returns `true` if the Mersenne number `2^p - 1` is prime, by the
+    /// Lucas-Lehmer test.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert!(BigNumber::is_mersenne_prime(127));
+    /// assert!(!BigNumber::is_mersenne_prime(11));
+    /// ```
+    pub fn is_mersenne_prime(p: u64) -> bool {
+        if p == 2 {
+            return true;
+        }
+        // 2^p - 1 is composite whenever p is
+        if !BigNumber::from(BigUint::from_limbs(vec![p as u32, (p >> 32) as u32])).is_prime() {
+            return false;
+        }
+
+        let mut mersenne = BigUint::one();
+        mersenne.shl_bits(p);
+        let mersenne = &mersenne - &BigUint::one();
+
+        // s_0 = 4, s_i+1 = s_i^2 - 2, and 2^p - 1 is prime iff s_p-2 = 0
+        let mut s = BigUint::from_limbs(vec![4]);
+        let two = BigUint::from_limbs(vec![2]);
+        for _ in 0..p - 2 {
+            let mut square = reduce_mersenne(&s * &s, p, &mersenne);
+            if square < two {
+                square.add_assign_magnitude(&mersenne);
+            }
+            s = &square - &two;
+        }
+
+        s.is_zero()
+    }
+
+    /// Returns `true` if the number passes `rounds` rounds of the Miller-Rabin
+    /// test with random bases drawn from `rng`.
+    ///
+    /// A prime always passes. A composite passes each round with probability at
+    /// most 1/4, so the chance of a wrong `true` is below `4^-rounds`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// let mersenne = BigNumber::from_string("170141183460469231731687303715884105727");
+    /// assert!(mersenne.is_probable_prime(20, &mut rng));
+    /// assert!(!(&mersenne + &BigNumber::from_string("2")).is_probable_prime(20, &mut rng));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn is_probable_prime<R: RngCore + ?Sized>(&self, rounds: u32, rng: &mut R) -> bool {
+        if self.is_negative() {
+            return false;
+        }
+        let n = &self.magnitude;
+        if n.limbs.len() == 1 && n.limbs[0] < SIEVE_LIMIT {
+            return small_primes().binary_search(&n.limbs[0]).is_ok();
+        }
+        if n.is_even() {
+            return false;
+        }
+
+        let test = MillerRabin::new(n);
+        // Bases are drawn from 2..=n-2
+        let range = n - &BigUint::from_limbs(vec![3]);
+        (0..rounds).all(|_| {
+            let mut base = random_below(rng, &range);
+            base.add_small(2);
+            test.is_strong_probable_prime(&base)
+        })
+    }
+}
+
+// Reduces x modulo 2^p - 1 using 2^p = 1: fold the high bits onto the low ones
+fn reduce_mersenne(mut x: BigUint, p: u64, mersenne: &BigUint) -> BigUint {
+    while x.bits() > p {
+        let mut high = x.clone();
+        high.shr_bits(p);
+
+        x.limbs.truncate(p.div_ceil(LIMB_BITS as u64) as usize);
+        let top_bits = p % LIMB_BITS as u64;
+        if top_bits != 0 {
+            *x.limbs.last_mut().unwrap() &= (1 << top_bits) - 1;
+        }
+
+        x.normalize();
+        x.add_assign_magnitude(&high);
+    }
+
+    if x == *mersenne {
+        BigUint::zero()
+    } else {
+        x
+    }
+}
+
+// Miller-Rabin strong probable prime tests against a fixed odd n >= 5, with
+// n - 1 = d 2^s and all arithmetic in Montgomery form
+pub(crate) struct MillerRabin {
+    ctx: MontgomeryContext,
+    d: BigUint,
+    s: u64,
+    one: BigUint,
+    minus_one: BigUint,
+}
+
+impl MillerRabin {
+    pub(crate) fn new(n: &BigUint) -> MillerRabin {
+        let ctx = MontgomeryContext::new(n).unwrap();
+        let n_minus_one = n - &BigUint::one();
+        let s = n_minus_one.trailing_zeros();
+        let mut d = n_minus_one.clone();
+        d.shr_bits(s);
+
+        MillerRabin {
+            one: ctx.to_montgomery(&BigUint::one()),
+            minus_one: ctx.to_montgomery(&n_minus_one),
+            ctx,
+            d,
+            s,
+        }
+    }
+
+    // Returns `true` if n is a strong probable prime to `base`
+    pub(crate) fn is_strong_probable_prime(&self, base: &BigUint) -> bool {
+        let base = self.ctx.to_montgomery(base);
+        if base.is_zero() {
+            return true;
+        }
+
+        let mut x = self.ctx.pow(&base, &self.d);
+        if x == self.one || x == self.minus_one {
+            return true;
+        }
+
+        for _ in 1..self.s {
+            x = self.ctx.square(&x);
+            if x == self.minus_one {
+                return true;
+            }
+            if x == self.one {
+                return false;
+            }
+        }
+
+        false
+    }
+}
+
+// The strong Lucas probable prime test with Selfridge's parameters: P = 1 and
+// Q = (1 - D) / 4 for the first D in 5, -7, 9, -11, ... with (D / n) = -1.
+// n must be odd and at least 5.
+pub(crate) fn is_strong_lucas_probable_prime(n: &BigUint) -> bool {
+    // No suitable D exists for a perfect square
+    if n.is_perfect_square() {
+        return false;
+    }
+
+    let modulus = BigNumber::from(n.clone());
+    let mut d = BigNumber::from(BigUint::from_limbs(vec![5]));
+    loop {
+        match d.jacobi(&modulus) {
+            -1 => break,
+            0 if d.magnitude != *n => return false,
+            _ => {}
+        }
+        let step = BigUint::from_limbs(vec![2]);
+        let magnitude = &d.magnitude + &step;
+        d = BigNumber::from_biguint(d.sign.negated(), magnitude);
+    }
+
+    let mut q = &BigNumber::one() - &d;
+    q.magnitude.shr_bits(2);
+
+    // Write n + 1 = k 2^s with k odd and compute U_k, V_k and Q^k for P = 1
+    let n_plus_one = n + &BigUint::one();
+    let s = n_plus_one.trailing_zeros();
+    let mut k = n_plus_one;
+    k.shr_bits(s);
+    let (u, v, q_k) = lucas_chain(&BigNumber::one(), &q, &k, Some(&modulus));
+    let (u, mut v, mut q_k) = (u.magnitude, v.magnitude, q_k.magnitude);
+
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+
+    let reduce = |x: &BigUint| x % n;
+    // a - b mod n for a, b < n
+    let sub_mod = |a: &BigUint, b: &BigUint| if a >= b { a - b } else { &(a + n) - b };
+    for _ in 1..s {
+        v = sub_mod(&reduce(&(&v * &v)), &reduce(&(&q_k + &q_k)));
+        q_k = reduce(&(&q_k * &q_k));
+        if v.is_zero() {
+            return true;
+        }
+    }
+
+    false
+}
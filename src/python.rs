@@ -0,0 +1,275 @@
+//! Python bindings, behind the `python` feature.
+//!
+//! The `bignumber` extension module exposes a `BigNumber` class with the
+//! arithmetic and comparison operators of Python's `int`, which it mixes with
+//! freely, and the primality and factoring routines as methods and module
+//! level functions. [`BigNumber`] converts to and from `int` in function
+//! signatures, so Rust code embedding Python can pass numbers across directly.
+//!
+//! Build the module with
+//! `cargo rustc --release --lib --features python --crate-type cdylib` and
+//! copy `libbignumber.so` to `bignumber.so` (`bignumber.pyd` on Windows)
+//! somewhere on the Python path:
+//!
+//! ```python
+//! from bignumber import BigNumber, factorize
+//!
+//! n = BigNumber(2) ** 67 - 1
+//! print(n.is_prime(), factorize(n))  # False [(193707721, 1), (761838257287, 1)]
+//! ```
+
+use pyo3::exceptions::{PyOverflowError, PyValueError, PyZeroDivisionError};
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict, PyInt, PyString};
+
+use crate::BigNumber;
+
+/// Converts from a Python `int`, or anything else with `__index__` such as
+/// the `BigNumber` class.
+impl<'py> FromPyObject<'_, 'py> for BigNumber {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<'_, 'py, PyAny>) -> PyResult<BigNumber> {
+        if let Ok(number) = ob.cast::<PyBigNumber>() {
+            return Ok(number.get().0.clone());
+        }
+
+        // operator.index accepts ints and anything with `__index__`, and raises the
+        // usual TypeError otherwise
+        let py = ob.py();
+        let int = py.import("operator")?.call_method1("index", (ob,))?;
+        let bits: u64 = int.call_method0(pyo3::intern!(py, "bit_length"))?.extract()?;
+        // One more bit for the sign of the two's complement form
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("signed", true)?;
+        let bytes = int.call_method("to_bytes", (bits / 8 + 1, "little"), Some(&kwargs))?;
+        Ok(BigNumber::from_signed_bytes_le(bytes.cast::<PyBytes>()?.as_bytes()))
+    }
+}
+
+/// Converts to a Python `int`.
+impl<'py> IntoPyObject<'py> for &BigNumber {
+    type Target = PyInt;
+    type Output = Bound<'py, PyInt>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyInt>> {
+        let bytes = PyBytes::new(py, &self.to_signed_bytes_le());
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("signed", true)?;
+        let int = py.get_type::<PyInt>().call_method("from_bytes", (bytes, "little"), Some(&kwargs))?;
+        Ok(int.cast_into::<PyInt>()?)
+    }
+}
+
+/// Converts to a Python `int`.
+impl<'py> IntoPyObject<'py> for BigNumber {
+    type Target = PyInt;
+    type Output = Bound<'py, PyInt>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyInt>> {
+        (&self).into_pyobject(py)
+    }
+}
+
+/// The `BigNumber` class of the Python module.
+#[pyclass(name = "BigNumber", module = "bignumber", frozen)]
+pub struct PyBigNumber(BigNumber);
+
+impl From<BigNumber> for PyBigNumber {
+    fn from(number: BigNumber) -> PyBigNumber {
+        PyBigNumber(number)
+    }
+}
+
+// The error Python raises for a zero divisor
+fn check_divisor(divisor: &BigNumber) -> PyResult<()> {
+    if divisor.is_zero() {
+        return Err(PyZeroDivisionError::new_err("division by zero"));
+    }
+    Ok(())
+}
+
+#[pymethods]
+impl PyBigNumber {
+    /// Builds a number from an int, or from a decimal string with an optional
+    /// leading `-`.
+    #[new]
+    fn new(value: &Bound<'_, PyAny>) -> PyResult<PyBigNumber> {
+        if let Ok(text) = value.cast::<PyString>() {
+            let text = text.to_str()?;
+            return BigNumber::parse(text)
+                .map(PyBigNumber)
+                .map_err(|err| PyValueError::new_err(format!("invalid number {:?}: {}", text, err)));
+        }
+        value.extract::<BigNumber>().map(PyBigNumber)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BigNumber({})", self.0)
+    }
+
+    fn __int__(&self) -> BigNumber {
+        self.0.clone()
+    }
+
+    fn __index__(&self) -> BigNumber {
+        self.0.clone()
+    }
+
+    fn __bool__(&self) -> bool {
+        !self.0.is_zero()
+    }
+
+    // Hashes like the equal int, so the two can share dictionary keys
+    fn __hash__(&self, py: Python<'_>) -> PyResult<isize> {
+        (&self.0).into_pyobject(py)?.hash()
+    }
+
+    fn __richcmp__(&self, other: BigNumber, op: CompareOp) -> bool {
+        op.matches(self.0.cmp(&other))
+    }
+
+    fn __neg__(&self) -> PyBigNumber {
+        PyBigNumber(-&self.0)
+    }
+
+    fn __pos__(&self) -> PyBigNumber {
+        PyBigNumber(self.0.clone())
+    }
+
+    fn __abs__(&self) -> PyBigNumber {
+        PyBigNumber(BigNumber::from(self.0.magnitude().clone()))
+    }
+
+    fn __add__(&self, other: BigNumber) -> PyBigNumber {
+        PyBigNumber(&self.0 + &other)
+    }
+
+    fn __radd__(&self, other: BigNumber) -> PyBigNumber {
+        PyBigNumber(&other + &self.0)
+    }
+
+    fn __sub__(&self, other: BigNumber) -> PyBigNumber {
+        PyBigNumber(&self.0 - &other)
+    }
+
+    fn __rsub__(&self, other: BigNumber) -> PyBigNumber {
+        PyBigNumber(&other - &self.0)
+    }
+
+    fn __mul__(&self, other: BigNumber) -> PyBigNumber {
+        PyBigNumber(&self.0 * &other)
+    }
+
+    fn __rmul__(&self, other: BigNumber) -> PyBigNumber {
+        PyBigNumber(&other * &self.0)
+    }
+
+    // Division rounds towards negative infinity, as for int
+    fn __floordiv__(&self, other: BigNumber) -> PyResult<PyBigNumber> {
+        check_divisor(&other)?;
+        Ok(PyBigNumber(self.0.div_floor(&other)))
+    }
+
+    fn __rfloordiv__(&self, other: BigNumber) -> PyResult<PyBigNumber> {
+        check_divisor(&self.0)?;
+        Ok(PyBigNumber(other.div_floor(&self.0)))
+    }
+
+    fn __mod__(&self, other: BigNumber) -> PyResult<PyBigNumber> {
+        check_divisor(&other)?;
+        Ok(PyBigNumber(self.0.mod_floor(&other)))
+    }
+
+    fn __rmod__(&self, other: BigNumber) -> PyResult<PyBigNumber> {
+        check_divisor(&self.0)?;
+        Ok(PyBigNumber(other.mod_floor(&self.0)))
+    }
+
+    fn __divmod__(&self, other: BigNumber) -> PyResult<(PyBigNumber, PyBigNumber)> {
+        check_divisor(&other)?;
+        Ok((PyBigNumber(self.0.div_floor(&other)), PyBigNumber(self.0.mod_floor(&other))))
+    }
+
+    fn __pow__(&self, exponent: BigNumber, modulus: Option<BigNumber>) -> PyResult<PyBigNumber> {
+        if exponent.is_negative() {
+            return Err(PyValueError::new_err("negative exponents are not supported"));
+        }
+        match modulus {
+            Some(modulus) => {
+                if modulus.is_zero() {
+                    return Err(PyValueError::new_err("pow() 3rd argument cannot be 0"));
+                }
+                // modpow gives the residue in 0..|m|; int takes the sign of m
+                Ok(PyBigNumber(self.0.modpow(&exponent, &modulus).mod_floor(&modulus)))
+            }
+            None => {
+                let exponent = exponent
+                    .to_u64()
+                    .ok_or_else(|| PyOverflowError::new_err("exponent too large"))?;
+                Ok(PyBigNumber(self.0.pow(exponent)))
+            }
+        }
+    }
+
+    /// The number of bits in the absolute value, like `int.bit_length`.
+    fn bit_length(&self) -> u64 {
+        self.0.bits()
+    }
+
+    /// Returns the greatest common divisor with `other`.
+    fn gcd(&self, other: BigNumber) -> PyBigNumber {
+        PyBigNumber(self.0.gcd(&other))
+    }
+
+    /// Returns whether the number is prime.
+    fn is_prime(&self) -> bool {
+        self.0.is_prime()
+    }
+
+    /// Returns the smallest prime greater than the number.
+    fn next_prime(&self) -> PyBigNumber {
+        PyBigNumber(self.0.next_prime())
+    }
+
+    /// Returns the prime factorization as a list of `(prime, exponent)` pairs.
+    fn factorize(&self, py: Python<'_>) -> Vec<(BigNumber, u32)> {
+        py.detach(|| self.0.factorize())
+    }
+}
+
+/// Returns whether `n` is prime.
+#[pyfunction]
+fn is_prime(n: BigNumber) -> bool {
+    n.is_prime()
+}
+
+/// Returns the smallest prime greater than `n`.
+#[pyfunction]
+fn next_prime(n: BigNumber) -> BigNumber {
+    n.next_prime()
+}
+
+/// Returns the prime factorization of `n` as a list of `(prime, exponent)`
+/// pairs, releasing the interpreter while it runs.
+#[pyfunction]
+fn factorize(py: Python<'_>, n: BigNumber) -> Vec<(BigNumber, u32)> {
+    py.detach(|| n.factorize())
+}
+
+/// The `bignumber` extension module.
+#[pymodule]
+pub fn bignumber(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyBigNumber>()?;
+    module.add_function(wrap_pyfunction!(is_prime, module)?)?;
+    module.add_function(wrap_pyfunction!(next_prime, module)?)?;
+    module.add_function(wrap_pyfunction!(factorize, module)?)?;
+    Ok(())
+}
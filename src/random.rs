@@ -0,0 +1,196 @@
+//! Random numbers, behind the `rand` feature.
+//!
+//! Every function takes the generator as a `&mut R` for any
+//! [`RngCore`](rand::RngCore), including `dyn RngCore`, and never creates one
+//! of its own, so a seeded generator gives the same numbers on every run.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use rand::RngCore;
+
+use crate::sieve::small_primes;
+use crate::{BigNumber, BigUint};
+
+// Odd primes the candidates of `random_prime` are sieved by
+const PRIME_SIEVE_PRIMES: usize = 1000;
+// Consecutive odd candidates sieved from each random starting point
+const PRIME_SIEVE_WINDOW: usize = 4096;
+
+impl BigNumber {
+    /// Returns a uniformly random number with exactly `bits` binary digits,
+    /// that is in `2^(bits - 1)..2^bits`; zero bits give zero.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::random_bits(&mut rand::thread_rng(), 100);
+    /// assert_eq!(n.bits(), 100);
+    /// ```
+    pub fn random_bits<R: RngCore + ?Sized>(rng: &mut R, bits: u64) -> BigNumber {
+        let mut magnitude = random_bits_below(rng, bits);
+        if bits > 0 {
+            magnitude.set_bit(bits - 1);
+        }
+        BigNumber::from(magnitude)
+    }
+
+    /// Returns a uniformly random number with exactly `digits` decimal digits,
+    /// that is in `10^(digits - 1)..10^digits`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::random_digits(&mut rand::thread_rng(), 40);
+    /// assert_eq!(n.to_string().len(), 40);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is zero.
+    pub fn random_digits<R: RngCore + ?Sized>(rng: &mut R, digits: usize) -> BigNumber {
+        assert!(digits > 0, "random_digits needs a positive digit count");
+
+        // 10^(d-1) + [0, 9 * 10^(d-1))
+        let low = BigUint::pow10(digits - 1);
+        let mut span = low.clone();
+        span.mul_small(9);
+        BigNumber::from(&low + &random_below(rng, &span))
+    }
+
+    /// Returns a uniformly random number in `0..bound`.
+    ///
+    /// Candidates with the bit length of `bound` are drawn until one falls
+    /// below it, which takes fewer than two draws on average and keeps the
+    /// distribution exactly uniform, unlike reducing a random number modulo
+    /// `bound`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let bound = BigNumber::from_string("1000000000000000000000");
+    /// let n = BigNumber::random_below(&mut rand::thread_rng(), &bound);
+    /// assert!(!n.is_negative() && n < bound);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is not positive.
+    pub fn random_below<R: RngCore + ?Sized>(rng: &mut R, bound: &BigNumber) -> BigNumber {
+        assert!(
+            bound.is_positive() && !bound.is_zero(),
+            "random_below needs a positive bound"
+        );
+        BigNumber::from(random_below(rng, &bound.magnitude))
+    }
+
+    /// Returns a uniformly random number in `range`, which may include
+    /// negative numbers.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::random_range(&mut rand::thread_rng(), BigNumber::from(-5)..BigNumber::from(5));
+    /// assert!(n >= BigNumber::from(-5) && n < BigNumber::from(5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn random_range<R: RngCore + ?Sized>(rng: &mut R, range: Range<BigNumber>) -> BigNumber {
+        assert!(range.start < range.end, "random_range needs a non-empty range");
+        let span = &range.end - &range.start;
+        &range.start + &BigNumber::from(random_below(rng, &span.magnitude))
+    }
+
+    /// Returns a random prime with exactly `bits` binary digits.
+    ///
+    /// A random odd starting point with the top bit set is chosen, and the odd
+    /// numbers after it are sieved by the small primes, so only candidates
+    /// without a small factor reach the full [`is_prime`](BigNumber::is_prime)
+    /// test. Primes that follow a long gap are therefore slightly more likely
+    /// than others, as with most practical generators. With 2 bits the result
+    /// is 2 or 3, each half the time.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let p = BigNumber::random_prime(&mut rand::thread_rng(), 128);
+    /// assert_eq!(p.bits(), 128);
+    /// assert!(p.is_prime());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is below 2.
+    pub fn random_prime<R: RngCore + ?Sized>(rng: &mut R, bits: u64) -> BigNumber {
+        assert!(bits >= 2, "random_prime needs at least 2 bits");
+
+        // 2 is the one even prime, so forcing the candidates odd would miss it
+        if bits == 2 {
+            return BigNumber::from(2 + (rng.next_u32() & 1));
+        }
+
+        // Below the sieve primes, just test random odd numbers
+        if bits <= 32 {
+            loop {
+                let mut candidate = BigNumber::random_bits(rng, bits);
+                candidate.magnitude.set_bit(0);
+                if candidate.is_prime() {
+                    return candidate;
+                }
+            }
+        }
+
+        let primes = &small_primes()[1..=PRIME_SIEVE_PRIMES];
+        loop {
+            let mut start = BigNumber::random_bits(rng, bits).magnitude;
+            start.set_bit(0);
+
+            // composite[i] marks start + 2i as having a small factor
+            let mut composite = vec![false; PRIME_SIEVE_WINDOW];
+            for &p in primes {
+                let residue = start.rem_small(p) as usize;
+                let p = p as usize;
+                // The first i with start + 2i = 0 (mod p), using 2^-1 = (p + 1) / 2
+                let first = (p - residue) * p.div_ceil(2) % p;
+                for i in (first..PRIME_SIEVE_WINDOW).step_by(p) {
+                    composite[i] = true;
+                }
+            }
+
+            for (i, _) in composite.iter().enumerate().filter(|(_, &composite)| !composite) {
+                let mut candidate = start.clone();
+                candidate.add_small(2 * i as u32);
+                if candidate.bits() > bits {
+                    break;
+                }
+                let candidate = BigNumber::from(candidate);
+                if candidate.is_prime() {
+                    return candidate;
+                }
+            }
+        }
+    }
+}
+
+// A uniformly random number in 0..2^bits
+fn random_bits_below<R: RngCore + ?Sized>(rng: &mut R, bits: u64) -> BigUint {
+    let limbs = bits.div_ceil(32) as usize;
+    let mut value: Vec<u32> = (0..limbs).map(|_| rng.next_u32()).collect();
+    if let Some(top) = value.last_mut() {
+        *top &= u32::MAX >> ((32 - bits % 32) % 32);
+    }
+    BigUint::from_limbs(value)
+}
+
+// A uniformly random number in 0..bound, by rejection sampling on the bit length
+pub(crate) fn random_below<R: RngCore + ?Sized>(rng: &mut R, bound: &BigUint) -> BigUint {
+    loop {
+        let candidate = random_bits_below(rng, bound.bits());
+        if candidate < *bound {
+            return candidate;
+        }
+    }
+}
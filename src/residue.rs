@@ -0,0 +1,189 @@
+use alloc::vec;
+
+use crate::{BigNumber, BigUint};
+
+impl BigNumber {
+    /// Returns the Jacobi symbol `(self / n)`, which is `-1`, `0` or `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is not a positive odd number.
+    pub fn jacobi(&self, n: &BigNumber) -> i32 {
+        if n.is_negative() || n.magnitude.is_even() {
+            panic!("Jacobi symbol needs a positive odd modulus");
+        }
+
+        jacobi(self.rem_euclid(n).magnitude, n.magnitude.clone())
+    }
+
+    /// Returns the Legendre symbol `(self / p)`: `1` if the number is a non-zero
+    /// quadratic residue modulo the odd prime `p`, `-1` if it is a non-residue and
+    /// `0` if `p` divides it.
+    ///
+    /// This is the Jacobi symbol restricted to prime moduli, so a composite `p`
+    /// gives the Jacobi symbol rather than an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not a positive odd number.
+    pub fn legendre(&self, p: &BigNumber) -> i32 {
+        self.jacobi(p)
+    }
+
+    /// Returns the Kronecker symbol `(self / n)`, the extension of the Jacobi
+    /// symbol to every integer `n`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = |s: &str| BigNumber::from_string(s);
+    /// assert_eq!(n("5").kronecker(&n("-8")), -1);
+    /// assert_eq!(n("-3").kronecker(&n("0")), 0);
+    /// assert_eq!(n("1").kronecker(&n("0")), 1);
+    /// ```
+    pub fn kronecker(&self, n: &BigNumber) -> i32 {
+        if n.is_zero() {
+            return (self.magnitude == BigUint::one()) as i32;
+        }
+
+        let twos = n.magnitude.trailing_zeros();
+        if twos > 0 && self.magnitude.is_even() {
+            return 0;
+        }
+
+        // (a / 2) is 1 for a = 1, 7 (mod 8) and -1 for a = 3, 5 (mod 8)
+        let mut result = 1;
+        let low = self.rem_euclid(&BigNumber::from(BigUint::from_limbs(vec![8])));
+        if twos % 2 == 1 && matches!(low.magnitude.limbs[0], 3 | 5) {
+            result = -result;
+        }
+
+        // (a / -1) is -1 exactly when a is negative
+        if n.is_negative() && self.is_negative() {
+            result = -result;
+        }
+
+        let mut odd = n.magnitude.clone();
+        odd.shr_bits(twos);
+        let odd = BigNumber::from(odd);
+        result * jacobi(self.rem_euclid(&odd).magnitude, odd.magnitude)
+    }
+
+    /// Returns a square root of the number modulo `prime`, or `None` if the
+    /// number is not a quadratic residue.
+    ///
+    /// The result is the smaller of the two roots `r` and `prime - r`. `prime`
+    /// must be a prime; for other moduli the result is meaningless and may be
+    /// `None` even when a root exists.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let p = BigNumber::from_string("13");
+    /// assert_eq!(BigNumber::from_string("10").mod_sqrt(&p).unwrap().to_string(), "6");
+    /// assert_eq!(BigNumber::from_string("5").mod_sqrt(&p), None);
+    /// ```
+    pub fn mod_sqrt(&self, prime: &BigNumber) -> Option<BigNumber> {
+        if prime.is_negative() || prime.magnitude <= BigUint::one() {
+            return None;
+        }
+
+        let p = &prime.magnitude;
+        let a = self.rem_euclid(prime).magnitude;
+        let root = tonelli_shanks(&a, p)?;
+
+        let other = p - &root;
+        Some(BigNumber::from(if other < root { other } else { root }))
+    }
+}
+
+// Tonelli-Shanks for an odd prime `p` (2 is handled directly), with the direct
+// formula for p = 3 (mod 4)
+fn tonelli_shanks(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let one = BigUint::one();
+    if a.is_zero() || *p == BigUint::from_limbs(vec![2]) {
+        return Some(a.clone());
+    }
+
+    if jacobi(a.clone(), p.clone()) != 1 {
+        return None;
+    }
+
+    if p.limbs[0] % 4 == 3 {
+        let mut exponent = p + &one;
+        exponent.shr_bits(2);
+        return Some(a.modpow(&exponent, p));
+    }
+
+    // Write p - 1 = q 2^s with q odd
+    let p_minus_one = p - &one;
+    let s = p_minus_one.trailing_zeros();
+    let mut q = p_minus_one.clone();
+    q.shr_bits(s);
+
+    // Any quadratic non-residue will do; half of all candidates are one
+    let mut z = BigUint::from_limbs(vec![2]);
+    while jacobi(z.clone(), p.clone()) != -1 {
+        z.add_small(1);
+        if z >= *p {
+            return None;
+        }
+    }
+
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = a.modpow(&q, p);
+    let mut exponent = &q + &one;
+    exponent.shr_bits(1);
+    let mut r = a.modpow(&exponent, p);
+
+    while t != one {
+        // The least i with t^(2^i) = 1
+        let mut i = 0;
+        let mut power = t.clone();
+        while power != one {
+            power = &(&power * &power) % p;
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = &(&b * &b) % p;
+        }
+        m = i;
+        c = &(&b * &b) % p;
+        t = &(&t * &c) % p;
+        r = &(&r * &b) % p;
+    }
+
+    Some(r)
+}
+
+// The Jacobi symbol (a / n) for odd n, by quadratic reciprocity
+fn jacobi(mut a: BigUint, mut n: BigUint) -> i32 {
+    let mut result = 1;
+    a = &a % &n;
+
+    while !a.is_zero() {
+        let twos = a.trailing_zeros();
+        a.shr_bits(twos);
+        if twos % 2 == 1 && matches!(n.limbs[0] % 8, 3 | 5) {
+            result = -result;
+        }
+
+        core::mem::swap(&mut a, &mut n);
+        if a.limbs[0] % 4 == 3 && n.limbs[0] % 4 == 3 {
+            result = -result;
+        }
+        a = &a % &n;
+    }
+
+    if n == BigUint::one() {
+        result
+    } else {
+        0
+    }
+}
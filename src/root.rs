@@ -0,0 +1,255 @@
+use crate::sieve::small_primes;
+use crate::{BigNumber, BigUint, Sign};
+
+// Bit r of each mask is set when r is a square modulo the given number
+const SQUARES_MOD_64: u128 = square_residues(64);
+const SQUARES_MOD_63: u128 = square_residues(63);
+const SQUARES_MOD_65: u128 = square_residues(65);
+const SQUARES_MOD_11: u128 = square_residues(11);
+
+const fn square_residues(modulus: u32) -> u128 {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < modulus {
+        mask |= 1 << (i * i % modulus);
+        i += 1;
+    }
+    mask
+}
+
+impl BigUint {
+    /// Returns the integer square root, the largest `r` with `r * r <= self`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("99999999999999999999");
+    /// assert_eq!(n.magnitude().isqrt().to_string(), "9999999999");
+    /// ```
+    pub fn isqrt(&self) -> BigUint {
+        if self.is_zero() {
+            return BigUint::zero();
+        }
+
+        // Newton's iteration from a power of two that is at least the root
+        // decreases until it reaches the floor
+        let mut x = BigUint::one();
+        x.shl_bits(self.bits().div_ceil(2));
+
+        loop {
+            let mut next = &x + &(self / &x);
+            next.shr_bits(1);
+            if next >= x {
+                return x;
+            }
+            x = next;
+        }
+    }
+
+    /// Returns the integer `n`th root, the largest `r` with `r^n <= self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn nth_root(&self, n: u32) -> BigUint {
+        if n == 0 {
+            panic!("Zeroth root is undefined");
+        }
+        if n == 1 || self.is_zero() {
+            return self.clone();
+        }
+        if n == 2 {
+            return self.isqrt();
+        }
+        // Roots of numbers below 2^n are 1
+        if self.bits() <= n as u64 {
+            return BigUint::one();
+        }
+
+        // Newton's iteration x -> ((n - 1) x + self / x^(n - 1)) / n, started
+        // from a power of two that is at least the root
+        let mut x = BigUint::one();
+        x.shl_bits(self.bits().div_ceil(n as u64));
+
+        loop {
+            let mut next = &x * &BigUint::from(n as u64 - 1);
+            next.add_assign_magnitude(&(self / &x.pow(n as u64 - 1)));
+            next.div_rem_small(n);
+            if next >= x {
+                return x;
+            }
+            x = next;
+        }
+    }
+
+    // Rules out about 99.4% of non-squares by their residues modulo 64, 63, 65
+    // and 11 before falling back to the square root
+    pub(crate) fn is_perfect_square(&self) -> bool {
+        if SQUARES_MOD_64 >> (self.limbs[0] & 63) & 1 == 0 {
+            return false;
+        }
+        let r = self.rem_small(63 * 65 * 11);
+        if SQUARES_MOD_63 >> (r % 63) & 1 == 0
+            || SQUARES_MOD_65 >> (r % 65) & 1 == 0
+            || SQUARES_MOD_11 >> (r % 11) & 1 == 0
+        {
+            return false;
+        }
+
+        let root = self.isqrt();
+        &root * &root == *self
+    }
+
+    /// Returns the integer square root together with the remainder
+    /// `self - root * root`.
+    pub fn sqrt_rem(&self) -> (BigUint, BigUint) {
+        let root = self.isqrt();
+        let remainder = self - &(&root * &root);
+        (root, remainder)
+    }
+}
+
+impl BigNumber {
+    /// Returns the integer square root, the largest `r` with `r * r <= self`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("1000000").isqrt().to_string(), "1000");
+    /// assert_eq!(BigNumber::from_string("999999").isqrt().to_string(), "999");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is negative.
+    pub fn isqrt(&self) -> BigNumber {
+        assert_non_negative(self);
+        BigNumber::from(self.magnitude.isqrt())
+    }
+
+    /// Returns the integer square root and the remainder `self - root * root`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is negative.
+    pub fn sqrt_rem(&self) -> (BigNumber, BigNumber) {
+        assert_non_negative(self);
+        let (root, remainder) = self.magnitude.sqrt_rem();
+        (BigNumber::from(root), BigNumber::from(remainder))
+    }
+
+    /// Returns the floor of the real `n`th root of the number.
+    ///
+    /// Odd roots of negative numbers are negative, and round towards negative
+    /// infinity like the non-negative ones.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::from_string("1000").nth_root(3).to_string(), "10");
+    /// assert_eq!(BigNumber::from_string("-1001").nth_root(3).to_string(), "-11");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero, or if `n` is even and the number is negative.
+    pub fn nth_root(&self, n: u32) -> BigNumber {
+        if self.is_negative() && n.is_multiple_of(2) {
+            panic!("Even root of a negative number");
+        }
+
+        let root = self.magnitude.nth_root(n);
+        if self.is_negative() && root.pow(n as u64) != self.magnitude {
+            return BigNumber::from_biguint(Sign::Negative, &root + &BigUint::one());
+        }
+        BigNumber::from_biguint(self.sign, root)
+    }
+
+    /// Returns `true` if the number is the square of an integer.
+    ///
+    /// Most non-squares are rejected by their residues modulo a few small
+    /// numbers, so only likely candidates pay for a square root.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert!(BigNumber::from_string("152415787532388367501905199875019052100").is_perfect_square());
+    /// assert!(!BigNumber::from_string("152415787532388367501905199875019052101").is_perfect_square());
+    /// ```
+    pub fn is_perfect_square(&self) -> bool {
+        !self.is_negative() && self.magnitude.is_perfect_square()
+    }
+
+    /// Returns `(base, k)` with `base^k == self` and `k >= 2` as large as
+    /// possible, or `None` if the number is not a perfect power.
+    ///
+    /// Negative numbers are only powers with odd exponents. Zero and `±1` are
+    /// powers with any exponent and give `None`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let (base, k) = BigNumber::from_string("-2187").is_perfect_power().unwrap();
+    /// assert_eq!((base.to_string(), k), ("-3".to_string(), 7));
+    /// assert_eq!(BigNumber::from_string("1000001").is_perfect_power(), None);
+    /// ```
+    pub fn is_perfect_power(&self) -> Option<(BigNumber, u32)> {
+        if self.magnitude <= BigUint::one() {
+            return None;
+        }
+
+        // Take prime roots for as long as one is exact
+        let mut base = self.magnitude.clone();
+        let mut exponent = 1;
+        while let Some((root, p)) = exact_prime_root(&base) {
+            base = root;
+            exponent *= p;
+        }
+
+        if self.is_negative() {
+            while exponent.is_multiple_of(2) {
+                base = &base * &base;
+                exponent /= 2;
+            }
+        }
+
+        if exponent == 1 {
+            return None;
+        }
+        Some((BigNumber::from_biguint(self.sign, base), exponent))
+    }
+
+    /// Returns the integer square root; this is the same as
+    /// [`isqrt`](BigNumber::isqrt).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is negative.
+    pub fn sqrt(&self) -> BigNumber {
+        self.isqrt()
+    }
+}
+
+// Finds the smallest prime p with n = r^p for some r, for n >= 2. Exponents
+// beyond log2(n) would need r < 2.
+pub(crate) fn exact_prime_root(n: &BigUint) -> Option<(BigUint, u32)> {
+    if n.is_perfect_square() {
+        return Some((n.isqrt(), 2));
+    }
+
+    let max_exponent = (n.bits() - 1) as u32;
+    small_primes()
+        .iter()
+        .skip(1)
+        .take_while(|&&p| p <= max_exponent)
+        .find_map(|&p| {
+            let root = n.nth_root(p);
+            (root.pow(p as u64) == *n).then_some((root, p))
+        })
+}
+
+fn assert_non_negative(n: &BigNumber) {
+    if n.is_negative() {
+        panic!("Square root of a negative number");
+    }
+}
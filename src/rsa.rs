@@ -0,0 +1,214 @@
+//! Textbook RSA built on the crate's own number theory, behind the `rand`
+//! feature.
+//!
+//! This is a demonstration of [`BigNumber::random_prime`],
+//! [`BigNumber::mod_inverse`] and [`BigNumber::modpow`] working together, not
+//! a cryptographic library: there is no padding, so equal messages encrypt to
+//! equal ciphertexts and signatures can be combined, and nothing runs in
+//! constant time.
+//!
+//! ```
+//! use bignumber::rsa::PrivateKey;
+//! use bignumber::{BigNumber, Sign};
+//!
+//! let key = PrivateKey::generate(&mut rand::thread_rng(), 512);
+//! let message = BigNumber::from_bytes_be(Sign::Positive, b"attack at dawn");
+//!
+//! let ciphertext = key.public_key().encrypt(&message);
+//! assert_eq!(key.decrypt(&ciphertext), message);
+//!
+//! let signature = key.sign(&message);
+//! assert!(key.public_key().verify(&message, &signature));
+//! ```
+
+use core::fmt;
+
+use rand::RngCore;
+
+use crate::BigNumber;
+
+// The usual public exponent, 2^16 + 1
+const PUBLIC_EXPONENT: u32 = 65537;
+// The smallest modulus `generate` accepts
+const MIN_BITS: u64 = 64;
+
+/// An RSA public key, the modulus `n` and the exponent `e`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    modulus: BigNumber,
+    exponent: BigNumber,
+}
+
+impl PublicKey {
+    /// Builds a key from its modulus and exponent.
+    pub fn new(modulus: BigNumber, exponent: BigNumber) -> PublicKey {
+        PublicKey { modulus, exponent }
+    }
+
+    /// Returns the modulus `n`.
+    pub fn modulus(&self) -> &BigNumber {
+        &self.modulus
+    }
+
+    /// Returns the public exponent `e`.
+    pub fn exponent(&self) -> &BigNumber {
+        &self.exponent
+    }
+
+    /// Returns `message^e mod n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `message` is not in `0..n`.
+    pub fn encrypt(&self, message: &BigNumber) -> BigNumber {
+        self.check_range(message);
+        message.modpow(&self.exponent, &self.modulus)
+    }
+
+    /// Returns `true` if `signature` is a valid signature of `message`, that is
+    /// `signature^e mod n == message`.
+    pub fn verify(&self, message: &BigNumber, signature: &BigNumber) -> bool {
+        self.in_range(message)
+            && self.in_range(signature)
+            && signature.modpow(&self.exponent, &self.modulus) == *message
+    }
+
+    fn in_range(&self, value: &BigNumber) -> bool {
+        !value.is_negative() && *value < self.modulus
+    }
+
+    fn check_range(&self, value: &BigNumber) {
+        assert!(self.in_range(value), "RSA input must be in 0..n");
+    }
+}
+
+/// An RSA private key, which keeps the factors of the modulus to decrypt and
+/// sign through the Chinese remainder theorem.
+///
+/// With the `zeroize` feature the secret values are cleared when the key is
+/// dropped. The `Debug` output only shows the public half.
+#[derive(Clone)]
+pub struct PrivateKey {
+    public: PublicKey,
+    exponent: BigNumber,
+    p: BigNumber,
+    q: BigNumber,
+    // d mod (p - 1), d mod (q - 1) and q^-1 mod p
+    exponent_p: BigNumber,
+    exponent_q: BigNumber,
+    q_inverse: BigNumber,
+}
+
+impl PrivateKey {
+    /// Generates a key with a modulus of exactly `bits` binary digits and the
+    /// public exponent 65537, drawing the primes from `rng`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is below 64.
+    pub fn generate<R: RngCore + ?Sized>(rng: &mut R, bits: u64) -> PrivateKey {
+        assert!(bits >= MIN_BITS, "RSA keys need at least {} bits", MIN_BITS);
+
+        let e = BigNumber::from(PUBLIC_EXPONENT);
+        let one = BigNumber::one();
+        // A prime whose p - 1 shares a factor with e would leave e without an inverse
+        let mut prime = |bits| loop {
+            let p = BigNumber::random_prime(rng, bits);
+            if (&p - &one).gcd(&e) == one {
+                break p;
+            }
+        };
+
+        loop {
+            let p = prime(bits - bits / 2);
+            let q = prime(bits / 2);
+            if p == q {
+                continue;
+            }
+            let modulus = &p * &q;
+            if modulus.bits() != bits {
+                continue;
+            }
+
+            let lambda = (&p - &one).lcm(&(&q - &one));
+            let d = e.mod_inverse(&lambda).unwrap();
+            return PrivateKey::from_parts(PublicKey::new(modulus, e), d, p, q);
+        }
+    }
+
+    /// Builds a key from the public key, the private exponent `d` and the two
+    /// prime factors of the modulus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p * q` is not the modulus of `public`, or if `p` and `q` are
+    /// equal.
+    pub fn from_parts(public: PublicKey, exponent: BigNumber, p: BigNumber, q: BigNumber) -> PrivateKey {
+        assert!(&p * &q == public.modulus, "p * q must equal the modulus");
+        let one = BigNumber::one();
+        let exponent_p = exponent.rem_euclid(&(&p - &one));
+        let exponent_q = exponent.rem_euclid(&(&q - &one));
+        let q_inverse = q.mod_inverse(&p).expect("p and q must be distinct primes");
+        PrivateKey {
+            public,
+            exponent,
+            p,
+            q,
+            exponent_p,
+            exponent_q,
+            q_inverse,
+        }
+    }
+
+    /// Returns the public half of the key.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    /// Returns the private exponent `d`.
+    pub fn exponent(&self) -> &BigNumber {
+        &self.exponent
+    }
+
+    /// Returns the prime factors `p` and `q` of the modulus.
+    pub fn primes(&self) -> (&BigNumber, &BigNumber) {
+        (&self.p, &self.q)
+    }
+
+    /// Returns `ciphertext^d mod n`, undoing [`PublicKey::encrypt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ciphertext` is not in `0..n`.
+    pub fn decrypt(&self, ciphertext: &BigNumber) -> BigNumber {
+        self.public.check_range(ciphertext);
+        self.power(ciphertext)
+    }
+
+    /// Returns the signature `message^d mod n`, which
+    /// [`PublicKey::verify`] accepts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `message` is not in `0..n`.
+    pub fn sign(&self, message: &BigNumber) -> BigNumber {
+        self.public.check_range(message);
+        self.power(message)
+    }
+
+    // x^d mod n from the powers modulo p and q, which are a quarter of the work
+    fn power(&self, x: &BigNumber) -> BigNumber {
+        let mp = x.modpow(&self.exponent_p, &self.p);
+        let mq = x.modpow(&self.exponent_q, &self.q);
+        let h = (&self.q_inverse * &(&mp - &mq)).rem_euclid(&self.p);
+        &mq + &(&h * &self.q)
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("public", &self.public)
+            .finish_non_exhaustive()
+    }
+}
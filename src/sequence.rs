@@ -0,0 +1,107 @@
+use crate::{BigNumber, BigUint};
+
+impl BigNumber {
+    /// Returns the Fibonacci number `F(n)`, with `F(0) = 0` and `F(1) = 1`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// assert_eq!(BigNumber::fibonacci(100).to_string(), "354224848179261915075");
+    /// ```
+    pub fn fibonacci(n: u64) -> BigNumber {
+        BigNumber::fibonacci_pair(n).0
+    }
+
+    /// Returns the consecutive Fibonacci numbers `(F(n), F(n + 1))`.
+    ///
+    /// Uses the fast doubling identities `F(2k) = F(k) (2 F(k + 1) - F(k))` and
+    /// `F(2k + 1) = F(k)^2 + F(k + 1)^2`, so it takes `O(log n)` multiplications.
+    pub fn fibonacci_pair(n: u64) -> (BigNumber, BigNumber) {
+        let mut f0 = BigNumber::zero();
+        let mut f1 = BigNumber::one();
+
+        for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+            let doubled = &(&f1 + &f1) - &f0;
+            let even = &f0 * &doubled;
+            let odd = &(&f0 * &f0) + &(&f1 * &f1);
+            if n >> bit & 1 == 1 {
+                f1 = &even + &odd;
+                f0 = odd;
+            } else {
+                f0 = even;
+                f1 = odd;
+            }
+        }
+
+        (f0, f1)
+    }
+
+    /// Returns the Lucas sequence terms `(U_n, V_n)` for the parameters `p` and
+    /// `q`, defined by `U_0 = 0`, `U_1 = 1`, `V_0 = 2`, `V_1 = p` and
+    /// `X_k = p X_(k-1) - q X_(k-2)` for both sequences.
+    ///
+    /// `p = 1, q = -1` gives the Fibonacci and Lucas numbers. The terms are
+    /// computed by doubling along the bits of `n`.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let (p, q) = (BigNumber::from_string("3"), BigNumber::from_string("2"));
+    /// let (u, v) = BigNumber::lucas_sequence(&p, &q, 10);
+    /// assert_eq!((u.to_string(), v.to_string()), ("1023".to_string(), "1025".to_string()));
+    /// ```
+    pub fn lucas_sequence(p: &BigNumber, q: &BigNumber, n: u64) -> (BigNumber, BigNumber) {
+        let (u, v, _) = lucas_chain(p, q, &BigUint::from(n), None);
+        (u, v)
+    }
+}
+
+// Computes (U_k, V_k, q^k), reduced into [0, m) when a modulus m is given,
+// which must then be odd. Doubling uses U_2j = U_j V_j and
+// V_2j = V_j^2 - 2 q^j, and the odd steps U_j+1 = (p U_j + V_j) / 2 and
+// V_j+1 = (D U_j + p V_j) / 2 with D = p^2 - 4q. Those halvings are exact over
+// the integers, and modulo m an odd value is made even by adding m first.
+pub(crate) fn lucas_chain(
+    p: &BigNumber,
+    q: &BigNumber,
+    k: &BigUint,
+    modulus: Option<&BigNumber>,
+) -> (BigNumber, BigNumber, BigNumber) {
+    let reduce = |x: BigNumber| match modulus {
+        Some(m) => x.rem_euclid(m),
+        None => x,
+    };
+    let halve = |x: BigNumber| {
+        let x = match modulus {
+            Some(m) if !x.magnitude.is_even() => &x + m,
+            _ => x,
+        };
+        let mut magnitude = x.magnitude;
+        magnitude.shr_bits(1);
+        BigNumber::from_biguint(x.sign, magnitude)
+    };
+
+    if k.is_zero() {
+        return (reduce(BigNumber::zero()), reduce(BigNumber::from_small(2)), reduce(BigNumber::one()));
+    }
+
+    let mut four_q = q.clone();
+    four_q.multiply_by_int(4);
+    let d = &(p * p) - &four_q;
+
+    let (mut u, mut v, mut q_k) = (reduce(BigNumber::one()), reduce(p.clone()), reduce(q.clone()));
+    for i in (0..k.bits() - 1).rev() {
+        u = reduce(&u * &v);
+        v = reduce(&(&v * &v) - &(&q_k + &q_k));
+        q_k = reduce(&q_k * &q_k);
+
+        if k.bit(i) {
+            let next_u = halve(reduce(&(p * &u) + &v));
+            v = halve(reduce(&(&d * &u) + &(p * &v)));
+            u = next_u;
+            q_k = reduce(&q_k * q);
+        }
+    }
+
+    (u, v, q_k)
+}
@@ -0,0 +1,112 @@
+//! `Serialize` and `Deserialize` implementations, behind the `serde` feature.
+//!
+//! Human readable formats such as JSON and TOML get a decimal string, so values
+//! survive tools that would round large JSON numbers. Binary formats such as
+//! bincode get the bytes least significant first: the magnitude for
+//! [`BigUint`], and the minimal two's complement encoding for [`BigNumber`].
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{BigNumber, BigUint};
+
+impl Serialize for BigUint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.to_bytes_le())
+        }
+    }
+}
+
+impl Serialize for BigNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.to_signed_bytes_le())
+        }
+    }
+}
+
+// Accepts a decimal string, a primitive integer, or the byte encoding, turning
+// each into a `BigNumber` first
+struct NumberVisitor {
+    signed: bool,
+}
+
+impl<'de> Visitor<'de> for NumberVisitor {
+    type Value = BigNumber;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.signed { "an integer" } else { "a non-negative integer" })
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<BigNumber, E> {
+        BigNumber::parse(value).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<BigNumber, E> {
+        Ok(BigNumber::from(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<BigNumber, E> {
+        Ok(BigNumber::from(value))
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<BigNumber, E> {
+        Ok(BigNumber::from(value))
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<BigNumber, E> {
+        Ok(BigNumber::from(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<BigNumber, E> {
+        Ok(if self.signed {
+            BigNumber::from_signed_bytes_le(value)
+        } else {
+            BigNumber::from(BigUint::from_bytes_le(value))
+        })
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<BigNumber, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
+    }
+}
+
+fn deserialize_number<'de, D: Deserializer<'de>>(deserializer: D, signed: bool) -> Result<BigNumber, D::Error> {
+    let visitor = NumberVisitor { signed };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for BigUint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BigUint, D::Error> {
+        let number = deserialize_number(deserializer, false)?;
+        if number.is_negative() {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Other("a negative integer"),
+                &NumberVisitor { signed: false },
+            ));
+        }
+        Ok(number.magnitude().clone())
+    }
+}
+
+impl<'de> Deserialize<'de> for BigNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BigNumber, D::Error> {
+        deserialize_number(deserializer, true)
+    }
+}
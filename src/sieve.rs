@@ -0,0 +1,50 @@
+//! A lazily built table of small primes, shared by the primality tests and the
+//! factoring routines.
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use once_cell::race::OnceBox;
+
+// Primes below this bound are kept in the table
+pub(crate) const SIEVE_LIMIT: u32 = 1_000_000;
+
+static SMALL_PRIMES: OnceBox<Vec<u32>> = OnceBox::new();
+
+// All primes below `SIEVE_LIMIT`, in increasing order. The sieve runs on first
+// use and is shared by all threads afterwards; threads that race to build it
+// may each sieve, but only one table is kept.
+pub(crate) fn small_primes() -> &'static [u32] {
+    SMALL_PRIMES.get_or_init(|| Box::new(sieve(SIEVE_LIMIT as usize)))
+}
+
+// All primes up to and including `n`, taken from the table when it is large
+// enough and sieved afresh otherwise
+pub(crate) fn primes_up_to(n: u32) -> Cow<'static, [u32]> {
+    if n < SIEVE_LIMIT {
+        let primes = small_primes();
+        Cow::Borrowed(&primes[..primes.partition_point(|&p| p <= n)])
+    } else {
+        Cow::Owned(sieve(n as usize + 1))
+    }
+}
+
+// The primes below `limit` by the sieve of Eratosthenes
+fn sieve(limit: usize) -> Vec<u32> {
+    let mut composite = vec![false; limit];
+    let mut primes = Vec::new();
+
+    for i in 2..limit {
+        if composite[i] {
+            continue;
+        }
+        primes.push(i as u32);
+        for multiple in (i.saturating_mul(i)..limit).step_by(i) {
+            composite[multiple] = true;
+        }
+    }
+
+    primes
+}
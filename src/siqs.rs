@@ -0,0 +1,575 @@
+//! The self-initializing quadratic sieve.
+//!
+//! The sieve looks for many `x` where `Q(x) = (A x + B)^2 - n` factors over a
+//! base of small primes, combines some of them into a congruence of squares
+//! `X^2 = Y^2 (mod n)` with Gaussian elimination over GF(2), and takes
+//! `gcd(X - Y, n)`. Polynomials come in families sharing `A`, a product of `s`
+//! factor base primes, with `B = B_1 +- B_2 ... +- B_s`; the residues of every
+//! `B_j` are computed once per family, so each of its `2^(s-1)` polynomials is
+//! set up with small-integer arithmetic alone. Only full relations are kept;
+//! there is no large prime variation.
+
+use alloc::collections::BTreeSet;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::factor::Deadline;
+use crate::sieve::small_primes;
+use crate::{BigNumber, BigUint, Sign};
+
+// Relations collected beyond the factor base size, so that several
+// dependencies are available
+const EXTRA_RELATIONS: usize = 16;
+// Primes below this are not sieved; the threshold allows for their absence
+const SMALL_PRIME_CUTOFF: u32 = 30;
+// Draws in a row that repeat a used A, after which the combinations are taken
+// to be used up
+const MAX_REPEATED_DRAWS: usize = 64;
+// Slack, in bits, between a sieve total and the expected size of the value
+const THRESHOLD_SLACK: u32 = 4;
+
+// Sieve parameters by size of n in decimal digits: (digits, factor base size,
+// half width of the sieve interval)
+const PARAMETERS: [(usize, usize, usize); 7] = [
+    (24, 100, 16_384),
+    (30, 200, 32_768),
+    (40, 400, 65_536),
+    (50, 1_200, 65_536),
+    (60, 2_500, 98_304),
+    (70, 4_500, 131_072),
+    (80, 7_000, 196_608),
+];
+
+impl BigNumber {
+    /// Looks for a non-trivial factor of the number with the self-initializing
+    /// quadratic sieve.
+    ///
+    /// The running time depends only on the size of the number, not on its
+    /// factors, which makes this the method of choice for composites of 40
+    /// digits and up without small factors. Parameters are tuned up to 80
+    /// digits; in an optimized build a 50 digit semiprime takes seconds and a 60
+    /// digit one a couple of minutes. Returns `None` for `0`, `±1` and primes,
+    /// and in the rare case that the sieve runs out of polynomials first.
+    ///
+    /// ```
+    /// use bignumber::BigNumber;
+    ///
+    /// let n = BigNumber::from_string("1000000016000000063");
+    /// let factor = n.factor_siqs().unwrap();
+    /// assert!(factor.to_string() == "1000000007" || factor.to_string() == "1000000009");
+    /// ```
+    pub fn factor_siqs(&self) -> Option<BigNumber> {
+        let n = &self.magnitude;
+        if *n <= BigUint::one() || self.is_prime() {
+            return None;
+        }
+        if n.is_even() {
+            return Some(BigNumber::from_small(2));
+        }
+
+        // The sieve cannot split a perfect square
+        if n.is_perfect_square() {
+            return Some(BigNumber::from(n.isqrt()));
+        }
+
+        siqs(n, Deadline::NONE).map(BigNumber::from)
+    }
+}
+
+// A factor base prime with a square root of n modulo it
+struct BasePrime {
+    p: u32,
+    sqrt_n: u32,
+    log: u8,
+}
+
+// A full relation: `value^2 = (-1)^negative * prod p_i^e_i (mod n)`
+struct Relation {
+    value: BigUint,
+    negative: bool,
+    exponents: Vec<(usize, u32)>,
+}
+
+// Factors odd composite n that is not a perfect square, giving up at the deadline
+pub(crate) fn siqs(n: &BigUint, deadline: Deadline) -> Option<BigUint> {
+    let digits = n.to_string().len();
+    let &(_, base_size, half_width) = PARAMETERS
+        .iter()
+        .find(|&&(max_digits, _, _)| digits <= max_digits)
+        .unwrap_or(PARAMETERS.last().unwrap());
+
+    let base = match factor_base(n, base_size) {
+        Ok(base) => base,
+        Err(factor) => return Some(factor),
+    };
+
+    let target = base.len() + 1 + EXTRA_RELATIONS;
+    let mut relations = Vec::with_capacity(target);
+    let mut seen = BTreeSet::new();
+    let mut polynomials = PolynomialFamily::new(n, &base, half_width);
+
+    while relations.len() < target {
+        // Small n leave few combinations for A, which can run out
+        let family = polynomials.next_family(deadline)?;
+        for index in 0..family.count() {
+            let polynomial = family.polynomial(index);
+            for relation in sieve_polynomial(n, &base, &family, &polynomial, half_width) {
+                if seen.insert(relation.value.clone()) {
+                    relations.push(relation);
+                }
+            }
+            if relations.len() >= target {
+                break;
+            }
+        }
+    }
+
+    for dependency in find_dependencies(&relations, base.len()) {
+        let factor = square_root(n, &base, &relations, &dependency);
+        if factor != BigUint::one() && factor != *n {
+            return Some(factor);
+        }
+    }
+
+    None
+}
+
+// The primes p with (n / p) = 1, which are the only odd primes that can divide
+// Q(x), together with 2. Finding a prime that divides n ends the search early.
+fn factor_base(n: &BigUint, size: usize) -> Result<Vec<BasePrime>, BigUint> {
+    let mut base = vec![BasePrime {
+        p: 2,
+        sqrt_n: 1,
+        log: 1,
+    }];
+
+    for &p in &small_primes()[1..] {
+        if base.len() == size {
+            break;
+        }
+
+        let residue = n.rem_small(p);
+        if residue == 0 {
+            return Err(BigUint::from_limbs(vec![p]));
+        }
+        if pow_mod(residue as u64, (p as u64 - 1) / 2, p as u64) != 1 {
+            continue;
+        }
+
+        base.push(BasePrime {
+            p,
+            sqrt_n: sqrt_mod(residue as u64, p as u64) as u32,
+            log: (32 - p.leading_zeros()) as u8,
+        });
+    }
+
+    Ok(base)
+}
+
+// Chooses the leading coefficients A = q_1 ... q_s, each close to
+// sqrt(2n) / M, from primes in the middle of the factor base
+struct PolynomialFamily<'a> {
+    base: &'a [BasePrime],
+    target_bits: f64,
+    pool: core::ops::Range<usize>,
+    used: BTreeSet<Vec<usize>>,
+    state: u64,
+}
+
+// All polynomials sharing one A
+struct Family {
+    a: BigUint,
+    factors: Vec<usize>,
+    // B_j = (A / q_j) * gamma_j, with B = sum of +-B_j
+    b_terms: Vec<BigUint>,
+    // For every base prime: A^-1 mod p and B_j mod p, or None when p divides A
+    // or is not sieved
+    residues: Vec<Option<(u32, Vec<u32>)>>,
+}
+
+// One polynomial (A x + B)^2 - n of a family
+struct Polynomial {
+    b: BigNumber,
+    // Bit j - 1 set means B_j is subtracted
+    signs: usize,
+}
+
+impl<'a> PolynomialFamily<'a> {
+    fn new(n: &'a BigUint, base: &'a [BasePrime], half_width: usize) -> PolynomialFamily<'a> {
+        let target_bits = (n.bits() as f64 + 1.0) / 2.0 - log2(half_width as u64);
+
+        // Primes from the middle third of the base keep the number of factors in A
+        // moderate while leaving plenty of combinations
+        let start = (base.len() / 3).max(base.partition_point(|prime| prime.p < SMALL_PRIME_CUTOFF));
+        let end = (2 * base.len() / 3).max(start + 3).min(base.len());
+
+        PolynomialFamily {
+            base,
+            target_bits,
+            pool: start..end,
+            used: BTreeSet::new(),
+            state: 0x9e37_79b9_7f4a_7c15,
+        }
+    }
+
+    fn random(&mut self, bound: usize) -> usize {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state % bound as u64) as usize
+    }
+
+    // The next family with an unused A, or None once the deadline passes or the
+    // draws keep repeating used ones
+    fn next_family(&mut self, deadline: Deadline) -> Option<Family> {
+        for _ in 0..MAX_REPEATED_DRAWS {
+            if deadline.passed() {
+                return None;
+            }
+            let factors = self.choose_factors();
+            if self.used.insert(factors.clone()) {
+                return Some(self.family(factors));
+            }
+        }
+        None
+    }
+
+    // Picks all but one factor at random, then the last one to bring the product
+    // closest to the target size
+    fn choose_factors(&mut self) -> Vec<usize> {
+        let pool_len = self.pool.end - self.pool.start;
+        let typical_bits = log2(self.base[self.pool.start + pool_len / 2].p as u64);
+        // With one factor every draw would pick the same closest prime
+        let count = ((self.target_bits / typical_bits + 0.5) as usize).clamp(2, pool_len.min(20));
+
+        let mut factors = Vec::with_capacity(count);
+        let mut bits = 0.0;
+        while factors.len() + 1 < count {
+            let index = self.pool.start + self.random(pool_len);
+            if !factors.contains(&index) {
+                factors.push(index);
+                bits += log2(self.base[index].p as u64);
+            }
+        }
+
+        let remaining = self.target_bits - bits;
+        let last = (self.pool.start..self.pool.end)
+            .filter(|index| !factors.contains(index))
+            .min_by(|&x, &y| {
+                let distance = |i: usize| (log2(self.base[i].p as u64) - remaining).abs();
+                distance(x).total_cmp(&distance(y))
+            })
+            .unwrap();
+        factors.push(last);
+        factors.sort_unstable();
+        factors
+    }
+
+    fn family(&self, factors: Vec<usize>) -> Family {
+        let a = factors
+            .iter()
+            .fold(BigUint::one(), |a, &i| &a * &BigUint::from_limbs(vec![self.base[i].p]));
+
+        // B_j = (A / q_j) gamma_j with gamma_j = sqrt(n) (A / q_j)^-1 mod q_j, so
+        // that B^2 = n (mod A)
+        let b_terms: Vec<BigUint> = factors
+            .iter()
+            .map(|&i| {
+                let q = self.base[i].p;
+                let mut cofactor = a.clone();
+                cofactor.div_rem_small(q);
+                let inverse = inverse_mod(cofactor.rem_small(q) as u64, q as u64);
+                let mut gamma = (self.base[i].sqrt_n as u64 * inverse % q as u64) as u32;
+                if gamma > q / 2 {
+                    gamma = q - gamma;
+                }
+                cofactor.mul_small(gamma);
+                cofactor
+            })
+            .collect();
+
+        let residues = self
+            .base
+            .iter()
+            .enumerate()
+            .map(|(i, prime)| {
+                if prime.p < SMALL_PRIME_CUTOFF || factors.contains(&i) {
+                    return None;
+                }
+                let a_inverse = inverse_mod(a.rem_small(prime.p) as u64, prime.p as u64) as u32;
+                let terms = b_terms.iter().map(|term: &BigUint| term.rem_small(prime.p)).collect();
+                Some((a_inverse, terms))
+            })
+            .collect();
+
+        Family {
+            a,
+            factors,
+            b_terms,
+            residues,
+        }
+    }
+}
+
+impl Family {
+    fn count(&self) -> usize {
+        1 << (self.b_terms.len() - 1)
+    }
+
+    // The polynomial with B = B_1 +- B_2 ... +- B_s, where the signs are the bits
+    // of `index`
+    fn polynomial(&self, index: usize) -> Polynomial {
+        let mut b = BigNumber::from(self.b_terms[0].clone());
+        for (j, term) in self.b_terms.iter().enumerate().skip(1) {
+            let term = BigNumber::from(term.clone());
+            if (index >> (j - 1)) & 1 == 1 {
+                b = &b - &term;
+            } else {
+                b = &b + &term;
+            }
+        }
+        Polynomial { b, signs: index }
+    }
+}
+
+// Sieves (A x + B)^2 - n over -M <= x < M and returns the smooth values
+fn sieve_polynomial(
+    n: &BigUint,
+    base: &[BasePrime],
+    family: &Family,
+    polynomial: &Polynomial,
+    half_width: usize,
+) -> Vec<Relation> {
+    let width = 2 * half_width;
+    let mut sieve = vec![0u8; width];
+
+    for (prime, residues) in base.iter().zip(&family.residues) {
+        let Some((a_inverse, terms)) = residues else {
+            continue;
+        };
+        let p = prime.p as u64;
+        let b = terms.iter().enumerate().fold(0, |b, (j, &term)| {
+            let subtract = j > 0 && (polynomial.signs >> (j - 1)) & 1 == 1;
+            (b + if subtract { p - term as u64 } else { term as u64 }) % p
+        });
+
+        // Roots of A x + B = +-sqrt(n) (mod p), moved to sieve array offsets
+        let offset = half_width as u64 % p;
+        for root in [prime.sqrt_n as u64, p - prime.sqrt_n as u64] {
+            let x = (root + p - b) % p * *a_inverse as u64 % p;
+            let mut i = ((x + offset) % p) as usize;
+            while i < width {
+                sieve[i] = sieve[i].wrapping_add(prime.log);
+                i += prime.p as usize;
+            }
+            if prime.sqrt_n == 0 {
+                break;
+            }
+        }
+    }
+
+    // Values of Q(x) / A reach about M sqrt(n / 2); accept anything whose sieve
+    // total falls short of that by no more than a large base prime and the
+    // unsieved small primes
+    let largest = base.last().unwrap().p;
+    let expected = (n.bits() as u32).div_ceil(2) + (half_width as u32).ilog2();
+    let threshold = expected.saturating_sub((32 - largest.leading_zeros()) + THRESHOLD_SLACK);
+
+    let a = BigNumber::from(family.a.clone());
+    let n_signed = BigNumber::from(n.clone());
+    let mut relations = Vec::new();
+
+    for (i, &total) in sieve.iter().enumerate() {
+        if (total as u32) < threshold {
+            continue;
+        }
+
+        let x = BigNumber::from_biguint(
+            if i < half_width { Sign::Negative } else { Sign::Positive },
+            BigUint::from_limbs(vec![(i as i64 - half_width as i64).unsigned_abs() as u32]),
+        );
+        let value = &(&a * &x) + &polynomial.b;
+        let q = &(&value * &value) - &n_signed;
+        if q.is_zero() {
+            continue;
+        }
+
+        // Q(x) = A g(x), and the factors of A are already known
+        let g = &q / &a;
+        if let Some(mut exponents) = factor_over_base(g.magnitude(), base) {
+            for &factor in &family.factors {
+                match exponents.iter_mut().find(|(index, _)| *index == factor) {
+                    Some((_, exponent)) => *exponent += 1,
+                    None => exponents.push((factor, 1)),
+                }
+            }
+            relations.push(Relation {
+                value: value.magnitude().clone(),
+                negative: q.is_negative(),
+                exponents,
+            });
+        }
+    }
+
+    relations
+}
+
+// The exponents of `value` over the factor base, if it factors completely
+fn factor_over_base(value: &BigUint, base: &[BasePrime]) -> Option<Vec<(usize, u32)>> {
+    let mut remaining = value.clone();
+    let mut exponents = Vec::new();
+
+    for (index, prime) in base.iter().enumerate() {
+        let mut exponent = 0;
+        while remaining.rem_small(prime.p) == 0 {
+            remaining.div_rem_small(prime.p);
+            exponent += 1;
+        }
+        if exponent > 0 {
+            exponents.push((index, exponent));
+        }
+        if remaining == BigUint::one() {
+            return Some(exponents);
+        }
+    }
+
+    None
+}
+
+// Subsets of relations whose exponent vectors sum to zero mod 2, found by
+// Gaussian elimination on bit-packed rows that carry their own history
+fn find_dependencies(relations: &[Relation], base_len: usize) -> Vec<Vec<usize>> {
+    // Column 0 is the sign, column i + 1 is base prime i
+    let columns = base_len + 1;
+    let words = (columns + relations.len()).div_ceil(64);
+    let mut rows: Vec<Vec<u64>> = relations
+        .iter()
+        .enumerate()
+        .map(|(r, relation)| {
+            let mut row = vec![0u64; words];
+            let mut flip = |bit: usize| row[bit / 64] ^= 1 << (bit % 64);
+            if relation.negative {
+                flip(0);
+            }
+            for &(index, exponent) in &relation.exponents {
+                if exponent % 2 == 1 {
+                    flip(index + 1);
+                }
+            }
+            flip(columns + r);
+            row
+        })
+        .collect();
+
+    let bit = |row: &[u64], column: usize| (row[column / 64] >> (column % 64)) & 1 == 1;
+    let mut pivot_row = 0;
+    for column in 0..columns {
+        let Some(found) = (pivot_row..rows.len()).find(|&r| bit(&rows[r], column)) else {
+            continue;
+        };
+        rows.swap(pivot_row, found);
+
+        let (pivot, rest) = rows.split_at_mut(pivot_row + 1);
+        let pivot = &pivot[pivot_row];
+        for row in rest.iter_mut().filter(|row| bit(row, column)) {
+            for (word, pivot_word) in row.iter_mut().zip(pivot) {
+                *word ^= pivot_word;
+            }
+        }
+        pivot_row += 1;
+    }
+
+    // Rows past the last pivot have an empty exponent part
+    rows[pivot_row..]
+        .iter()
+        .map(|row| (0..relations.len()).filter(|&r| bit(row, columns + r)).collect())
+        .collect()
+}
+
+// Turns a dependency into X^2 = Y^2 (mod n) and returns gcd(X - Y, n)
+fn square_root(n: &BigUint, base: &[BasePrime], relations: &[Relation], dependency: &[usize]) -> BigUint {
+    let mut x = BigUint::one();
+    let mut exponents = vec![0u32; base.len()];
+
+    for &r in dependency {
+        x = &(&x * &relations[r].value) % n;
+        for &(index, exponent) in &relations[r].exponents {
+            exponents[index] += exponent;
+        }
+    }
+
+    let mut y = BigUint::one();
+    for (prime, &exponent) in base.iter().zip(&exponents) {
+        debug_assert!(exponent % 2 == 0);
+        let power = BigUint::from_limbs(vec![prime.p]).modpow(&BigUint::from_limbs(vec![exponent / 2]), n);
+        y = &(&y * &power) % n;
+    }
+
+    let difference = if x >= y { &x - &y } else { &y - &x };
+    difference.gcd(n)
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    base %= modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result
+}
+
+// a^-1 mod a prime p, by Fermat's little theorem
+fn inverse_mod(a: u64, p: u64) -> u64 {
+    pow_mod(a, p - 2, p)
+}
+
+// A square root of the residue a modulo an odd prime p below 2^32, by
+// Tonelli-Shanks
+fn sqrt_mod(a: u64, p: u64) -> u64 {
+    if a == 0 {
+        return 0;
+    }
+    if p % 4 == 3 {
+        return pow_mod(a, (p + 1) / 4, p);
+    }
+
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p).find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1).unwrap();
+
+    let mut m = s;
+    let mut c = pow_mod(z, q, p);
+    let mut t = pow_mod(a, q, p);
+    let mut r = pow_mod(a, q.div_ceil(2), p);
+    while t != 1 {
+        let mut i = 0;
+        let mut power = t;
+        while power != 1 {
+            power = power * power % p;
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        m = i;
+        c = b * b % p;
+        t = t * c % p;
+        r = r * b % p;
+    }
+    r
+}
+
+// The binary logarithm of a positive integer, to within about 1e-6, without the
+// float functions of std. With x = 2^k m and m in [1, 2), ln m is the series
+// 2 (t + t^3/3 + t^5/5 + ...) in t = (m - 1) / (m + 1) <= 1/3.
+fn log2(value: u64) -> f64 {
+    let exponent = value.ilog2();
+    let m = value as f64 / (1u64 << exponent) as f64;
+    let t = (m - 1.0) / (m + 1.0);
+    let t2 = t * t;
+    let ln_m = 2.0 * t * (1.0 + t2 * (1.0 / 3.0 + t2 * (1.0 / 5.0 + t2 * (1.0 / 7.0 + t2 / 9.0))));
+    exponent as f64 + ln_m * core::f64::consts::LOG2_E
+}
@@ -1,123 +1,2846 @@
-use std::io;
 use crate::BigNumber;
+use crate::BigUint;
 use crate::Sign;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn subtraction(a :&str, b :&str, c :&str) {
+    let number1 = BigNumber::from_string(a);
+    let number2 = BigNumber::from_string(b);
+    let number3 = BigNumber::from_string(c);
 
-    fn subtraction(a :&str, b :&str, c :&str) {
-        let mut number1 = BigNumber::from_string(&a);
-        let mut number2 = BigNumber::from_string(&b);
-        let number3 = BigNumber::from_string(&c);
+    let result = number1.subtract(&number2);
 
-        number1.subtract(&mut number2);
+    assert_eq!(result.magnitude.limbs, number3.magnitude.limbs);
+}
+
+fn add(a :&str, b :&str, c :&str) {
+    let number1 = BigNumber::from_string(a);
+    let number2 = BigNumber::from_string(b);
+    let number3 = BigNumber::from_string(c);
+
+    println!("{}", number1);
+    println!("{}", number2);
+    let result = number1.add(&number2);
+    println!("{}", result);
+    println!("{}", number3);
+
+    assert_eq!(result.magnitude.limbs, number3.magnitude.limbs);
+}
+
+#[test]
+fn test_is_prime_2() {
+    let num = BigNumber::from_string("2");
+    let is_prime = num.is_prime();
+    assert!(is_prime);
+}
+
+#[test]
+fn test_is_prime_11() {
+    let num = BigNumber::from_string("11");
+    let is_prime = num.is_prime();
+    assert!(is_prime);
+}
+
+#[test]
+fn test_from_string_positive() {
+    let input = "123";
+    let expected_limbs = vec![123];
+    let expected_sign = Sign::Positive;
+    let num = BigNumber::from_string(input);
+
+    assert_eq!(num.magnitude.limbs, expected_limbs);
+    assert_eq!(num.sign, expected_sign);
+}
+
+#[test]
+fn test_from_string_negative() {
+    let input = "-123";
+    let expected_limbs = vec![123];
+    let expected_sign = Sign::Negative;
+    let num = BigNumber::from_string(input);
+
+    assert_eq!(num.magnitude.limbs, expected_limbs);
+    assert_eq!(num.sign, expected_sign);
+}
+
+#[test]
+fn subtraction_1() {
+    subtraction("11", "13", "-2");
+}
+
+#[test]
+fn subtraction_2() {
+    subtraction("1000000000", "1000000000", "0");
+}
+
+#[test]
+fn subtraction_3() {
+    subtraction("9999999999999999", "9999999999999999", "0");
+}
+
+#[test]
+fn subtraction_4() {
+    subtraction("99999999999999999999999999999999", "99999999999999999999999999999998", "1");
+}
+
+#[test]
+fn subtraction_5() {
+    subtraction("0", "0", "0");
+}
+
+#[test]
+fn add_1() {
+    add("11", "13", "24");
+}
+
+#[test]
+fn add_2() {
+    add("1000000000", "1000000000", "2000000000");
+}
+
+#[test]
+fn add_3() {
+    add("5", "-5", "0");
+}
+
+#[test]
+fn add_4() {
+    add("99999999999999999999999999999999", "-99999999999999999999999999999998", "1");
+}
+
+#[test]
+fn add_5() {
+    add("0", "0", "0");
+}
+
+
+fn check(result: BigNumber, expected: &str) {
+    let expected = BigNumber::from_string(expected);
+    assert!(
+        result.is_equal_to(&expected),
+        "got {:?}, expected {:?}",
+        result,
+        expected
+    );
+}
+
+#[test]
+fn operator_add() {
+    let a = BigNumber::from_string("-7");
+    let b = BigNumber::from_string("12");
+    check(&a + &b, "5");
+    check(&b + &a, "5");
+    check(a.clone() + BigNumber::from_string("-3"), "-10");
+    check(BigNumber::from_string("999") + &BigNumber::from_string("1"), "1000");
+}
+
+#[test]
+fn operator_sub() {
+    let a = BigNumber::from_string("11");
+    let b = BigNumber::from_string("13");
+    check(&a - &b, "-2");
+    check(&b - &a, "2");
+    check(&a - &a, "0");
+    check(BigNumber::from_string("-5") - BigNumber::from_string("-8"), "3");
+    // The right-hand side is left untouched
+    assert!(b.is_equal_to(&BigNumber::from_string("13")));
+}
+
+#[test]
+fn operator_mul() {
+    let a = BigNumber::from_string("-123456789");
+    let b = BigNumber::from_string("987654321");
+    check(&a * &b, "-121932631112635269");
+    check(&a * &a, "15241578750190521");
+    check(a * BigNumber::from_string("0"), "0");
+}
+
+#[test]
+fn operator_div_rem() {
+    let cases = [
+        ("7", "2", "3", "1"),
+        ("-7", "2", "-3", "-1"),
+        ("7", "-2", "-3", "1"),
+        ("-7", "-2", "3", "-1"),
+        ("1", "5", "0", "1"),
+        ("121932631112635269", "123456789", "987654321", "0"),
+    ];
+
+    for (a, b, q, r) in cases {
+        let a = BigNumber::from_string(a);
+        let b = BigNumber::from_string(b);
+        check(&a / &b, q);
+        check(&a % &b, r);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Division by zero")]
+fn operator_div_by_zero() {
+    let _ = BigNumber::from_string("1") / BigNumber::from_string("0");
+}
+
+#[test]
+fn operator_neg() {
+    check(-BigNumber::from_string("5"), "-5");
+    check(-&BigNumber::from_string("-5"), "5");
+    check(-BigNumber::from_string("0"), "0");
+}
+
+#[test]
+fn compound_assignment() {
+    let mut total = BigNumber::from_string("0");
+    for i in 1..=100 {
+        total += BigNumber::from_string(&i.to_string());
+    }
+    check(total.clone(), "5050");
+
+    total -= &BigNumber::from_string("6000");
+    check(total.clone(), "-950");
+    total -= BigNumber::from_string("-950");
+    check(total.clone(), "0");
+
+    let mut product = BigNumber::from_string("1");
+    for i in 1..=25 {
+        product *= BigNumber::from_string(&i.to_string());
+    }
+    check(product.clone(), "15511210043330985984000000");
+
+    product /= &BigNumber::from_string("-1000000");
+    check(product.clone(), "-15511210043330985984");
+    product %= BigNumber::from_string("1000");
+    check(product, "-984");
+}
+
+#[test]
+fn ordering() {
+    let mut numbers: Vec<BigNumber> = ["10", "-3", "0", "-25", "7", "-0", "100", "-4"]
+        .iter()
+        .map(|s| BigNumber::from_string(s))
+        .collect();
+    numbers.sort();
+
+    let expected: Vec<BigNumber> = ["-25", "-4", "-3", "0", "0", "7", "10", "100"]
+        .iter()
+        .map(|s| BigNumber::from_string(s))
+        .collect();
+    assert_eq!(numbers, expected);
+
+    assert!(BigNumber::from_string("-12") < BigNumber::from_string("-11"));
+    assert!(BigNumber::from_string("12") > BigNumber::from_string("11"));
+    assert!(BigNumber::from_string("-1").is_less_than(&BigNumber::from_string("0")));
+    assert!(BigNumber::from_string("-1").is_less_than_or_equal_to(&BigNumber::from_string("-1")));
+    assert!(BigNumber::from_string("-1").is_greater_than_or_equal_to(&BigNumber::from_string("-2")));
+}
+
+#[test]
+fn equality_ignores_leading_zeros_and_zero_sign() {
+    assert_eq!(BigNumber::from_string("007"), BigNumber::from_string("7"));
+    assert_eq!(BigNumber::from_string("-0"), BigNumber::from_string("0"));
+    assert_ne!(BigNumber::from_string("-7"), BigNumber::from_string("7"));
+}
+
+#[test]
+fn usable_as_map_keys() {
+    use std::collections::{BTreeMap, HashSet};
+
+    let set: HashSet<BigNumber> = ["42", "042", "-0", "0", "-42"]
+        .iter()
+        .map(|s| BigNumber::from_string(s))
+        .collect();
+    assert_eq!(set.len(), 3);
+
+    let mut map = BTreeMap::new();
+    map.insert(BigNumber::from_string("2"), "two");
+    map.insert(BigNumber::from_string("-1"), "minus one");
+    map.insert(BigNumber::from_string("10"), "ten");
+    let values: Vec<&str> = map.values().copied().collect();
+    assert_eq!(values, ["minus one", "two", "ten"]);
+}
+
+#[test]
+fn display() {
+    assert_eq!(BigNumber::from_string("123").to_string(), "123");
+    assert_eq!(BigNumber::from_string("-123").to_string(), "-123");
+    assert_eq!(BigNumber::from_string("000120").to_string(), "120");
+    assert_eq!(BigNumber::from_string("-0").to_string(), "0");
+    assert_eq!(format!("[{}]", BigNumber::from_string("-42")), "[-42]");
+}
+
+#[test]
+fn from_str() {
+    let n: BigNumber = "-98765432109876543210".parse().unwrap();
+    assert_eq!(n, BigNumber::from_string("-98765432109876543210"));
+    assert_eq!("0042".parse::<BigNumber>().unwrap().to_string(), "42");
+
+    assert!("".parse::<BigNumber>().is_err());
+    assert!("-".parse::<BigNumber>().is_err());
+    assert!("12a".parse::<BigNumber>().is_err());
+    assert!("+12".parse::<BigNumber>().is_err());
+}
+
+#[test]
+fn parse_errors() {
+    use crate::ParseBigNumberError;
+
+    assert_eq!(BigNumber::parse(""), Err(ParseBigNumberError::Empty));
+    assert_eq!(BigNumber::parse("-"), Err(ParseBigNumberError::BareSign));
+    assert_eq!(
+        BigNumber::parse("12a4"),
+        Err(ParseBigNumberError::InvalidDigit { position: 2, character: 'a' })
+    );
+    assert_eq!(
+        BigNumber::parse("-1 2"),
+        Err(ParseBigNumberError::InvalidDigit { position: 2, character: ' ' })
+    );
+    assert_eq!(
+        BigNumber::parse("--1"),
+        Err(ParseBigNumberError::InvalidDigit { position: 1, character: '-' })
+    );
+    assert_eq!(BigNumber::parse("-0012").unwrap().to_string(), "-12");
+}
+
+#[test]
+#[should_panic(expected = "invalid digit 'x' at position 1")]
+fn from_string_panics_on_invalid_input() {
+    BigNumber::from_string("1x");
+}
+
+#[test]
+fn checked_division() {
+    use crate::DivisionByZero;
+
+    let a = BigNumber::from_string("100");
+    let b = BigNumber::from_string("-7");
+    let zero = BigNumber::from_string("0");
+
+    assert_eq!(a.checked_div(&b), Some(BigNumber::from_string("-14")));
+    assert_eq!(a.checked_rem(&b), Some(BigNumber::from_string("2")));
+    assert_eq!(
+        b.checked_div_rem(&a),
+        Ok((BigNumber::from_string("0"), BigNumber::from_string("-7")))
+    );
+
+    assert_eq!(a.checked_div(&zero), None);
+    assert_eq!(a.checked_rem(&zero), None);
+    assert_eq!(a.checked_div_rem(&zero), Err(DivisionByZero));
+}
+
+#[test]
+fn arithmetic_leaves_operands_untouched() {
+    let a = BigNumber::from_string("11");
+    let b = BigNumber::from_string("-13");
+
+    check(a.add(&b), "-2");
+    check(a.subtract(&b), "24");
+    check(b.subtract(&a), "-24");
+    check(a.multiply(&b), "-143");
+    check(b.divide(&a), "-1");
+    assert!(b.is_divisible_by(&BigNumber::from_string("13")));
+    assert!(!a.is_divisible_by(&b));
+
+    assert_eq!(a, BigNumber::from_string("11"));
+    assert_eq!(b, BigNumber::from_string("-13"));
+}
+
+#[test]
+fn in_place_arithmetic() {
+    let mut n = BigNumber::from_string("5");
+    n.add_in_place(&BigNumber::from_string("-8"));
+    check(n.clone(), "-3");
+    n.subtract_in_place(&BigNumber::from_string("-3"));
+    check(n.clone(), "0");
+    assert!(n.is_positive());
+    n.add_in_place(&BigNumber::from_string("12"));
+    n.multiply_in_place(&BigNumber::from_string("-12"));
+    check(n.clone(), "-144");
+    n.multiply_by_int(-7);
+    check(n.clone(), "1008");
+    n.multiply_by_int(0);
+    check(n, "0");
+}
+
+fn assert_canonical(n: &BigNumber) {
+    let limbs = &n.magnitude.limbs;
+    assert!(!limbs.is_empty(), "empty limbs");
+    assert!(limbs.len() == 1 || *limbs.last().unwrap() != 0, "leading zero in {:?}", n);
+    if *limbs == [0] {
+        assert_eq!(n.sign, Sign::Positive, "negative zero");
+    }
+}
+
+#[test]
+fn zero_is_canonical() {
+    let five = BigNumber::from_string("5");
+    let minus_five = BigNumber::from_string("-5");
+    let zero = BigNumber::zero();
+
+    let results = [
+        BigNumber::from_string("-0"),
+        BigNumber::from_string("-000"),
+        &five + &minus_five,
+        &minus_five - &minus_five,
+        &minus_five * &zero,
+        &zero * &minus_five,
+        &zero / &minus_five,
+        &minus_five % &five,
+        -&zero,
+        minus_five.subtract(&minus_five),
+    ];
+    for result in &results {
+        assert_canonical(result);
+        assert!(result.is_zero());
+        assert_eq!(result.to_string(), "0");
+    }
+
+    let mut shifted = BigNumber::zero();
+    shifted.shift_left(3);
+    assert_canonical(&shifted);
+
+    let mut shifted = BigNumber::from_string("-5");
+    shifted.shift_right(1);
+    assert_canonical(&shifted);
+    assert_eq!(shifted, zero);
+
+    let mut scaled = BigNumber::from_string("-17");
+    scaled.multiply_by_int(0);
+    assert_canonical(&scaled);
+}
+
+#[test]
+fn results_have_no_leading_zeros() {
+    let a = BigNumber::from_string("1000");
+    let b = BigNumber::from_string("999");
+    for result in [&a - &b, &b - &a, &a / &b, &a % &b, &a * &b, &a + &b] {
+        assert_canonical(&result);
+    }
+    assert_eq!(BigNumber::one().magnitude.limbs, [1]);
+}
+
+#[test]
+fn biguint_arithmetic() {
+    let a: BigUint = "123456789012345678901234567890".parse().unwrap();
+    let b = BigUint::parse("987654321").unwrap();
+
+    assert_eq!((&a + &b).to_string(), "123456789012345678902222222211");
+    assert_eq!((&a - &b).to_string(), "123456789012345678900246913569");
+    assert_eq!((&a * &b).to_string(), "121932631124828532112482853211126352690");
+    assert_eq!((&a / &b).to_string(), "124999998873437499901");
+    assert_eq!((&a % &b).to_string(), "574845669");
+    assert_eq!(b.checked_sub(&a), None);
+    assert!(b < a);
+
+    let mut n = BigUint::one();
+    n += &b;
+    n *= BigUint::parse("2").unwrap();
+    n -= BigUint::one();
+    assert_eq!(n.to_string(), "1975308643");
+}
+
+#[test]
+#[should_panic]
+fn biguint_subtraction_underflow() {
+    let _ = BigUint::one() - BigUint::parse("2").unwrap();
+}
+
+#[test]
+fn signed_wrapper_over_biguint() {
+    use crate::BigInt;
+
+    let magnitude = BigUint::parse("42").unwrap();
+    let n = BigInt::from_biguint(Sign::Negative, magnitude.clone());
+    assert_eq!(n.to_string(), "-42");
+    assert_eq!(n.magnitude(), &magnitude);
+    assert_eq!(BigNumber::from(magnitude), BigNumber::from_string("42"));
+    assert_eq!(BigNumber::from_biguint(Sign::Negative, BigUint::zero()).sign(), Sign::Positive);
+}
+
+// A small xorshift generator so that randomized tests are reproducible
+struct TestRng(u64);
+
+impl TestRng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn decimal(&mut self, len: usize) -> String {
+        let mut s: String = (0..len).map(|_| char::from(b'0' + (self.next() % 10) as u8)).collect();
+        if s.starts_with('0') {
+            s.replace_range(0..1, "1");
+        }
+        s
+    }
+}
+
+#[test]
+fn limb_boundaries() {
+    let max_limb = BigUint::parse("4294967295").unwrap();
+    assert_eq!(max_limb.limbs, [u32::MAX]);
+
+    let two_limbs = &max_limb + &BigUint::one();
+    assert_eq!(two_limbs.limbs, [0, 1]);
+    assert_eq!(two_limbs.to_string(), "4294967296");
+    assert_eq!((&two_limbs - &BigUint::one()).limbs, [u32::MAX]);
+    assert_eq!((&max_limb * &max_limb).to_string(), "18446744065119617025");
+    assert_eq!(BigUint::parse("1000000000000000000").unwrap().to_string(), "1000000000000000000");
+    assert_eq!(BigUint::parse("1000000000").unwrap().to_string(), "1000000000");
+
+    let mut n = BigNumber::from_string("-123");
+    n.shift_left(12);
+    assert_eq!(n.to_string(), "-123000000000000");
+    n.shift_right(13);
+    assert_eq!(n.to_string(), "-12");
+}
+
+#[test]
+fn arithmetic_identities() {
+    let mut rng = TestRng(0x2545_f491_4f6c_dd1d);
+    for round in 0..40 {
+        let a = BigNumber::from_string(&rng.decimal(1 + round * 3));
+        let b = BigNumber::from_string(&rng.decimal(1 + (round * 7) % 50));
+        let b = if round % 3 == 0 { -b } else { b };
+
+        assert_eq!(BigNumber::from_string(&a.to_string()), a);
+        assert_eq!(&(&a + &b) - &b, a);
+        assert_eq!(&(&a * &b) / &b, a);
+        let (q, r) = a.checked_div_rem(&b).unwrap();
+        assert_eq!(&(&q * &b) + &r, a);
+        assert!(r.magnitude() < b.magnitude());
+    }
+}
+
+#[test]
+fn multiplication_tiers_agree() {
+    use crate::mul::{karatsuba_mul, mul_limbs, schoolbook_mul, toom3_mul};
+
+    let mut rng = TestRng(0x9e37_79b9_7f4a_7c15);
+    let mut limbs = |len: usize| -> Vec<u32> { (0..len).map(|_| rng.next() as u32).collect() };
+
+    for (a_len, b_len) in [(1, 1), (5, 3), (40, 33), (64, 64), (100, 70), (130, 129), (200, 140), (300, 40), (400, 390)] {
+        let a = limbs(a_len);
+        let b = limbs(b_len);
+        let expected = schoolbook_mul(&a, &b);
+
+        assert_eq!(karatsuba_mul(&a, &b), expected, "karatsuba {}x{}", a_len, b_len);
+        assert_eq!(toom3_mul(&a, &b), expected, "toom3 {}x{}", a_len, b_len);
+        assert_eq!(mul_limbs(&a, &b), expected, "ladder {}x{}", a_len, b_len);
+    }
+
+    let all_ones = vec![u32::MAX; 300];
+    assert_eq!(toom3_mul(&all_ones, &all_ones), schoolbook_mul(&all_ones, &all_ones));
+    assert_eq!(karatsuba_mul(&all_ones[..100], &all_ones[..90]), schoolbook_mul(&all_ones[..100], &all_ones[..90]));
+}
+
+#[test]
+fn ntt_multiplication_is_exact() {
+    use crate::mul::{schoolbook_mul, toom3_mul};
+    use crate::ntt::ntt_mul;
+
+    let mut rng = TestRng(0xdead_beef_cafe_f00d);
+    let mut limbs = |len: usize| -> Vec<u32> { (0..len).map(|_| rng.next() as u32).collect() };
+
+    for (a_len, b_len) in [(1, 1), (2, 7), (33, 64), (257, 300)] {
+        let a = limbs(a_len);
+        let b = limbs(b_len);
+        assert_eq!(ntt_mul(&a, &b), schoolbook_mul(&a, &b), "ntt {}x{}", a_len, b_len);
+    }
+
+    let all_ones = vec![u32::MAX; 3000];
+    assert_eq!(ntt_mul(&all_ones, &all_ones), toom3_mul(&all_ones, &all_ones));
+
+    let a = limbs(2500);
+    let b = limbs(1800);
+    assert_eq!(ntt_mul(&a, &b), toom3_mul(&a, &b));
+}
+
+#[test]
+fn long_division_reconstructs_the_dividend() {
+    let mut rng = TestRng(0x0123_4567_89ab_cdef);
+
+    for (u_len, v_len) in [(2, 2), (5, 2), (8, 3), (40, 17), (100, 99)] {
+        for _ in 0..20 {
+            // Runs of all-ones and zero limbs make the quotient estimate overshoot
+            let mut limbs = |len: usize| {
+                let limbs: Vec<u32> = (0..len)
+                    .map(|_| match rng.next() % 4 {
+                        0 => 0,
+                        1 => u32::MAX,
+                        _ => rng.next() as u32,
+                    })
+                    .collect();
+                BigUint::from_limbs(limbs)
+            };
+            let u = limbs(u_len);
+            let v = limbs(v_len);
+            if v.is_zero() {
+                continue;
+            }
+
+            let (q, r) = u.div_rem(&v);
+            assert!(r < v);
+            assert_eq!(&(&q * &v) + &r, u);
+        }
+    }
+
+    // Hacker's Delight's case, scaled to 32-bit limbs, that needs the add-back step
+    let u = BigUint::from_limbs(vec![0, 0xffff_fffe, 0, 0x8000_0000]);
+    let v = BigUint::from_limbs(vec![0xffff_ffff, 0, 0x8000_0000]);
+    let (q, r) = u.div_rem(&v);
+    assert_eq!(q.limbs, [0xffff_ffff]);
+    assert_eq!(r.limbs, [0xffff_ffff, 0xffff_ffff, 0x7fff_ffff]);
+}
+
+#[test]
+fn rounding_division_variants() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    // (a, b, floor, mod_floor, ceil, euclid, rem_euclid)
+    let cases = [
+        ("7", "2", "3", "1", "4", "3", "1"),
+        ("-7", "2", "-4", "1", "-3", "-4", "1"),
+        ("7", "-2", "-4", "-1", "-3", "-3", "1"),
+        ("-7", "-2", "3", "-1", "4", "4", "1"),
+        ("6", "-3", "-2", "0", "-2", "-2", "0"),
+        ("0", "-5", "0", "0", "0", "0", "0"),
+    ];
+
+    for (a, b, floor, modulo, ceil, euclid, rem) in cases {
+        let (a, b) = (n(a), n(b));
+        check(a.div_floor(&b), floor);
+        check(a.mod_floor(&b), modulo);
+        check(a.div_ceil(&b), ceil);
+        check(a.div_euclid(&b), euclid);
+        check(a.rem_euclid(&b), rem);
+        assert_eq!(&(&a.div_euclid(&b) * &b) + &a.rem_euclid(&b), a);
+    }
+}
+
+#[test]
+fn remainder_follows_the_dividend_sign() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    let cases = [
+        ("17", "5", "2"),
+        ("-17", "5", "-2"),
+        ("17", "-5", "2"),
+        ("-17", "-5", "-2"),
+        ("15", "-5", "0"),
+    ];
+
+    for (a, b, expected) in cases {
+        let (a, b) = (n(a), n(b));
+        check(a.rem(&b), expected);
+        check(&a % &b, expected);
+        assert_eq!(&(&a.divide(&b) * &b) + &a.rem(&b), a);
+    }
+
+    let big = n("123456789012345678901234567890");
+    check(big.rem(&n("-1000000007")), "197434842");
+}
+
+#[test]
+fn pow_by_squaring() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    check(n("7").pow(77), "118181386580595879976868414312001964434038548836769923458287039207");
+    check(n("-3").pow(3), "-27");
+    check(n("-3").pow(4), "81");
+    check(n("0").pow(0), "1");
+    check(n("0").pow(5), "0");
+
+    let big = n("2").pow(10000).to_string();
+    assert_eq!(big.len(), 3011);
+    assert!(big.starts_with("19950631168807583848"));
+    assert!(big.ends_with("81774304792596709376"));
+}
+
+#[test]
+fn modpow_reduces_every_step() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    check(n("4").modpow(&n("13"), &n("497")), "445");
+    check(n("-2").modpow(&n("3"), &n("5")), "2");
+    check(n("-2").modpow(&n("3"), &n("-5")), "2");
+    check(n("12345").modpow(&n("0"), &n("1")), "0");
+    check(n("0").modpow(&n("0"), &n("7")), "1");
+
+    let base = n("265613988875874769338781322035779626829233452653394495974574961739092490901302182994384699044018");
+    let exponent = n("1267650600228229401496703205379");
+    let modulus = n("10000000000000000000000000000000000000123");
+    check(base.modpow(&exponent, &modulus), "1485322788545298164573602715341172288441");
+}
+
+#[test]
+#[should_panic(expected = "Division by zero")]
+fn modpow_by_zero_modulus_panics() {
+    let two = BigNumber::from_string("2");
+    let _ = two.modpow(&two, &BigNumber::zero());
+}
+
+#[test]
+fn montgomery_matches_plain_reduction() {
+    use crate::MontgomeryContext;
+
+    let u = |s: &str| s.parse::<BigUint>().unwrap();
+    let m = u("1328221009966658550846953834599263485347303735486886792856524326951478097480333706218863685");
+    let a = u("2256344149419036036726650059573849694778280017803638728964869726990710973905200104236868703083650169354490841016823245726443429509716273860594236657784669839725545367803734100114848");
+    let b = u("1582520166216234977970160768713629102036870617656673823410748692452379283183899680374407");
+    let e = u("117991604031595324762292047548332516115853613064910523190429");
+
+    let ctx = MontgomeryContext::new(&m).unwrap();
+    let (a_mont, b_mont) = (ctx.to_montgomery(&a), ctx.to_montgomery(&b));
+    assert_eq!(ctx.from_montgomery(&a_mont), &a % &m);
+    assert_eq!(
+        ctx.from_montgomery(&ctx.mul(&a_mont, &b_mont)).to_string(),
+        "109643176697262263658591422543718516946030443200405770106806762508828487907276924151649751"
+    );
+    assert_eq!(ctx.square(&b_mont), ctx.mul(&b_mont, &b_mont));
+    assert_eq!(
+        ctx.from_montgomery(&ctx.pow(&a_mont, &e)).to_string(),
+        "1037646201681323167575879379158984218462869496463925562079313527210994003825258275167834518"
+    );
+
+    assert!(MontgomeryContext::new(&u("1000")).is_none());
+    assert!(MontgomeryContext::new(&BigUint::zero()).is_none());
+    assert_eq!(a.modpow(&e, &u("3802951800684688204490109616128")).to_string(), "1267650600228229401496703205376");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "Montgomery input not below the modulus")]
+fn montgomery_rejects_unreduced_inputs() {
+    use crate::MontgomeryContext;
+
+    let m = BigUint::from(1_000_000_007u32);
+    let ctx = MontgomeryContext::new(&m).unwrap();
+    let a = ctx.to_montgomery(&BigUint::from(5u32));
+    ctx.mul(&a, &(&m + &BigUint::one()));
+}
+
+#[test]
+fn barrett_matches_division() {
+    use crate::BarrettContext;
+
+    let mut rng = TestRng(0x5eed_1234_abcd_0001);
+    let mut number = |len: usize| {
+        let limbs = (0..len).map(|_| rng.next() as u32).collect();
+        BigUint::from_limbs(limbs)
+    };
+
+    for m_len in [1, 2, 5, 12] {
+        let mut m = number(m_len);
+        m.limbs[0] &= !1;
+        if m.is_zero() {
+            continue;
+        }
+        let ctx = BarrettContext::new(&m).unwrap();
+
+        for x_len in [1, m_len, 2 * m_len - 1, 2 * m_len, 3 * m_len] {
+            let x = number(x_len);
+            assert_eq!(ctx.reduce(&x), &x % &m);
+        }
+
+        let (a, b) = (number(m_len), number(m_len));
+        assert_eq!(ctx.mul(&a, &b), &(&a * &b) % &m);
+    }
+
+    assert!(BarrettContext::new(&BigUint::zero()).is_none());
+}
+
+#[test]
+fn gcd_handles_signs_and_zero() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    check(n("84").gcd(&n("36")), "12");
+    check(n("-84").gcd(&n("-36")), "12");
+    check(n("0").gcd(&n("-5")), "5");
+    check(n("-5").gcd(&n("0")), "5");
+    check(n("0").gcd(&n("0")), "0");
+    check(n("17").gcd(&n("31")), "1");
+
+    let a = n("8075323118233071899646933143225079285642938160326710796886844848216004009113962938368");
+    let b = n("2320557918175134378127253645676467220305884678966445267278700666420520710570470539264");
+    check(a.gcd(&b), "1024618246531448192529486101931556275808450117982966277666337116389376");
+}
+
+#[test]
+fn binary_gcd_agrees_with_euclid() {
+    let mut rng = TestRng(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..30 {
+        let common = BigNumber::from_string(&rng.decimal(20));
+        let a = &BigNumber::from_string(&rng.decimal(60)) * &common;
+        let b = &BigNumber::from_string(&rng.decimal(45)) * &common;
+
+        let g = a.gcd(&b);
+        assert!(a.is_divisible_by(&g) && b.is_divisible_by(&g));
+        check((&a / &g).gcd(&(&b / &g)), "1");
+    }
+
+    let mut power = BigUint::one();
+    power.shl_bits(300);
+    let mut odd = power.clone();
+    odd.add_small(1);
+    assert_eq!(power.gcd(&odd), BigUint::one());
+    assert_eq!(power.gcd(&(&power * &BigUint::from_limbs(vec![6]))), power);
+}
+
+#[test]
+fn lehmer_gcd_on_large_operands() {
+    use crate::gcd::{binary_gcd, lehmer_gcd};
+
+    let mut rng = TestRng(0x2545_f491_4f6c_dd1d);
+
+    for (len, common_len) in [(200, 1), (600, 150), (3000, 40)] {
+        let common = BigUint::parse(&rng.decimal(common_len)).unwrap();
+        let a = &BigUint::parse(&rng.decimal(len)).unwrap() * &common;
+        let b = &BigUint::parse(&rng.decimal(len - 7)).unwrap() * &common;
+        assert_eq!(lehmer_gcd(a.clone(), b.clone()), binary_gcd(a, b));
+    }
+
+    // Consecutive Fibonacci numbers are the worst case for Euclid
+    let (mut f0, mut f1) = (BigUint::zero(), BigUint::one());
+    for _ in 0..5000 {
+        let next = &f0 + &f1;
+        f0 = std::mem::replace(&mut f1, next);
+    }
+    assert_eq!(f0.gcd(&f1), BigUint::one());
+
+    let shared = BigUint::parse(&rng.decimal(20_000)).unwrap();
+    let a = &shared * &BigUint::from_limbs(vec![6]);
+    let b = &shared * &BigUint::from_limbs(vec![35]);
+    assert_eq!(a.gcd(&b), shared);
+}
+
+#[test]
+fn lcm_of_pairs_and_slices() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    check(n("4").lcm(&n("6")), "12");
+    check(n("-4").lcm(&n("6")), "12");
+    check(n("0").lcm(&n("6")), "0");
+    check(n("0").lcm(&n("0")), "0");
+    check(
+        n("123456789012345678901234567890").lcm(&n("987654321")),
+        "13548070124980948012498094801236261410",
+    );
+
+    let values: Vec<BigNumber> = (1..=30).map(|i| n(&i.to_string())).collect();
+    check(BigNumber::lcm_all(&values), "2329089562800");
+    check(BigNumber::lcm_all(&[]), "1");
+    check(BigNumber::lcm_all(&[n("5"), n("0")]), "0");
+}
+
+#[test]
+fn extended_gcd_gives_bezout_coefficients() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    let cases = [
+        ("240", "46", "2"),
+        ("-240", "46", "2"),
+        ("240", "-46", "2"),
+        ("-240", "-46", "2"),
+        ("0", "7", "7"),
+        ("-7", "0", "7"),
+        ("0", "0", "0"),
+        ("123456789012345678901234567891", "98765432109876543210987", "1"),
+    ];
+
+    for (a, b, expected) in cases {
+        let (a, b) = (n(a), n(b));
+        let (g, x, y) = a.extended_gcd(&b);
+        check(g.clone(), expected);
+        assert_eq!(&(&a * &x) + &(&b * &y), g);
+    }
+}
+
+#[test]
+fn mod_sqrt_tonelli_shanks() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    // p = 39 * 2^70 + 1 forces the full Tonelli-Shanks loop
+    let p = n("46043073207979040833537");
+    check(n("10189934533217359459019").mod_sqrt(&p).unwrap(), "123456789123456789");
+    assert_eq!(n("5").mod_sqrt(&p), None);
+
+    // The Mersenne prime 2^127 - 1 is 3 mod 4
+    let m = n("170141183460469231731687303715884105727");
+    check(n("975461059740893157555403139789971041").mod_sqrt(&m).unwrap(), "987654321987654321");
+
+    for x in 0..41 {
+        let square = n(&(x * x).to_string());
+        let root = square.mod_sqrt(&n("41")).unwrap();
+        assert_eq!(&(&root * &root) % &n("41"), &square % &n("41"));
+    }
+    check(n("-1").mod_sqrt(&n("13")).unwrap(), "5");
+    check(n("3").mod_sqrt(&n("2")).unwrap(), "1");
+    assert_eq!(n("3").mod_sqrt(&n("1")), None);
+}
+
+#[test]
+fn jacobi_legendre_and_kronecker_symbols() {
+    let n = |i: i32| BigNumber::from_string(&i.to_string());
+
+    assert_eq!(n(1001).jacobi(&n(9907)), -1);
+    assert_eq!(n(19).jacobi(&n(45)), 1);
+    assert_eq!(n(8).jacobi(&n(21)), -1);
+    assert_eq!(n(5).jacobi(&n(21)), 1);
+    assert_eq!(n(21).jacobi(&n(21)), 0);
+
+    // Quadratic residues modulo 11 are 1, 3, 4, 5 and 9
+    let residues: Vec<i32> = (0..11).map(|a| n(a).legendre(&n(11))).collect();
+    assert_eq!(residues, [0, 1, -1, 1, 1, 1, -1, -1, -1, 1, -1]);
+
+    // Rows are a = -6..=6, columns n = -6..=6
+    let table = [
+        "0-000-0+000+0",
+        "+0--+-0+-++0-",
+        "0-0+0-0+0-0+0",
+        "0+-0+-0+-0+-0",
+        "0+0-0-0+0+0-0",
+        "+--+--+++-++-",
+        "00000+0+00000",
+        "+++++++++++++",
+        "0-0-0+0+0-0-0",
+        "0-+0-+0+-0+-0",
+        "0+0+0+0+0+0+0",
+        "+0+--+0+--+0+",
+        "0+000+0+000+0",
+    ];
+    for (row, a) in table.iter().zip(-6..=6) {
+        let symbols: String = (-6..=6)
+            .map(|m| match n(a).kronecker(&n(m)) {
+                1 => '+',
+                -1 => '-',
+                _ => '0',
+            })
+            .collect();
+        assert_eq!(&symbols, row, "kronecker({}, n)", a);
+    }
+}
+
+#[test]
+#[should_panic(expected = "positive odd modulus")]
+fn jacobi_rejects_even_modulus() {
+    BigNumber::from_string("3").jacobi(&BigNumber::from_string("10"));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn miller_rabin_with_random_bases() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let n = |s: &str| BigNumber::from_string(s);
+
+    for prime in ["2", "3", "5", "97", "2147483647", "170141183460469231731687303715884105727"] {
+        assert!(n(prime).is_probable_prime(10, &mut rng), "{}", prime);
+    }
+
+    // 561 and 41041 are Carmichael numbers, which fool the Fermat test
+    for composite in ["0", "1", "4", "561", "41041", "-7", "170141183460469231731687303715884105729"] {
+        assert!(!n(composite).is_probable_prime(10, &mut rng), "{}", composite);
+    }
+
+    let p = n("2305843009213693951");
+    let q = n("618970019642690137449562111");
+    assert!(!(&p * &q).is_probable_prime(10, &mut rng));
+}
+
+#[test]
+fn deterministic_is_prime_below_the_bound() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    let mut sieve = vec![true; 2000];
+    sieve[0] = false;
+    sieve[1] = false;
+    for i in 2..2000 {
+        if sieve[i] {
+            for multiple in (i * i..2000).step_by(i) {
+                sieve[multiple] = false;
+            }
+        }
+    }
+    for (i, &expected) in sieve.iter().enumerate() {
+        assert_eq!(n(&i.to_string()).is_prime(), expected, "{}", i);
+    }
+
+    // A strong pseudoprime to every prime base up to 37, caught by base 41
+    assert!(!n("318665857834031151167461").is_prime());
+    assert!(n("18446744073709551557").is_prime());
+    assert!(!n("-7").is_prime());
+}
+
+#[test]
+fn baillie_psw_above_the_deterministic_bound() {
+    use crate::prime::is_strong_lucas_probable_prime;
+
+    let u = |s: &str| s.parse::<BigUint>().unwrap();
+    let n = |s: &str| BigNumber::from_string(s);
+
+    // The smallest strong Lucas pseudoprimes pass the Lucas half on its own
+    for pseudoprime in ["5459", "5777", "10877", "16109", "18971"] {
+        assert!(is_strong_lucas_probable_prime(&u(pseudoprime)), "{}", pseudoprime);
+    }
+    for composite in ["15", "21", "25", "561", "1001", "5461"] {
+        assert!(!is_strong_lucas_probable_prime(&u(composite)), "{}", composite);
+    }
+    for prime in ["5", "7", "101", "7919", "2147483647"] {
+        assert!(is_strong_lucas_probable_prime(&u(prime)), "{}", prime);
+    }
+
+    let m89 = n("618970019642690137449562111");
+    let m127 = n("170141183460469231731687303715884105727");
+    assert!(m89.is_prime());
+    assert!(m127.is_prime());
+    assert!(!(&m89 * &m127).is_prime());
+    assert!(!(&m127 * &m127).is_prime());
+    assert!(!(&m127 + &n("2")).is_prime());
+}
+
+#[test]
+fn lucas_lehmer_mersenne_exponents() {
+    let exponents: Vec<u64> = (2..700).filter(|&p| BigNumber::is_mersenne_prime(p)).collect();
+    assert_eq!(exponents, [2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521, 607]);
+}
+
+#[test]
+fn next_and_previous_primes() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    let mut primes = vec![];
+    let mut p = n("-5");
+    for _ in 0..30 {
+        p = p.next_prime();
+        primes.push(p.to_string());
+    }
+    assert_eq!(primes.first().unwrap(), "2");
+    assert_eq!(primes.last().unwrap(), "113");
+
+    let mut back = vec![];
+    let mut q = n("114");
+    while let Some(prev) = q.prev_prime() {
+        back.push(prev.to_string());
+        q = prev;
+    }
+    back.reverse();
+    assert_eq!(back, primes);
+
+    // Mersenne primes on either side of large gaps
+    let m89 = n("618970019642690137449562111");
+    check(n("618970019642690137449562110").next_prime(), "618970019642690137449562111");
+    check(m89.next_prime(), "618970019642690137449562141");
+    check(n("618970019642690137449562141").prev_prime().unwrap(), "618970019642690137449562111");
+    assert_eq!(n("-3").prev_prime(), None);
+}
+
+#[test]
+fn small_prime_table() {
+    use crate::sieve::small_primes;
+
+    let primes = small_primes();
+    assert_eq!(primes.len(), 78498);
+    assert_eq!(&primes[..10], [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    assert_eq!(*primes.last().unwrap(), 999983);
+    assert!(std::ptr::eq(primes, small_primes()));
+
+    // Just above the table, where is_prime falls through to trial division
+    assert!(BigNumber::from_string("1000003").is_prime());
+    assert!(!BigNumber::from_string("1000001").is_prime());
+}
+
+#[test]
+fn pollard_rho_finds_factors() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    let composites = [
+        "10403",
+        "18446744073709551617",
+        "600851475143",
+        "618970023975480274948393073146934777",
+        "-1000000016000000063",
+    ];
+    for composite in composites {
+        let composite = n(composite);
+        let factor = composite.factor_rho().unwrap();
+        assert!(factor > BigNumber::one() && factor.magnitude() < composite.magnitude());
+        assert!(composite.is_divisible_by(&factor), "{} / {}", composite, factor);
+    }
+
+    assert_eq!(n("1000000007").factor_rho(), None);
+    assert_eq!(n("1").factor_rho(), None);
+    assert_eq!(n("0").factor_rho(), None);
+    check(n("1024").factor_rho().unwrap(), "2");
+}
+
+#[test]
+fn pollard_p_minus_one_stages() {
+    let n = |s: &str| BigNumber::from_string(s);
+    // q - 1 has the prime factor 2111423087, far beyond any bound used here
+    let q = n("1000000000000000000000000103");
+
+    // p - 1 = 2 * 59 * 367 * 449 * 479 * 661 * 919 * 953 * 971 falls to stage one
+    let p = n("5235510409262682067943");
+    check((&p * &q).factor_pm1(1000, 1000).unwrap(), "5235510409262682067943");
+    assert_eq!((&p * &q).factor_pm1(900, 900), None);
+
+    // p - 1 = 2 * 41 * 97 * 103 * 521 * 547 * 769 * 839 * 40009 needs stage two
+    let p = n("6026898232294076012087");
+    assert_eq!((&p * &q).factor_pm1(1000, 1000), None);
+    check((&p * &q).factor_pm1(1000, 50_000).unwrap(), "6026898232294076012087");
+
+    assert_eq!(q.factor_pm1(1000, 50_000), None);
+    check(n("-98").factor_pm1(10, 10).unwrap(), "2");
+}
+
+#[test]
+fn quadratic_sieve_splits_semiprimes() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    for (p, q) in [("1000000007", "1000000009"), ("100000000012397", "3000000000001091")] {
+        let (p, q) = (n(p), n(q));
+        let factor = (&p * &q).factor_siqs().unwrap();
+        assert!(factor == p || factor == q, "{}", factor);
+    }
+
+    check(n("1000018000081").factor_siqs().unwrap(), "1000009");
+    assert_eq!(n("618970019642690137449562111").factor_siqs(), None);
+}
+
+#[test]
+fn factorize_returns_prime_powers() {
+    let n = |s: &str| BigNumber::from_string(s);
+    let factors = |s: &str| -> Vec<(String, u32)> {
+        n(s).factorize().iter().map(|(p, e)| (p.to_string(), *e)).collect()
+    };
+    let pairs = |expected: &[(&str, u32)]| -> Vec<(String, u32)> {
+        expected.iter().map(|&(p, e)| (p.to_string(), e)).collect()
+    };
+
+    assert_eq!(
+        factors("-21600129948482098320556882279921840865977803679089144581784648"),
+        pairs(&[
+            ("2", 3),
+            ("3", 2),
+            ("1000003", 2),
+            ("1000000007", 1),
+            ("1000000009", 1),
+            ("100000000012397", 1),
+            ("3000000000001091", 1),
+        ])
+    );
+    assert_eq!(factors("1000000007"), pairs(&[("1000000007", 1)]));
+    assert!(factors("0").is_empty() && factors("1").is_empty() && factors("-1").is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn factorize_respects_time_limit() {
+    use crate::FactorOptions;
+    use std::time::Duration;
+
+    let n = |s: &str| BigNumber::from_string(s);
+    let options = FactorOptions {
+        time_limit: Some(Duration::ZERO),
+        ..FactorOptions::default()
+    };
+    assert_eq!(n("340282366920938463463374607431768211457").factorize_with(&options), None);
+    assert!(n("1024").factorize_with(&options).is_some());
+}
+
+#[test]
+fn euler_phi_from_factorization() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    check(n("1").euler_phi(), "1");
+    check(n("1000000007").euler_phi(), "1000000006");
+    check(n("600851475143").euler_phi(), "591194251200");
+
+    let factors = [
+        (n("2"), 3),
+        (n("3"), 2),
+        (n("1000003"), 2),
+        (n("1000000007"), 1),
+        (n("1000000009"), 1),
+        (n("100000000012397"), 1),
+        (n("3000000000001091"), 1),
+    ];
+    check(
+        BigNumber::euler_phi_from_factors(&factors),
+        "7200036101738836834384077753061088262409104790573379792455680",
+    );
+    check(BigNumber::euler_phi_from_factors(&[]), "1");
+}
+
+#[test]
+#[should_panic(expected = "Euler's totient needs a positive argument")]
+fn euler_phi_of_zero_panics() {
+    let _ = BigNumber::zero().euler_phi();
+}
+
+#[test]
+fn divisor_functions() {
+    let n = |s: &str| BigNumber::from_string(s);
+    let big = n("21600129948482098320556882279921840865977803679089144581784648");
+
+    check(big.num_divisors(), "576");
+    check(big.sum_divisors(), "58500410561042050743622112844747904568412265735067692831324800");
+    check(n("360").sum_divisors(), "1170");
+    check(n("1000000007").num_divisors(), "2");
+    check(n("1000000007").sum_divisors(), "1000000008");
+    check(n("1").num_divisors(), "1");
+    check(n("1").sum_divisors(), "1");
+}
+
+#[test]
+fn carmichael_lambda_divides_totient() {
+    let n = |s: &str| BigNumber::from_string(s);
+    let big = n("288001730018604096018144");
+
+    check(big.carmichael_lambda(), "2000010012012060000072");
+    assert!(big.euler_phi().is_divisible_by(&big.carmichael_lambda()));
+    check(n("3072").carmichael_lambda(), "256");
+    check(n("4").carmichael_lambda(), "2");
+    check(n("1").carmichael_lambda(), "1");
+}
+
+#[test]
+fn moebius_function() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    assert_eq!(n("1").moebius(), 1);
+    assert_eq!(n("1000000007").moebius(), -1);
+    assert_eq!(n("1000000016000000063").moebius(), 1);
+    assert_eq!(n("288001730018604096018144").moebius(), 0);
+    assert_eq!(n("2000000014").moebius(), 1);
+}
+
+#[test]
+fn primorial_products() {
+    check(BigNumber::primorial(0), "1");
+    check(BigNumber::primorial(1), "1");
+    check(BigNumber::primorial(2), "2");
+    check(BigNumber::primorial(100), "2305567963945518424753102147331756070");
+    check(BigNumber::primorial(97), "2305567963945518424753102147331756070");
+
+    // Past the end of the cached table
+    let modulus = BigNumber::from_string("1000000000000000003");
+    check(&BigNumber::primorial(1_000_100) % &modulus, "235407309224947893");
+}
+
+#[test]
+fn factorial_values() {
+    check(BigNumber::factorial(0), "1");
+    check(BigNumber::factorial(1), "1");
+    check(BigNumber::factorial(13), "6227020800");
+    check(
+        BigNumber::factorial(100),
+        "93326215443944152681699238856266700490715968264381621468592963895217599993229915608941463976156518286253697920827223758251185210916864000000000000000000000000",
+    );
+
+    let modulus = BigNumber::from_string("1000000000000000003");
+    check(&BigNumber::factorial(65536) % &modulus, "788416612731190350");
+}
+
+#[test]
+fn factorial_paths_agree() {
+    let modulus = BigNumber::from_string("1000000000000000003");
+
+    // Scalar multiplication below the threshold, prime factorization above
+    let below = BigNumber::factorial(2999);
+    check(&below % &modulus, "759897613735743072");
+    let mut above = below;
+    above.multiply_by_int(3000);
+    assert_eq!(BigNumber::factorial(3000), above);
+
+    check(&BigNumber::factorial(100_000) % &modulus, "841154720087012234");
+}
+
+#[test]
+fn double_rising_and_falling_factorials() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    check(BigNumber::double_factorial(0), "1");
+    check(BigNumber::double_factorial(1), "1");
+    check(
+        BigNumber::double_factorial(101),
+        "275264606114823679801052037785492781962370429385126144787167211167753726318359375",
+    );
+
+    let a = n("-123456789012345678901234567890");
+    check(
+        a.falling_factorial(7),
+        "-437124189926872542867019522318127092899961001308647124772454886987064594629695391747209645723890488470820298485359214919704005011104945338395194095281066906182179928404553773874883195863114075989306691200",
+    );
+    check(
+        a.rising_factorial(7),
+        "-437124189926872542867019522169417442148452105327868333788223858116162043932642360010549543935597389039432798944853435405271049501817466954386117929549702636368061172714799904467848242003514745413946182400",
+    );
+    check(a.falling_factorial(0), "1");
+    check(n("50").falling_factorial(60), "0");
+    assert_eq!(n("1").rising_factorial(30), BigNumber::factorial(30));
+}
 
-        assert_eq!(number1.digits, number3.digits);
+#[test]
+fn binomial_coefficients() {
+    check(BigNumber::binomial(0, 0), "1");
+    check(BigNumber::binomial(10, 11), "0");
+    check(BigNumber::binomial(10, 10), "1");
+    check(
+        BigNumber::binomial(1000, 500),
+        "270288240945436569515614693625975275496152008446548287007392875106625428705522193898612483924502370165362606085021546104802209750050679917549894219699518475423665484263751733356162464079737887344364574161119497604571044985756287880514600994219426752366915856603136862602484428109296905863799821216320",
+    );
+    check(BigNumber::binomial(u64::MAX, 3), "1046183622564446793632349203613672605920836997447371718655");
+    assert_eq!(BigNumber::binomial(u64::MAX, u64::MAX - 3), BigNumber::binomial(u64::MAX, 3));
+
+    let modulus = BigNumber::from_string("1000000000000000003");
+    check(&BigNumber::binomial(100_000, 20_000) % &modulus, "345530213239790285");
+}
+
+#[test]
+fn multinomial_coefficients() {
+    check(BigNumber::multinomial(&[]), "1");
+    check(BigNumber::multinomial(&[0, 7, 0]), "1");
+    check(
+        BigNumber::multinomial(&[100, 200, 300]),
+        "561812797503528108022984154079257534052996731014652713946693054369744723960763677665568262037666873387723276089814458729537081414663956828422022354533538575959599909246383409683752681979910264098753913152412447850160155228058413061273197901319560650755916294720",
+    );
+    assert_eq!(BigNumber::multinomial(&[30, 70]), BigNumber::binomial(100, 30));
+}
+
+#[test]
+fn fibonacci_fast_doubling() {
+    check(BigNumber::fibonacci(0), "0");
+    check(BigNumber::fibonacci(1), "1");
+    check(BigNumber::fibonacci(2), "1");
+    check(BigNumber::fibonacci(93), "12200160415121876738");
+
+    let (f1000, f1001) = BigNumber::fibonacci_pair(1000);
+    check(f1000, "43466557686937456435688527675040625802564660517371780402481729089536555417949051890403879840079255169295922593080322634775209689623239873322471161642996440906533187938298969649928516003704476137795166849228875");
+    check(f1001, "70330367711422815821835254877183549770181269836358732742604905087154537118196933579742249494562611733487750449241765991088186363265450223647106012053374121273867339111198139373125598767690091902245245323403501");
+
+    let modulus = BigNumber::from_string("1000000000000000003");
+    check(&BigNumber::fibonacci(100_000) % &modulus, "850294064977332739");
+}
+
+#[test]
+fn lucas_sequences() {
+    let n = |s: &str| BigNumber::from_string(s);
+    let lucas = |p: &str, q: &str, k: u64| {
+        let (u, v) = BigNumber::lucas_sequence(&n(p), &n(q), k);
+        (u.to_string(), v.to_string())
+    };
+    let pair = |u: &str, v: &str| (u.to_string(), v.to_string());
+
+    assert_eq!(
+        lucas("1", "-1", 200),
+        pair("280571172992510140037611932413038677189525", "627376215338105766356982006981782561278127")
+    );
+    assert_eq!(
+        lucas("-7", "11", 57),
+        pair("33463481539108948822998849417611721262", "-74826619485256902970908152286980430352")
+    );
+    assert_eq!(lucas("4", "4", 31), pair("33285996544", "4294967296"));
+    assert_eq!(lucas("5", "3", 0), pair("0", "2"));
+    assert_eq!(lucas("5", "3", 1), pair("1", "5"));
+}
+
+#[test]
+fn catalan_numbers() {
+    check(BigNumber::catalan(0), "1");
+    check(BigNumber::catalan(1), "1");
+    check(BigNumber::catalan(35), "3116285494907301262");
+    check(
+        BigNumber::catalan(250),
+        "465116795969233796497747947259667807407291160080922096111953326525143875193659257831340309862635877995262413955019878805418475969029457769094808256",
+    );
+}
+
+#[test]
+fn pell_fundamental_solutions() {
+    let solve = |d: u64| {
+        let (x, y) = BigNumber::pell(d).unwrap();
+        (x.to_string(), y.to_string())
+    };
+    let pair = |x: &str, y: &str| (x.to_string(), y.to_string());
+
+    assert_eq!(solve(2), pair("3", "2"));
+    assert_eq!(solve(13), pair("649", "180"));
+    assert_eq!(solve(109), pair("158070671986249", "15140424455100"));
+    assert_eq!(solve(991), pair("379516400906811930638014896080", "12055735790331359447442538767"));
+    assert!(BigNumber::pell(0).is_none() && BigNumber::pell(1).is_none() && BigNumber::pell(144).is_none());
+
+    let d = 1_000_000_007u64;
+    let (x, y) = BigNumber::pell(d).unwrap();
+    let d = BigNumber::from(BigUint::from(d));
+    check(&(&x * &x) - &(&d * &(&y * &y)), "1");
+}
+
+#[test]
+fn square_roots_round_down() {
+    let n = |s: &str| BigNumber::from_string(s);
+
+    check(n("0").isqrt(), "0");
+    check(n("1").isqrt(), "1");
+    check(n("3").isqrt(), "1");
+    check(n("4").sqrt(), "2");
+    check(n("99").sqrt(), "9");
+
+    // (2^256 - 1)^2 - 1
+    let (root, remainder) = n("13407807929942597099574024998205846127479365820592393377723561443721764030073315392623399665776056285720014482370779510884422601683867654778417822746804224").sqrt_rem();
+    check(root, "115792089237316195423570985008687907853269984665640564039457584007913129639934");
+    check(remainder, "231584178474632390847141970017375815706539969331281128078915168015826259279868");
+
+    let mut big = n("1");
+    big.shift_left(200);
+    big += &n("12345");
+    let (root, remainder) = big.sqrt_rem();
+    let mut expected = n("1");
+    expected.shift_left(100);
+    assert_eq!(root, expected);
+    check(remainder, "12345");
+}
+
+#[test]
+#[should_panic(expected = "Square root of a negative number")]
+fn square_root_of_negative_panics() {
+    let _ = BigNumber::from_string("-4").isqrt();
+}
+
+#[test]
+fn nth_roots_round_down() {
+    let n = |s: &str| BigNumber::from_string(s);
+    let power = |exponent: u64| BigNumber::from_small(3).pow(exponent);
+
+    let big = &power(1000) + &n("7");
+    check(
+        big.nth_root(5),
+        "265613988875874769338781322035779626829233452653394495974574961739092490901302182994384699044001",
+    );
+    check(big.nth_root(7), "144603646791632930926159927261170946283087621411912736405297903792851");
+    check(power(1000).nth_root(1000), "3");
+    check((&power(1000) - &n("1")).nth_root(1000), "2");
+    check(n("18446744073709551616").nth_root(64), "2");
+    check(n("18446744073709551615").nth_root(64), "1");
+    check(n("0").nth_root(3), "0");
+    check(n("-8").nth_root(3), "-2");
+    check(n("-1000000000000000000000000000000000000000000000000000000000001").nth_root(3), "-100000000000000000001");
+    assert_eq!(big.nth_root(2), big.isqrt());
+}
+
+#[test]
+fn perfect_squares() {
+    for i in 0..20_000u32 {
+        let n = BigNumber::from_small(i);
+        let root = n.isqrt();
+        assert_eq!(n.is_perfect_square(), &root * &root == n, "{}", i);
+    }
+
+    let root = BigNumber::from_string("12345678901234567890123456789");
+    let square = &root * &root;
+    assert!(square.is_perfect_square());
+    assert!(!(&square + &BigNumber::one()).is_perfect_square());
+    assert!(!(&square - &BigNumber::one()).is_perfect_square());
+    assert!(!BigNumber::from_string("-4").is_perfect_square());
+}
+
+#[test]
+fn perfect_powers() {
+    let n = |s: &str| BigNumber::from_string(s);
+    let power = |s: &str| n(s).is_perfect_power().map(|(base, k)| (base.to_string(), k));
+    let pair = |base: &str, k: u32| Some((base.to_string(), k));
+
+    assert_eq!(power("4"), pair("2", 2));
+    assert_eq!(power("64"), pair("2", 6));
+    assert_eq!(power("-64"), pair("-4", 3));
+    assert_eq!(power("-4"), None);
+    assert_eq!(power("1"), None);
+    assert_eq!(power("0"), None);
+    assert_eq!(power("72"), None);
+
+    let base = n("12345678901");
+    assert_eq!(base.pow(30).is_perfect_power(), Some((base.clone(), 30)));
+    assert_eq!((&base.pow(30) + &n("1")).is_perfect_power(), None);
+    assert_eq!(n("-2").pow(61).is_perfect_power(), Some((n("-2"), 61)));
+    assert_eq!(n("-36").pow(5).is_perfect_power(), Some((n("-36"), 5)));
+}
+
+#[test]
+fn bit_lengths() {
+    let two = BigNumber::from_small(2);
+    for k in [1u64, 31, 32, 33, 63, 64, 65, 1000] {
+        let power = two.pow(k);
+        assert_eq!(power.bits(), k + 1);
+        assert_eq!((&power - &BigNumber::one()).bits(), k);
+        assert_eq!((-&power).bits(), k + 1);
+    }
+    assert_eq!(BigNumber::one().bits(), 1);
+    assert_eq!(BigNumber::from_string("1000000000000").bits(), 40);
+}
+
+#[test]
+fn bitwise_twos_complement() {
+    let n = |s: &str| BigNumber::from_string(s);
+    let a = n("123456789012345678901234567890");
+    let b = n("-98765432109876543210");
+
+    let cases = [
+        (&a, &b, ["123456788933793542183975452690", "-20213295392617428010", "-123456788954006837576592880700"]),
+        (&-&a, &b, ["-123456789032558974293851995898", "-78552136717259115202", "123456788954006837576592880696"]),
+        (&-&a, &-&b, ["20213295392617428010", "-123456788933793542183975452690", "-123456788954006837576592880700"]),
+        (&a, &-&b, ["78552136717259115202", "123456789032558974293851995898", "123456788954006837576592880696"]),
+    ];
+    for (x, y, [and, or, xor]) in cases {
+        check(x & y, and);
+        check(x | y, or);
+        check(x ^ y, xor);
+    }
+
+    let mut c = a.clone();
+    c ^= &a;
+    check(c, "0");
+    check(&a & &n("-1"), "123456789012345678901234567890");
+    check(n("5").not_bits(8), "250");
+    check(n("-1").not_bits(64), "-18446744073709551616");
+
+    let ones = |bits: u64| &BigNumber::from_small(2).pow(bits) - &BigNumber::one();
+    let (x, y) = (ones(64).magnitude().clone(), ones(200).magnitude().clone());
+    assert_eq!(&x ^ &y, ones(200).magnitude() - ones(64).magnitude());
+    assert_eq!(&x & &y, x);
+    assert_eq!(&x | &y, y);
+    assert_eq!(y.not_bits(64), &y - &x);
+}
+
+#[test]
+fn binary_shifts() {
+    let n = |s: &str| BigNumber::from_string(s);
+    let a = n("-123456789012345678901234567890");
+
+    check(&a >> 1, "-61728394506172839450617283945");
+    check(&a >> 70, "-104571968");
+    check(&a >> 97, "-1");
+    check(&a << 100, "-156500072693749876333549759454926973536814597484617284976640");
+    check(&-&a >> 97, "0");
+    check(n("-18446744073709551616") >> 64, "-1");
+    check(n("-18446744073709551616") >> 65, "-1");
+    check(n("-18446744073709551616") >> 63, "-2");
+
+    let mut b = -&a;
+    b <<= 33;
+    b >>= 33;
+    assert_eq!(b, -&a);
+
+    let u = a.magnitude().clone();
+    assert_eq!(&(&u << 45) >> 45, u);
+    assert_eq!((u.clone() << 3) >> 4, &u >> 1);
+}
+
+#[test]
+fn bit_accessors() {
+    let mut n = BigNumber::zero();
+    n.set_bit(0);
+    n.set_bit(64);
+    check(n.clone(), "18446744073709551617");
+    assert!(n.bit(64) && n.bit(0) && !n.bit(63) && !n.bit(1000));
+
+    n.toggle_bit(64);
+    n.toggle_bit(1);
+    check(n.clone(), "3");
+    n.clear_bit(5000);
+    n.clear_bit(0);
+    n.clear_bit(1);
+    assert_canonical(&n);
+    check(n.clone(), "0");
+
+    let mut negative = BigNumber::from_string("-1");
+    negative.toggle_bit(0);
+    assert_canonical(&negative);
+    check(negative, "0");
+}
+
+#[test]
+fn popcount_and_trailing_zeros() {
+    let two = BigNumber::from_small(2);
+    let ones = &two.pow(200) - &BigNumber::one();
+    assert_eq!(ones.count_ones(), 200);
+    assert_eq!(ones.trailing_zeros(), 0);
+
+    let shifted = -&(&ones << 77);
+    assert_eq!(shifted.count_ones(), 200);
+    assert_eq!(shifted.trailing_zeros(), 77);
+    assert_eq!(two.pow(64).trailing_zeros(), 64);
+    assert_eq!(BigNumber::zero().count_ones(), 0);
+    assert_eq!(BigNumber::zero().trailing_zeros(), 0);
+}
+
+#[test]
+fn signed_byte_round_trips() {
+    let hex = |s: &str| -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    };
+    let cases = [
+        ("0", "00"),
+        ("127", "7f"),
+        ("128", "0080"),
+        ("-128", "80"),
+        ("-129", "ff7f"),
+        ("255", "00ff"),
+        ("-256", "ff00"),
+        ("-18446744073709551616", "ff0000000000000000"),
+        ("18446744073709551615", "00ffffffffffffffff"),
+        ("123456789012345678901234567890", "018ee90ff6c373e0ee4e3f0ad2"),
+        ("-123456789012345678901234567890", "fe7116f0093c8c1f11b1c0f52e"),
+    ];
+    for (decimal, encoded) in cases {
+        let n = BigNumber::from_string(decimal);
+        let big_endian = hex(encoded);
+        let little_endian: Vec<u8> = big_endian.iter().rev().copied().collect();
+        assert_eq!(n.to_signed_bytes_be(), big_endian, "{}", decimal);
+        assert_eq!(n.to_signed_bytes_le(), little_endian, "{}", decimal);
+
+        let decoded = BigNumber::from_signed_bytes_be(&big_endian);
+        assert_canonical(&decoded);
+        assert_eq!(decoded, n);
+        assert_eq!(BigNumber::from_signed_bytes_le(&little_endian), n);
+    }
+
+    // Redundant sign bytes are accepted
+    check(BigNumber::from_signed_bytes_be(&[0xff, 0xff, 0x7f]), "-129");
+    check(BigNumber::from_signed_bytes_be(&[0, 0, 0, 0, 0, 1]), "1");
+    check(BigNumber::from_signed_bytes_be(&[]), "0");
+}
+
+#[test]
+fn unsigned_byte_round_trips() {
+    let n = BigNumber::from_string("123456789012345678901234567890");
+    let big_endian = [0x01, 0x8e, 0xe9, 0x0f, 0xf6, 0xc3, 0x73, 0xe0, 0xee, 0x4e, 0x3f, 0x0a, 0xd2];
+    let little_endian: Vec<u8> = big_endian.iter().rev().copied().collect();
+
+    assert_eq!(n.magnitude().to_bytes_be(), big_endian);
+    assert_eq!(n.magnitude().to_bytes_le(), little_endian);
+    assert_eq!(BigUint::from_bytes_be(&big_endian), *n.magnitude());
+    assert_eq!(BigUint::from_bytes_le(&little_endian), *n.magnitude());
+
+    let negative = -&n;
+    assert_eq!(negative.to_bytes_be(), (Sign::Negative, big_endian.to_vec()));
+    assert_eq!(BigNumber::from_bytes_le(Sign::Negative, &little_endian), negative);
+
+    assert_eq!(BigUint::zero().to_bytes_be(), [0]);
+    assert_eq!(BigUint::from_bytes_be(&[0, 0, 0, 0, 0, 7]), BigUint::from_limbs(vec![7]));
+    assert_canonical(&BigNumber::from_bytes_be(Sign::Negative, &[]));
+}
+
+#[test]
+fn from_primitive_integers() {
+    check(BigNumber::from(0u8), "0");
+    check(BigNumber::from(u16::MAX), "65535");
+    check(BigNumber::from(u64::MAX), "18446744073709551615");
+    check(BigNumber::from(u128::MAX), "340282366920938463463374607431768211455");
+    check(BigNumber::from(i8::MIN), "-128");
+    check(BigNumber::from(-1i32), "-1");
+    check(BigNumber::from(i64::MIN), "-9223372036854775808");
+    check(BigNumber::from(i128::MIN), "-170141183460469231731687303715884105728");
+    check(BigNumber::from(usize::MAX), &usize::MAX.to_string());
+    assert_canonical(&BigNumber::from(0i64));
+    assert_eq!(BigUint::from(1u128 << 64), BigUint::from_limbs(vec![0, 0, 1]));
+}
+
+#[test]
+fn narrowing_conversions() {
+    use crate::TryFromBigNumberError;
+    let n = |s: &str| BigNumber::from_string(s);
+
+    assert_eq!(u8::try_from(&n("255")), Ok(255));
+    assert_eq!(u8::try_from(&n("256")), Err(TryFromBigNumberError));
+    assert_eq!(u32::try_from(n("-1")), Err(TryFromBigNumberError));
+    assert_eq!(i8::try_from(&n("-128")), Ok(-128));
+    assert_eq!(i8::try_from(&n("128")), Err(TryFromBigNumberError));
+    assert_eq!(i64::try_from(&n("-9223372036854775809")), Err(TryFromBigNumberError));
+    assert_eq!(u64::try_from(n("18446744073709551615").magnitude()), Ok(u64::MAX));
+
+    assert_eq!(n("-170141183460469231731687303715884105728").to_i128(), Some(i128::MIN));
+    assert_eq!(n("-170141183460469231731687303715884105729").to_i128(), None);
+    assert_eq!(n("170141183460469231731687303715884105728").to_i128(), None);
+    assert_eq!(n("340282366920938463463374607431768211455").to_u128(), Some(u128::MAX));
+    assert_eq!(n("340282366920938463463374607431768211456").to_u128(), None);
+    assert_eq!(n("-42").to_i64(), Some(-42));
+    assert_eq!(n("0").to_u64(), Some(0));
+}
+
+#[test]
+fn nearest_f64() {
+    let two = |k: usize| BigNumber::one() << k;
+
+    // Ties round to even, anything past the tie rounds up
+    assert_eq!((&two(64) + &two(11)).to_f64(), 1.8446744073709552e19);
+    assert_eq!((&(&two(64) + &two(11)) + &BigNumber::one()).to_f64(), 1.8446744073709556e19);
+    assert_eq!((&two(64) + &(&two(11) * &BigNumber::from(3u8))).to_f64(), 1.844674407370956e19);
+    assert_eq!((&two(200) - &BigNumber::one()).to_f64(), 1.6069380442589903e60);
+    assert_eq!(BigNumber::from(10u8).pow(300).to_f64(), 1e300);
+    assert_eq!(BigNumber::from_string("-123456789012345678901234567890").to_f64(), -1.2345678901234568e29);
+
+    // The largest finite double, and the first value that rounds past it
+    assert_eq!((&(&two(1024) - &two(970)) - &BigNumber::one()).to_f64(), f64::MAX);
+    assert_eq!((&two(1024) - &two(970)).to_f64(), f64::INFINITY);
+    assert_eq!((-&two(5000)).to_f64(), f64::NEG_INFINITY);
+    assert_eq!(BigNumber::zero().to_f64(), 0.0);
+}
+
+#[test]
+fn exact_f64_conversion() {
+    use crate::TryFromF64Error;
+    let exact = |value: f64| BigNumber::try_from(value).map(|n| n.to_string());
+
+    assert_eq!(exact(0.0), Ok("0".to_string()));
+    assert_eq!(exact(-0.0), Ok("0".to_string()));
+    assert_eq!(exact(-3458764513820540928.0), Ok("-3458764513820540928".to_string()));
+    assert_eq!(exact(0.5), Err(TryFromF64Error));
+    assert_eq!(exact(f64::MIN_POSITIVE), Err(TryFromF64Error));
+    assert_eq!(exact(4503599627370495.5), Err(TryFromF64Error));
+    assert_eq!(exact(f64::INFINITY), Err(TryFromF64Error));
+    assert_eq!(exact(f64::NAN), Err(TryFromF64Error));
+
+    let max = BigNumber::try_from(f64::MAX).unwrap();
+    assert_eq!(max.to_f64(), f64::MAX);
+    check(max, "179769313486231570814527423731704356798070567525844996598917476803157260780028538760589558632766878171540458953514382464234321326889464182768467546703537516986049910576551282076245490090389328944075868508455133942304583236903222948165808559332123348274797826204144723168738177180919299881250404026184124858368");
+
+    assert_canonical(&BigNumber::from_f64_truncated(-0.9).unwrap());
+    check(BigNumber::from_f64_truncated(-4503599627370495.5).unwrap(), "-4503599627370495");
+    check(BigNumber::from_f64_truncated(f64::MIN_POSITIVE).unwrap(), "0");
+}
+
+#[test]
+fn parse_in_other_radices() {
+    use crate::ParseBigNumberError;
+
+    check(
+        BigNumber::from_str_radix(&"zyxwvutsrqponmlkjihgfedcba9876543210".repeat(3), 36).unwrap(),
+        "1203137911837910018317843452729236641374825156564834895207021546984773391555352770770005066762264110682123614298945914204294608231074260524110491934233487284242919982340",
+    );
+    check(BigNumber::from_str_radix(&"1".repeat(100), 2).unwrap(), "1267650600228229401496703205375");
+    check(BigNumber::from_str_radix("-7654321076543210765432107654321", 8).unwrap(), "-9701408329431701550484379857");
+    check(BigNumber::from_str_radix("-0", 16).unwrap(), "0");
+    assert_eq!(BigUint::from_str_radix("FfFfFfFfFfFfFfFf", 16).unwrap(), BigUint::from(u64::MAX));
+    assert_eq!(
+        BigNumber::from_str_radix("129", 8),
+        Err(ParseBigNumberError::InvalidDigit { position: 2, character: '9' })
+    );
+    assert_eq!(BigNumber::from_str_radix("-", 2), Err(ParseBigNumberError::BareSign));
+    assert_eq!(BigUint::from_str_radix("", 2), Err(ParseBigNumberError::Empty));
+}
+
+#[test]
+#[should_panic(expected = "radix must be between 2 and 36")]
+fn parse_radix_out_of_range_panics() {
+    let _ = BigNumber::from_str_radix("1", 37);
+}
+
+#[test]
+fn to_str_radix_matches_reference_digits() {
+    let n = BigNumber::from_string(
+        "265613988875874769338781322035779626829233452653394495974574961739092490901302182994384699044001",
+    );
+    assert_eq!(
+        n.to_str_radix(7),
+        "56350001620311326050546044036024450534256404502461521121532421240263353466223524333212363561663331000456034203152"
+    );
+    assert_eq!((-&n).to_str_radix(36), "-3375wqjpxmrpeavhcpvslqcw6be4sb45nfy61n60v7llcxnro9dixzvozb0529");
+    assert_eq!(n.to_str_radix(10), n.to_string());
+    assert_eq!(BigNumber::from(1u128 << 64).to_str_radix(3), "11112220022122120101211020120210210211221");
+    assert_eq!(BigNumber::from_string("1000000000000000000000000000000").to_str_radix(16), "c9f2c9cd04674edea40000000");
+    assert_eq!(BigNumber::zero().to_str_radix(2), "0");
+
+    for radix in 2..=36 {
+        let digits = n.to_str_radix(radix);
+        assert_eq!(BigNumber::from_str_radix(&digits, radix).unwrap(), n, "radix {}", radix);
+    }
+}
+
+#[test]
+fn radix_formatting_traits() {
+    let n = BigNumber::from_string("515377520732011331036461129765621272702107522001");
+    assert_eq!(format!("{:x}", n), "5a4653ca673768565b41f775d6947d55cf3813d1");
+    assert_eq!(format!("{:#X}", n), "0x5A4653CA673768565B41F775D6947D55CF3813D1");
+    assert_eq!(format!("{:o}", n), "55106247451471566412626640767353532243725271716011721");
+    assert_eq!(
+        format!("{:b}", n),
+        "101101001000110010100111100101001100111001101110110100001010110010110110100000111110111011101011101011010010100011111010101010111001111001110000001001111010001"
+    );
+    assert_eq!(format!("{:#x}", -&n), "-0x5a4653ca673768565b41f775d6947d55cf3813d1");
+    assert_eq!(format!("{:#b}", BigNumber::zero()), "0b0");
+    assert_eq!(format!("{:#o}", BigUint::from(8u32)), "0o10");
+    assert_eq!(format!("{:#010x}", BigNumber::from(-255)), "-0x00000ff");
+}
+
+#[test]
+fn parse_literal_prefixes_and_separators() {
+    use crate::ParseBigNumberError;
+
+    check(
+        BigNumber::parse_literal("0x1234_5678_9abc_def0_1234_5678_9ABC_DEF_0000").unwrap(),
+        "99114423104580546006193429178388264714240",
+    );
+    check(BigNumber::parse_literal(&format!("-0b{}", "1".repeat(70))).unwrap(), "-1180591620717411303423");
+    check(BigNumber::parse_literal("1_000_000_").unwrap(), "1000000");
+    check(BigNumber::parse_literal("0x_ff").unwrap(), "255");
+    check(BigNumber::parse_literal("-0o0").unwrap(), "0");
+    assert_eq!(BigUint::parse_literal("007").unwrap(), BigUint::from(7u32));
+
+    assert_eq!(BigNumber::parse_literal("0x"), Err(ParseBigNumberError::BarePrefix));
+    assert_eq!(BigNumber::parse_literal("-0b__"), Err(ParseBigNumberError::BarePrefix));
+    assert_eq!(BigNumber::parse_literal("-"), Err(ParseBigNumberError::BareSign));
+    assert_eq!(
+        BigNumber::parse_literal("-_1"),
+        Err(ParseBigNumberError::InvalidDigit { position: 1, character: '_' })
+    );
+    assert_eq!(
+        BigNumber::parse_literal("0b102"),
+        Err(ParseBigNumberError::InvalidDigit { position: 4, character: '2' })
+    );
+    assert_eq!(
+        BigUint::parse_literal("0X1"),
+        Err(ParseBigNumberError::InvalidDigit { position: 1, character: 'X' })
+    );
+}
+
+#[test]
+fn lenient_and_strict_parsing() {
+    use crate::ParseBigNumberError;
+
+    check(BigNumber::parse_lenient("\t+000123456789012345678901234567890\n").unwrap(), "123456789012345678901234567890");
+    check(BigNumber::parse_lenient(" -0 ").unwrap(), "0");
+    assert_eq!(BigNumber::parse_lenient("   "), Err(ParseBigNumberError::Empty));
+    assert_eq!(BigNumber::parse_lenient(" + "), Err(ParseBigNumberError::BareSign));
+    assert_eq!(
+        BigNumber::parse_lenient("  12 3"),
+        Err(ParseBigNumberError::InvalidDigit { position: 4, character: ' ' })
+    );
+    assert_eq!(
+        BigUint::parse_lenient(" -1"),
+        Err(ParseBigNumberError::InvalidDigit { position: 1, character: '-' })
+    );
+
+    check(BigNumber::parse_strict("0").unwrap(), "0");
+    check(BigNumber::parse_strict("-100").unwrap(), "-100");
+    assert_eq!(BigNumber::parse_strict("-007"), Err(ParseBigNumberError::LeadingZero));
+    assert_eq!(BigNumber::parse_strict("00"), Err(ParseBigNumberError::LeadingZero));
+    assert_eq!(BigNumber::parse_strict("-0"), Err(ParseBigNumberError::NegativeZero));
+    assert_eq!(
+        BigNumber::parse_strict(" 1"),
+        Err(ParseBigNumberError::InvalidDigit { position: 0, character: ' ' })
+    );
+    assert_eq!(BigUint::parse_strict(""), Err(ParseBigNumberError::Empty));
+}
+
+#[test]
+fn parse_scientific_notation() {
+    use crate::ParseBigNumberError;
+
+    let mut googol = "1".to_string();
+    googol.push_str(&"0".repeat(100));
+    check(BigNumber::parse_scientific("1e100").unwrap(), &googol);
+    check(BigNumber::parse_scientific("-6.02214076E+23").unwrap(), "-602214076000000000000000");
+    check(BigNumber::parse_scientific("12345000e-3").unwrap(), "12345");
+    check(BigNumber::parse_scientific("1.50e1").unwrap(), "15");
+    check(BigNumber::parse_scientific(".5e1").unwrap(), "5");
+    check(BigNumber::parse_scientific("7.").unwrap(), "7");
+    check(BigNumber::parse_scientific("-0.000e-99999999999").unwrap(), "0");
+    check(BigNumber::parse_scientific("42").unwrap(), "42");
+
+    assert_eq!(BigNumber::parse_scientific("1.05e1"), Err(ParseBigNumberError::NotAnInteger));
+    assert_eq!(BigNumber::parse_scientific("100e-3"), Err(ParseBigNumberError::NotAnInteger));
+    assert_eq!(BigNumber::parse_scientific("1e-"), Err(ParseBigNumberError::MissingExponent));
+    assert_eq!(
+        BigNumber::parse_scientific("1e99999999999999999999"),
+        Err(ParseBigNumberError::ExponentTooLarge)
+    );
+    assert_eq!(
+        BigNumber::parse_scientific("-e5"),
+        Err(ParseBigNumberError::InvalidDigit { position: 1, character: 'e' })
+    );
+    assert_eq!(
+        BigNumber::parse_scientific("1.2.3e4"),
+        Err(ParseBigNumberError::InvalidDigit { position: 3, character: '.' })
+    );
+    assert_eq!(
+        BigNumber::parse_scientific("2e1x"),
+        Err(ParseBigNumberError::InvalidDigit { position: 3, character: 'x' })
+    );
+}
+
+#[test]
+fn scientific_notation_formatting() {
+    let n = BigNumber::from(3).pow(5000);
+    assert_eq!(format!("{:.10e}", n), "4.0389976298e2385");
+    assert_eq!(format!("{:.0E}", -&n), "-4E2385");
+
+    let n = BigNumber::from_string("-1606938044258990275541962092341162602522202993782792835301376");
+    assert_eq!(format!("{:e}", n), "-1.606938044258990275541962092341162602522202993782792835301376e60");
+    assert_eq!(format!("{:+.3e}", BigNumber::from_string("99999999999999999999999")), "+1.000e23");
+
+    // Ties round to even, both on the exact path and past the dropped digits
+    let tie = BigNumber::from_string("25").pow(20);
+    assert_eq!(tie.to_string(), "9094947017729282379150390625");
+    assert_eq!(format!("{:.26e}", tie), "9.09494701772928237915039062e27");
+    assert_eq!(format!("{:.0e}", BigNumber::from_string("250000000000000000000000000000000000000000")), "2e41");
+    assert_eq!(format!("{:.0e}", BigNumber::from_string("250000000000000000000000000000000000000001")), "3e41");
+    assert_eq!(format!("{:.1e}", BigUint::from(1250u32)), "1.2e3");
+
+    assert_eq!(format!("{:e}", BigNumber::from(1200)), "1.2e3");
+    assert_eq!(format!("{:.5e}", BigNumber::from(12)), "1.20000e1");
+    assert_eq!(format!("{:.2e}", BigNumber::zero()), "0.00e0");
+    assert_eq!(format!("{:08.1e}", BigNumber::from(-12345)), "-001.2e4");
+}
+
+#[test]
+fn grouped_formatting() {
+    let n = BigNumber::from_string("-1000000000000000000000");
+    assert_eq!(n.to_grouped_string(',', 3), "-1,000,000,000,000,000,000,000");
+    assert_eq!(n.to_grouped_string('\u{2009}', 5), "-10\u{2009}00000\u{2009}00000\u{2009}00000\u{2009}00000");
+    assert_eq!(n.to_grouped_string('_', 22), "-1000000000000000000000");
+    assert_eq!(BigNumber::from(123456).to_grouped_string('.', 3), "123.456");
+    assert_eq!(BigNumber::zero().to_grouped_string(',', 1), "0");
+    assert_eq!(BigUint::from(12u32).to_grouped_string(',', 1), "1,2");
+}
+
+#[test]
+#[should_panic(expected = "digit groups need at least one digit")]
+fn grouped_formatting_rejects_empty_groups() {
+    BigNumber::one().to_grouped_string(',', 0);
+}
+
+#[test]
+fn display_honours_format_flags() {
+    let n = BigNumber::from_string("-12345678901234567890");
+    assert_eq!(format!("{:>25}", n), "    -12345678901234567890");
+    assert_eq!(format!("{:<22}|", n), "-12345678901234567890 |");
+    assert_eq!(format!("{:*^24}", n), "*-12345678901234567890**");
+    assert_eq!(format!("{:025}", n), "-000012345678901234567890");
+    assert_eq!(format!("{:+}", -&n), "+12345678901234567890");
+    assert_eq!(format!("{:+}", BigNumber::zero()), "+0");
+    assert_eq!(format!("{:5}", n), "-12345678901234567890");
+    assert_eq!(format!("{:06}", BigUint::from(42u32)), "000042");
+    assert_eq!(format!("{:>6}", BigNumber::from(-42)), format!("{:>6}", -42));
+}
+
+#[test]
+fn display_radix_adapter() {
+    let n = BigNumber::from(7).pow(120);
+    assert_eq!(
+        format!("<{}>", (-&n).display_radix(8)),
+        "<-16577563435011006046656716451316450035220526425111371152624237561325642330347216021433612622263512015220204702501>"
+    );
+    assert_eq!(
+        n.display_radix(32).to_string(),
+        "3lvrjhq141gjdest55jkk0t91aq594np6lih7tomlq4r1pq70hhnhb4ipqa0qi1171a1"
+    );
+    assert_eq!(
+        n.magnitude().display_radix(4).to_string(),
+        "1311333232130131001020012010312232321310221121310220003222100222310111021023321031112110103331301122232202123003213101300101203132022302102303221100031102002010320111001"
+    );
+    assert_eq!(n.display_radix(10).to_string(), n.to_string());
+    assert_eq!(format!("{:+}", BigNumber::zero().display_radix(2)), "+0");
+    assert_eq!(format!("{:_<8}", BigNumber::from(-255).display_radix(16)), "-ff_____");
+    assert_eq!(format!("{:05}", BigUint::from(35u32).display_radix(36)), "0000z");
+}
+
+#[test]
+fn large_numbers_convert_by_splitting() {
+    // Reference digits from 2000 digit pieces, each short enough to be
+    // converted one chunk at a time
+    let reference = |n: &BigUint| {
+        let piece = BigUint::from(10u32).pow(2000);
+        let mut pieces = Vec::new();
+        let mut rest = n.clone();
+        while rest >= piece {
+            let (quotient, remainder) = rest.div_rem(&piece);
+            pieces.push(format!("{:02000}", remainder));
+            rest = quotient;
+        }
+        pieces.push(rest.to_string());
+        pieces.reverse();
+        pieces.concat()
+    };
+
+    let power = BigUint::from(3u32).pow(20000);
+    let digits = power.to_string();
+    assert_eq!(digits.len(), 9543);
+    assert!(digits.starts_with("266130342721741979197820171224"));
+    assert!(digits.ends_with("538413244308807535253104400001"));
+    assert_eq!(digits, reference(&power));
+
+    let ten = BigUint::from(10u32);
+    for n in [
+        ten.pow(8000),
+        &ten.pow(8000) + &BigUint::one(),
+        &BigUint::from(7u32).pow(9000) - &BigUint::one(),
+    ] {
+        assert_eq!(n.to_string(), reference(&n));
+        assert_eq!(BigUint::from_str_radix(&n.to_str_radix(7), 7).unwrap(), n);
+    }
+    assert_eq!(format!("{:>9545}", -BigNumber::from(power.clone())), format!(" -{}", digits));
+}
+
+#[test]
+fn barrett_newton_reciprocal_matches_division() {
+    use crate::BarrettContext;
+
+    let mut rng = TestRng(83);
+    for (limbs, top) in [(65, u32::MAX), (100, 1), (257, 12345)] {
+        let mut m: Vec<u32> = (0..limbs - 1).map(|_| rng.next() as u32).collect();
+        m.push(top);
+        let m = BigUint::from_limbs(m);
+        let ctx = BarrettContext::new(&m).unwrap();
+        let x = BigUint::from_limbs((0..2 * limbs).map(|_| rng.next() as u32).collect());
+        assert_eq!(ctx.reduce(&x), &x % &m);
+        assert_eq!(ctx.div_rem(&x), x.div_rem(&m));
+    }
+}
+
+#[test]
+fn long_inputs_parse_by_splitting() {
+    // Reference value from 1000 digit pieces, each short enough to be parsed
+    // one chunk at a time
+    let reference = |digits: &str, radix: u32| {
+        let piece = BigUint::from(radix).pow(1000);
+        let mut n = BigUint::zero();
+        let first = digits.len() % 1000;
+        if first > 0 {
+            n = BigUint::from_str_radix(&digits[..first], radix).unwrap();
+        }
+        for start in (first..digits.len()).step_by(1000) {
+            n = &(&n * &piece) + &BigUint::from_str_radix(&digits[start..start + 1000], radix).unwrap();
+        }
+        n
+    };
+
+    let mut rng = TestRng(84);
+    let decimal = rng.decimal(12345);
+    assert_eq!(BigUint::parse(&decimal).unwrap(), reference(&decimal, 10));
+    assert_eq!(BigUint::parse(&decimal).unwrap().to_string(), decimal);
+
+    let mut sparse = "1".to_string();
+    sparse.push_str(&"0".repeat(6000));
+    sparse.push('7');
+    assert_eq!(BigUint::parse(&sparse).unwrap(), reference(&sparse, 10));
+
+    let base36: String = (0..5000).map(|_| std::char::from_digit((rng.next() % 36) as u32, 36).unwrap()).collect();
+    assert_eq!(BigUint::from_str_radix(&base36, 36).unwrap(), reference(&base36, 36));
+    let binary: String = (0..9000).map(|_| char::from(b'0' + (rng.next() % 2) as u8)).collect();
+    assert_eq!(BigUint::from_str_radix(&binary, 2).unwrap(), reference(&binary, 2));
+
+    check(BigNumber::parse(&format!("-{}", decimal)).unwrap(), &format!("-{}", decimal));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips() {
+    let n = BigNumber::from_string("-123456789012345678901234567890");
+    let json = serde_json::to_string(&n).unwrap();
+    assert_eq!(json, "\"-123456789012345678901234567890\"");
+    assert_eq!(serde_json::from_str::<BigNumber>(&json).unwrap(), n);
+    assert_eq!(serde_json::from_str::<BigNumber>("-42").unwrap(), BigNumber::from(-42));
+    assert_eq!(serde_json::from_str::<BigUint>("\"18446744073709551616\"").unwrap(), BigUint::from(1u128 << 64));
+    assert!(serde_json::from_str::<BigUint>("\"-1\"").is_err());
+    assert!(serde_json::from_str::<BigNumber>("\"12x\"").is_err());
+
+    // Binary formats get the little endian bytes
+    let bytes = bincode::serialize(&n).unwrap();
+    assert_eq!(bytes[8..], n.to_signed_bytes_le()[..]);
+    assert_eq!(bincode::deserialize::<BigNumber>(&bytes).unwrap(), n);
+    for value in ["0", "-1", "255", "-128", "340282366920938463463374607431768211456"] {
+        let value = BigNumber::from_string(value);
+        let bytes = bincode::serialize(&value).unwrap();
+        assert_eq!(bincode::deserialize::<BigNumber>(&bytes).unwrap(), value);
+        let bytes = bincode::serialize(value.magnitude()).unwrap();
+        assert_eq!(&bincode::deserialize::<BigUint>(&bytes).unwrap(), value.magnitude());
+    }
+}
+
+#[test]
+fn binary_encoding_round_trips_and_validates() {
+    use crate::FromBinError;
+
+    assert_eq!(BigNumber::zero().to_bin(), [1, 0, 0, 0, 0, 0]);
+    assert_eq!(BigNumber::from(258).to_bin(), [1, 0, 1, 0, 0, 0, 2, 1, 0, 0]);
+    let mut rng = TestRng(86);
+    for len in [1, 9, 10, 50] {
+        for negative in [false, true] {
+            let digits = rng.decimal(len);
+            let n = BigNumber::from_string(&if negative { format!("-{}", digits) } else { digits });
+            let bytes = n.to_bin();
+            assert_eq!(bytes.len(), 6 + 4 * n.magnitude().limbs.len());
+            let decoded = BigNumber::from_bin(&bytes).unwrap();
+            assert_canonical(&decoded);
+            assert_eq!(decoded, n);
+        }
+    }
+
+    let valid = BigNumber::from(-5).to_bin();
+    assert_eq!(BigNumber::from_bin(&valid[..5]), Err(FromBinError::Truncated));
+    assert_eq!(BigNumber::from_bin(&valid[..9]), Err(FromBinError::Truncated));
+    assert_eq!(BigNumber::from_bin(&[valid.as_slice(), &[0]].concat()), Err(FromBinError::TrailingBytes));
+    assert_eq!(BigNumber::from_bin(&[2, 0, 0, 0, 0, 0]), Err(FromBinError::UnsupportedVersion(2)));
+    assert_eq!(BigNumber::from_bin(&[1, 2, 0, 0, 0, 0]), Err(FromBinError::InvalidSign(2)));
+    assert_eq!(BigNumber::from_bin(&[1, 1, 0, 0, 0, 0]), Err(FromBinError::NonCanonical));
+    assert_eq!(BigNumber::from_bin(&[1, 0, 1, 0, 0, 0, 0, 0, 0, 0]), Err(FromBinError::NonCanonical));
+    assert_eq!(BigNumber::from_bin(&[1, 0, 255, 255, 255, 255]), Err(FromBinError::Truncated));
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn rkyv_archives_read_in_place() {
+    use crate::ArchivedBigNumber;
+    use rkyv::rancor::Error;
+
+    let values: Vec<BigNumber> = ["0", "-1", "4294967296", "-123456789012345678901234567890"]
+        .iter()
+        .map(|s| BigNumber::from_string(s))
+        .collect();
+    let bytes = rkyv::to_bytes::<Error>(&values).unwrap();
+    let archived = rkyv::access::<rkyv::Archived<Vec<BigNumber>>, Error>(&bytes).unwrap();
+    for (archived, value) in archived.iter().zip(&values) {
+        assert!(*archived == *value);
+        assert_eq!(archived.sign(), value.sign());
+        assert_eq!(archived.magnitude().is_zero(), value.is_zero());
+        let decoded: BigNumber = rkyv::deserialize::<BigNumber, Error>(archived).unwrap();
+        assert_canonical(&decoded);
+        assert_eq!(decoded, *value);
+    }
+
+    let bytes = rkyv::to_bytes::<Error>(&BigNumber::from(7)).unwrap();
+    let archived = rkyv::access::<ArchivedBigNumber, Error>(&bytes).unwrap();
+    assert!(*archived != BigNumber::from(-7));
+    assert!(*archived.magnitude() == BigUint::from(7u32));
+}
+
+#[cfg(feature = "num-traits")]
+#[test]
+fn num_traits_generic_code() {
+    use num_traits::{CheckedDiv, CheckedSub, Num, One, Pow, Signed, Zero};
+
+    // Written against the traits only
+    fn sum_of_powers<T: Num + Clone + Pow<u32, Output = T>>(values: &[T], exponent: u32) -> T {
+        values.iter().cloned().fold(T::zero(), |acc, value| acc + value.pow(exponent))
+    }
+
+    let values: Vec<BigNumber> = (1..=10).map(BigNumber::from).collect();
+    check(sum_of_powers(&values, 20), "113394131858832552133");
+    let values: Vec<BigUint> = (1..=3u32).map(BigUint::from).collect();
+    assert_eq!(sum_of_powers(&values, 3), BigUint::from(36u32));
+
+    assert_eq!(<BigNumber as Num>::from_str_radix("-zz", 36).unwrap(), BigNumber::from(-1295));
+    assert!(<BigNumber as Zero>::is_zero(&BigNumber::zero()));
+    assert!(<BigUint as One>::one() == BigUint::one());
+
+    let n = BigNumber::from(-7);
+    check(Signed::abs(&n), "7");
+    check(n.signum(), "-1");
+    check(BigNumber::zero().signum(), "0");
+    check(n.abs_sub(&BigNumber::from(-9)), "2");
+    check(n.abs_sub(&BigNumber::from(3)), "0");
+    assert!(!Signed::is_positive(&BigNumber::zero()));
+    assert!(!Signed::is_negative(&BigNumber::zero()));
+    assert!(Signed::is_negative(&n));
+
+    assert_eq!(CheckedDiv::checked_div(&n, &BigNumber::zero()), None);
+    check(CheckedDiv::checked_div(&n, &BigNumber::from(2)).unwrap(), "-3");
+    assert_eq!(CheckedSub::checked_sub(&BigUint::one(), &BigUint::from(2u32)), None);
+    assert_eq!(CheckedDiv::checked_div(&BigUint::one(), &BigUint::zero()), None);
+}
+
+#[cfg(feature = "num-integer")]
+#[test]
+fn num_integer_generic_code() {
+    use num_integer::Integer;
+
+    // Written against the trait only
+    fn floor_pairs<T: Integer + Clone>(a: T, b: T) -> (T, T, T, T) {
+        let (q, r) = a.div_mod_floor(&b);
+        (q, r, a.gcd(&b), a.lcm(&b))
     }
 
-    fn add(a :&str, b :&str, c :&str) {
-        let mut number1 = BigNumber::from_string(&a);
-        let mut number2 = BigNumber::from_string(&b);
-        let number3 = BigNumber::from_string(&c);
+    let (q, r, g, l) = floor_pairs(BigNumber::from(-84), BigNumber::from(36));
+    check(q, "-3");
+    check(r, "24");
+    check(g, "12");
+    check(l, "252");
+    let (q, r, g, l) = floor_pairs(BigUint::from(84u32), BigUint::from(36u32));
+    assert_eq!((q, r, g, l), (BigUint::from(2u32), BigUint::from(12u32), BigUint::from(12u32), BigUint::from(252u32)));
 
-        number1.print();
-        number2.print();
-        number1.add(&mut number2);
-        number1.print();
-        number3.print();
+    let n = BigNumber::from(-7);
+    check(Integer::div_ceil(&n, &BigNumber::from(2)), "-3");
+    check(Integer::div_rem(&n, &BigNumber::from(2)).1, "-1");
+    assert!(Integer::is_odd(&n) && !Integer::is_even(&n));
+    assert!(Integer::is_even(&BigNumber::zero()));
+    assert!(Integer::is_multiple_of(&BigNumber::from(-21), &n));
+    assert!(Integer::is_multiple_of(&BigNumber::zero(), &BigNumber::zero()));
+    assert!(!Integer::is_multiple_of(&n, &BigNumber::zero()));
+    assert!(!Integer::is_multiple_of(&BigUint::one(), &BigUint::zero()));
 
-        assert_eq!(number1.digits, number3.digits);
+    let (a, b) = (BigNumber::from(240), BigNumber::from(-46));
+    let egcd = Integer::extended_gcd(&a, &b);
+    check(egcd.gcd.clone(), "2");
+    assert_eq!(&(&a * &egcd.x) + &(&b * &egcd.y), egcd.gcd);
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn num_bigint_conversions() {
+    for value in ["0", "-1", "4294967295", "-4294967296", "-123456789012345678901234567890"] {
+        let n = BigNumber::from_string(value);
+        let theirs = num_bigint::BigInt::from(&n);
+        assert_eq!(theirs.to_string(), value);
+        let back = BigNumber::from(theirs);
+        assert_canonical(&back);
+        assert_eq!(back, n);
+
+        let magnitude = num_bigint::BigUint::from(n.magnitude().clone());
+        assert_eq!(magnitude.to_string(), value.trim_start_matches('-'));
+        assert_eq!(&BigUint::from(magnitude), n.magnitude());
     }
+    check(BigNumber::from(-num_bigint::BigInt::from(0)), "0");
+}
 
-    #[test]
-    fn test_is_prime_2() {
-        let mut num = BigNumber::from_string("2");
-        let is_prime = num.is_prime();
-        assert_eq!(is_prime, true);
+#[cfg(feature = "proptest")]
+mod proptest_properties {
+    use proptest::prelude::*;
+
+    use super::assert_canonical;
+    use crate::{BigNumber, BigUint};
+
+    proptest! {
+        #[test]
+        fn generated_values_are_canonical_and_round_trip(n in any::<BigNumber>(), m in any::<BigUint>()) {
+            assert_canonical(&n);
+            prop_assert_eq!(BigNumber::parse(&n.to_string()).unwrap(), n);
+            prop_assert!(m.limbs.last() != Some(&0) || m.is_zero());
+        }
     }
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck_values_and_shrinking() {
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
 
-    #[test]
-    fn test_is_prime_11() {
-        let mut num = BigNumber::from_string("11");
-        let is_prime = num.is_prime();
-        assert_eq!(is_prime, true);
+    fn subtraction_undoes_addition(a: BigNumber, b: BigNumber) -> bool {
+        &(&a + &b) - &b == a
     }
+    QuickCheck::new().tests(200).quickcheck(subtraction_undoes_addition as fn(BigNumber, BigNumber) -> bool);
+
+    let mut g = Gen::new(10);
+    let values: Vec<BigNumber> = (0..200).map(|_| BigNumber::arbitrary(&mut g)).collect();
+    assert!(values.iter().any(BigNumber::is_zero));
+    assert!(values.iter().any(BigNumber::is_negative));
+    assert!(values.iter().any(|n| n.magnitude().limbs.len() > 4));
+
+    let shrunk: Vec<BigNumber> = BigNumber::from(-12).shrink().collect();
+    let expected: Vec<BigNumber> = [12, 0, -6, -3, -1, -11].into_iter().map(BigNumber::from).collect();
+    assert_eq!(shrunk, expected);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_numbers_of_a_given_size() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
-    #[test]
-    fn test_from_string_positive() {
-        let input = "123";
-        let expected_digits = vec![3, 2, 1];
-        let expected_sign = Sign::Positive;
-        let num = BigNumber::from_string(input);
+    let mut rng = StdRng::seed_from_u64(92);
+    for bits in [1, 31, 32, 33, 64, 100, 1000] {
+        let mut low_bit_set = false;
+        for _ in 0..20 {
+            let n = BigNumber::random_bits(&mut rng, bits);
+            assert_canonical(&n);
+            assert_eq!(n.bits(), bits);
+            low_bit_set |= n.bit(0);
+        }
+        assert!(low_bit_set || bits == 1);
+    }
+    check(BigNumber::random_bits(&mut rng, 0), "0");
 
-        assert_eq!(num.digits, expected_digits);
-        assert_eq!(num.sign, expected_sign);
+    // Every leading digit turns up
+    let mut leading = [0; 10];
+    for _ in 0..500 {
+        let n = BigNumber::random_digits(&mut rng, 25);
+        let digits = n.to_string();
+        assert_eq!(digits.len(), 25);
+        leading[(digits.as_bytes()[0] - b'0') as usize] += 1;
     }
+    assert_eq!(leading[0], 0);
+    assert!(leading[1..].iter().all(|&count| count > 20), "{:?}", leading);
+    let one_digit = BigNumber::random_digits(&mut rng, 1);
+    assert!(one_digit >= BigNumber::one() && one_digit < BigNumber::from(10));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn uniform_sampling_below_a_bound() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(93);
 
-    #[test]
-    fn test_from_string_negative() {
-        let input = "-123";
-        let expected_digits = vec![3, 2, 1];
-        let expected_sign = Sign::Negative;
-        let num = BigNumber::from_string(input);
+    // A bound just past a power of two is the worst case for rejection, and
+    // every value should still turn up about equally often
+    let bound = BigNumber::from(5);
+    let mut counts = [0; 5];
+    for _ in 0..5000 {
+        let n = BigNumber::random_below(&mut rng, &bound);
+        counts[u32::try_from(&n).unwrap() as usize] += 1;
+    }
+    assert!(counts.iter().all(|&count| (900..1100).contains(&count)), "{:?}", counts);
 
-        assert_eq!(num.digits, expected_digits);
-        assert_eq!(num.sign, expected_sign);
+    let bound = BigNumber::from_string("340282366920938463463374607431768211457");
+    for _ in 0..50 {
+        let n = BigNumber::random_below(&mut rng, &bound);
+        assert_canonical(&n);
+        assert!(!n.is_negative() && n < bound);
     }
 
-    #[test]
-    fn subtraction_1() {
-        subtraction("11", "13", "-2");
+    let low = BigNumber::from_string("-18446744073709551616");
+    let high = BigNumber::from_string("-18446744073709551613");
+    let mut seen = [false; 3];
+    for _ in 0..100 {
+        let n = BigNumber::random_range(&mut rng, low.clone()..high.clone());
+        assert!(n >= low && n < high);
+        seen[u32::try_from(&(&n - &low)).unwrap() as usize] = true;
     }
+    assert_eq!(seen, [true; 3]);
+    check(BigNumber::random_range(&mut rng, BigNumber::from(7)..BigNumber::from(8)), "7");
+}
+
+#[cfg(feature = "rand")]
+#[test]
+#[should_panic(expected = "random_below needs a positive bound")]
+fn random_below_rejects_zero_bound() {
+    BigNumber::random_below(&mut rand::thread_rng(), &BigNumber::zero());
+}
 
-    #[test]
-    fn subtraction_2() {
-        subtraction("1000000000", "1000000000", "0");
+#[cfg(feature = "rand")]
+#[test]
+fn random_primes_have_the_requested_size() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(94);
+
+    let two_bits: Vec<String> =
+        (0..32).map(|_| BigNumber::random_prime(&mut rng, 2).to_string()).collect();
+    assert!(two_bits.iter().all(|p| p == "2" || p == "3"));
+    assert!(two_bits.iter().any(|p| p == "2") && two_bits.iter().any(|p| p == "3"));
+    for bits in [3, 16, 33, 64, 100, 256] {
+        for _ in 0..4 {
+            let p = BigNumber::random_prime(&mut rng, bits);
+            assert_canonical(&p);
+            assert_eq!(p.bits(), bits);
+            assert!(p.is_prime(), "{}", p);
+        }
     }
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn seeded_generators_reproduce_results() {
+    use rand::rngs::StdRng;
+    use rand::{RngCore, SeedableRng};
+
+    let run = |rng: &mut dyn RngCore| {
+        let n = BigNumber::from_string("618970023975480274948393073146934777");
+        (
+            BigNumber::random_bits(rng, 200),
+            BigNumber::random_range(rng, BigNumber::from(-1000)..BigNumber::from(1000)),
+            BigNumber::random_prime(rng, 96),
+            n.factor_rho_with(rng).unwrap(),
+            n.is_probable_prime(10, rng),
+        )
+    };
+
+    let first = run(&mut StdRng::seed_from_u64(95));
+    let second = run(&mut StdRng::seed_from_u64(95));
+    assert_eq!(first, second);
+
+    let (_, _, prime, factor, probable) = first;
+    assert!(prime.is_prime());
+    assert!(factor > BigNumber::one());
+    assert!(BigNumber::from_string("618970023975480274948393073146934777").is_divisible_by(&factor));
+    assert!(!probable);
+
+    let mut rng = StdRng::seed_from_u64(95);
+    assert_eq!(BigNumber::from_string("1000000007").factor_rho_with(&mut rng), None);
+    check(BigNumber::from_string("1024").factor_rho_with(&mut rng).unwrap(), "2");
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_leaves_a_canonical_zero() {
+    use zeroize::Zeroize;
+
+    let mut n = BigNumber::from_string("-123456789012345678901234567890123456789");
+    let capacity = n.magnitude.limbs.capacity();
+    n.zeroize();
+    assert_canonical(&n);
+    assert!(n.is_zero());
+    // The buffer is cleared in place rather than replaced
+    assert_eq!(n.magnitude.limbs.capacity(), capacity);
+
+    let mut m = BigUint::from(u128::MAX);
+    m.zeroize();
+    assert!(m.is_zero());
+    m.add_small(7);
+    assert_eq!(m.to_string(), "7");
+}
+
+#[test]
+fn modular_inverses() {
+    let n = |s: &str| BigNumber::from_string(s);
 
-    #[test]
-    fn subtraction_3() {
-        subtraction("9999999999999999", "9999999999999999", "0");
+    // 2^127 - 1 is prime, so every non-multiple has an inverse
+    let m = n("170141183460469231731687303715884105727");
+    for a in ["2", "-3", "123456789012345678901234567890", "170141183460469231731687303715884105726"] {
+        let a = n(a);
+        let inverse = a.mod_inverse(&m).unwrap();
+        assert!(!inverse.is_negative() && inverse < m);
+        check((&a * &inverse).rem_euclid(&m), "1");
     }
 
-    #[test]
-    fn subtraction_4() {
-        subtraction("99999999999999999999999999999999", "99999999999999999999999999999998", "1");
+    check(n("3").mod_inverse(&n("-7")).unwrap(), "5");
+    check(n("5").mod_inverse(&n("1")).unwrap(), "0");
+    assert_eq!(n("6").mod_inverse(&n("9")), None);
+    assert_eq!(n("0").mod_inverse(&n("9")), None);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn rsa_round_trips() {
+    use crate::rsa::{PrivateKey, PublicKey};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let n = |s: &str| BigNumber::from_string(s);
+
+    // The classic worked example with p = 61 and q = 53
+    let public = PublicKey::new(n("3233"), n("17"));
+    let key = PrivateKey::from_parts(public.clone(), n("2753"), n("61"), n("53"));
+    check(public.encrypt(&n("65")), "2790");
+    check(key.decrypt(&n("2790")), "65");
+    assert!(public.verify(&n("65"), &key.sign(&n("65"))));
+    assert!(!public.verify(&n("66"), &key.sign(&n("65"))));
+
+    let mut rng = StdRng::seed_from_u64(98);
+    let key = PrivateKey::generate(&mut rng, 256);
+    let public = key.public_key();
+    assert_eq!(public.modulus().bits(), 256);
+    let (p, q) = key.primes();
+    assert!(p.is_prime() && q.is_prime());
+    for _ in 0..10 {
+        let message = BigNumber::random_below(&mut rng, public.modulus());
+        assert_eq!(key.decrypt(&public.encrypt(&message)), message);
+        assert!(public.verify(&message, &key.sign(&message)));
     }
+    assert!(!format!("{:?}", key).contains(&p.to_string()));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn diffie_hellman_agreement() {
+    use crate::dh::{Group, PrivateKey};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(99);
+
+    let modp = Group::modp_2048();
+    assert_eq!(modp.prime().bits(), 2048);
+    assert!(modp.is_valid());
+
+    let group = Group::generate(&mut rng, 128);
+    assert_eq!(group.prime().bits(), 128);
+    assert!(group.is_valid());
 
-    #[test]
-    fn subtraction_5() {
-        subtraction("0", "0", "0");
+    for group in [modp, group] {
+        let alice = PrivateKey::generate(&mut rng, &group);
+        let bob = PrivateKey::generate(&mut rng, &group);
+        let secret = alice.shared_secret(bob.public_key()).unwrap();
+        assert_eq!(bob.shared_secret(alice.public_key()), Some(secret));
     }
 
-    #[test]
-    fn add_1() {
-        add("11", "13", "24");
+    // Squares mod 23 form the subgroup of order 11, and 5 is not one of them
+    let small = Group::new(BigNumber::from(23u32), BigNumber::from(4u32));
+    assert!(small.is_valid());
+    let key = PrivateKey::generate(&mut rng, &small);
+    for rejected in [0, 1, 5, 22, 23] {
+        assert_eq!(key.shared_secret(&BigNumber::from(rejected)), None, "{}", rejected);
     }
+    assert!(key.shared_secret(&BigNumber::from(2u32)).is_some());
+    assert!(!Group::new(BigNumber::from(23u32), BigNumber::from(5u32)).is_valid());
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi_round_trip() {
+    use crate::ffi::*;
+    use std::ffi::{CStr, CString};
+    use std::ptr;
+
+    unsafe {
+        let parse = |s: &str, radix| {
+            let input = CString::new(s).unwrap();
+            let mut out = ptr::null_mut();
+            let status = bignumber_parse(input.as_ptr(), radix, &mut out);
+            (status, out)
+        };
+        let format = |n, radix| {
+            let mut out = ptr::null_mut();
+            assert_eq!(bignumber_to_string(n, radix, &mut out), BIGNUMBER_OK);
+            let s = CStr::from_ptr(out).to_str().unwrap().to_string();
+            bignumber_string_free(out);
+            s
+        };
+
+        let (status, a) = parse("-123456789012345678901234567890", 10);
+        assert_eq!(status, BIGNUMBER_OK);
+        let (status, b) = parse("ffffffffffffffff", 16);
+        assert_eq!(status, BIGNUMBER_OK);
+
+        let mut out = ptr::null_mut();
+        assert_eq!(bignumber_mul(a, b, &mut out), BIGNUMBER_OK);
+        assert_eq!(format(out, 10), "-2277375791072698140124934049010216029110176642350");
+        bignumber_free(out);
+
+        assert_eq!(bignumber_div(a, b, &mut out), BIGNUMBER_OK);
+        assert_eq!(format(out, 10), "-6692605942");
+        bignumber_free(out);
+        assert_eq!(bignumber_rem(a, b, &mut out), BIGNUMBER_OK);
+        assert_eq!(format(out, 16), "-c373e0efdd281ac8");
+        bignumber_free(out);
+
+        let mut ordering = 2;
+        assert_eq!(bignumber_cmp(a, b, &mut ordering), BIGNUMBER_OK);
+        assert_eq!(ordering, -1);
+
+        let zero = bignumber_from_i64(0);
+        out = ptr::null_mut();
+        assert_eq!(bignumber_div(a, zero, &mut out), BIGNUMBER_DIVISION_BY_ZERO);
+        assert!(out.is_null());
+        assert_eq!(bignumber_add(a, ptr::null(), &mut out), BIGNUMBER_NULL_POINTER);
+        assert_eq!(parse("12z", 10).0, BIGNUMBER_PARSE_ERROR);
+        assert_eq!(parse("12", 37).0, BIGNUMBER_INVALID_RADIX);
 
-    #[test]
-    fn add_2() {
-        add("1000000000", "1000000000", "2000000000");
+        let copy = bignumber_clone(a);
+        assert_eq!(bignumber_sub(a, copy, &mut out), BIGNUMBER_OK);
+        assert_eq!(format(out, 10), "0");
+
+        for n in [a, b, zero, copy, out, ptr::null_mut()] {
+            bignumber_free(n);
+        }
     }
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn python_int_conversions() {
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "bignumber").unwrap();
+        crate::python::bignumber(&module).unwrap();
+        let globals = PyDict::new(py);
+        globals.set_item("bignumber", &module).unwrap();
+
+        let values = ["0", "-1", "255", "-256", "340282366920938463463374607431768211457", "-18446744073709551616"];
+        for s in values {
+            let n = BigNumber::from_string(s);
+            let int = (&n).into_pyobject(py).unwrap();
+            assert_eq!(int.str().unwrap().to_str().unwrap(), s);
+            assert_eq!(int.extract::<BigNumber>().unwrap(), n);
+        }
+
+        let eval = |code: &str| {
+            py.eval(&std::ffi::CString::new(code).unwrap(), Some(&globals), None)
+                .unwrap()
+        };
+        let n: BigNumber = eval("bignumber.BigNumber(2) ** 67 - 1").extract().unwrap();
+        check(n, "147573952589676412927");
+        check(eval("divmod(bignumber.BigNumber(-7), 2)[1]").extract().unwrap(), "1");
+        check(eval("pow(bignumber.BigNumber(3), 200, -1000)").extract().unwrap(), "-999");
+        let factors: Vec<(BigNumber, u32)> = eval("bignumber.factorize(2 ** 67 - 1)").extract().unwrap();
+        assert_eq!(factors.len(), 2);
+        assert!(eval("bignumber.BigNumber(5) == 5 and hash(bignumber.BigNumber(5)) == hash(5)").is_truthy().unwrap());
+        assert!(eval("bignumber.is_prime(2 ** 127 - 1)").is_truthy().unwrap());
+    });
+}
+
+#[test]
+fn expressions_parse_and_evaluate() {
+    use crate::expr::{EvalError, Expr, ParseExprError};
 
-    #[test]
-    fn add_3() {
-        add("5", "-5", "0");
+    let eval = |input: &str| Expr::parse(input).unwrap().eval();
+    check(eval("2^3^2").unwrap(), "512");
+    check(eval("-2^2").unwrap(), "-4");
+    check(eval("(3! + 2^100) % 97").unwrap(), "22");
+    check(eval("25! / 2^64").unwrap(), "840864");
+    check(eval("0xff * 1_000_000").unwrap(), "255000000");
+    check(eval("1 - 2 - 3").unwrap(), "-4");
+
+    let expr: Expr = "(x^3 - 7) % 1000000007".parse().unwrap();
+    let x = BigNumber::from_string("12345678901234567890");
+    check(expr.eval_with(|name| (name == "x").then(|| x.clone())).unwrap(), "753508588");
+    assert_eq!(expr.eval(), Err(EvalError::UnknownVariable("x".to_string())));
+
+    assert_eq!(eval("2^-1"), Err(EvalError::NegativeExponent));
+    assert_eq!(eval("(0 - 1)!"), Err(EvalError::NegativeFactorial));
+    assert_eq!(Expr::parse(""), Err(ParseExprError::UnexpectedEnd));
+    assert_eq!(Expr::parse("2 * (1 + 3"), Err(ParseExprError::UnclosedParenthesis { position: 4 }));
+    assert_eq!(Expr::parse("1)"), Err(ParseExprError::UnexpectedToken { position: 1 }));
+    assert_eq!(
+        Expr::parse("1 # 2"),
+        Err(ParseExprError::UnexpectedCharacter { position: 2, character: '#' })
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_decimal_matches_parse() {
+    use crate::ParseBigNumberError;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::io::{ErrorKind, Read};
+
+    // Hands out the input a few bytes at a time, as a pipe might
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            let len = buffer.len().min(self.0.len()).min(1000);
+            buffer[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
     }
 
-    #[test]
-    fn add_4() {
-        add("99999999999999999999999999999999", "-99999999999999999999999999999998", "1");
+    let mut rng = StdRng::seed_from_u64(108);
+    for len in [1, 9, 32767, 32768, 32769, 65536, 3 * 32768 + 5, 250_000] {
+        let digits: String = (0..len).map(|_| char::from(b'0' + rng.gen_range(0..10))).collect();
+        let expected = BigNumber::parse(&format!("-{}", digits)).unwrap();
+
+        // Break the digits into lines, as in a wrapped file
+        let mut text = String::from(" \n-");
+        for (i, c) in digits.chars().enumerate() {
+            if i % 77 == 76 {
+                text.push('\n');
+            }
+            text.push(c);
+        }
+        text.push_str("\r\n");
+        let n = BigNumber::read_decimal(Trickle(text.as_bytes())).unwrap();
+        assert_eq!(n, expected, "{} digits", len);
+        assert_canonical(&n);
     }
 
-    #[test]
-    fn add_5() {
-        add("0", "0", "0");
+    check(BigNumber::read_decimal("+00042".as_bytes()).unwrap(), "42");
+    let error = |input: &str| {
+        let err = BigNumber::read_decimal(input.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        err.into_inner().unwrap().downcast::<ParseBigNumberError>().map(|err| *err).unwrap()
+    };
+    assert_eq!(error(" \n "), ParseBigNumberError::Empty);
+    assert_eq!(error(" - "), ParseBigNumberError::BareSign);
+    assert_eq!(error("12 3-4"), ParseBigNumberError::InvalidDigit { position: 4, character: '-' });
+}
+
+#[test]
+fn factorize_prime_powers() {
+    let p = BigNumber::from_string("1099511627791");
+    let q = BigNumber::from_string("1000000007");
+
+    let cube = BigNumber::from_string("1329227995839317534787207543490874671");
+    assert_eq!(cube.factorize(), vec![(p.clone(), 3)]);
+    check(cube.euler_phi(), "1329227995838108608967559928967334990");
+    assert_eq!(cube.moebius(), 0);
+
+    let n = BigNumber::from_string("1329228014448509541669824826638339484445415025052858879");
+    assert_eq!(n.factorize(), vec![(q, 2), (p, 3)]);
+}
+
+#[test]
+fn deeply_nested_expressions_are_rejected() {
+    use crate::expr::{Expr, ParseExprError, MAX_DEPTH};
+
+    let nested = |depth: usize| format!("{}1{}", "(-".repeat(depth), ")".repeat(depth));
+    check(Expr::parse(&nested(MAX_DEPTH / 2 - 1)).unwrap().eval().unwrap(), "-1");
+    let chain = format!("1{}", " + 1".repeat(MAX_DEPTH - 1));
+    check(Expr::parse(&chain).unwrap().eval().unwrap(), &MAX_DEPTH.to_string());
+
+    let too_deep = |input: &str| matches!(Expr::parse(input), Err(ParseExprError::TooDeep { .. }));
+    assert!(too_deep(&nested(50_000)));
+    assert!(too_deep(&format!("{}1", "-".repeat(50_000))));
+    assert!(too_deep(&format!("{}1{}", "(".repeat(50_000), ")".repeat(50_000))));
+    assert!(too_deep(&format!("1{}", " + 1".repeat(50_000))));
+    assert!(too_deep(&format!("{}2", "2^".repeat(50_000))));
+    assert!(too_deep(&format!("3{}", "!".repeat(50_000))));
+}
+
+#[test]
+fn huge_powers_and_factorials_are_rejected() {
+    use crate::expr::{EvalError, Expr};
+
+    let eval = |input: &str| Expr::parse(input).unwrap().eval();
+    assert_eq!(eval("2^4294967295"), Err(EvalError::ExponentTooLarge));
+    assert_eq!(eval("10^(10^30)"), Err(EvalError::ExponentTooLarge));
+    assert_eq!(eval("100000000!"), Err(EvalError::FactorialTooLarge));
+    assert_eq!(eval("(10^30)!"), Err(EvalError::FactorialTooLarge));
+
+    // Bases whose powers stay small accept any exponent
+    check(eval("1^(10^30)").unwrap(), "1");
+    check(eval("(0 - 1)^(10^30 + 1)").unwrap(), "-1");
+    check(eval("0^(10^30)").unwrap(), "0");
+    check(eval("0^0").unwrap(), "1");
+    assert_eq!(eval("2^2097152").unwrap().bits(), 2097153);
+}
+
+#[test]
+fn pm1_bounds_are_capped() {
+    // 1000003 - 1 is smooth, while 2000303 - 1 is twice a prime above the table
+    let n = BigNumber::from_string("2000309000909");
+    check(n.factor_pm1(u64::MAX, u64::MAX).unwrap(), "1000003");
+}
+
+#[test]
+fn scientific_exponents_are_bounded() {
+    use crate::ParseBigNumberError;
+
+    let too_large = Err(ParseBigNumberError::ExponentTooLarge);
+    assert_eq!(BigNumber::parse_scientific("1e99999999999"), too_large);
+    assert_eq!(BigNumber::parse_scientific("1e1000001"), too_large);
+    assert_eq!(BigNumber::parse_scientific("1.5e1000002"), too_large);
+    let power = BigNumber::from(10).pow(1_000_000);
+    assert_eq!(BigNumber::parse_scientific("1e1000000").unwrap(), power);
+    assert_eq!(BigNumber::parse_scientific("1.5e1000001").unwrap(), &power * &BigNumber::from(15));
+    check(BigNumber::parse_scientific("0e99999999999").unwrap(), "0");
+
+    // Exponents near i64::MIN, where the scale overflows
+    let not_an_integer = Err(ParseBigNumberError::NotAnInteger);
+    check(BigNumber::parse_scientific("0.00e-9223372036854775807").unwrap(), "0");
+    check(BigNumber::parse_scientific("-0.0e-9223372036854775808").unwrap(), "0");
+    assert_eq!(BigNumber::parse_scientific("1.50e-9223372036854775807"), not_an_integer);
+    assert_eq!(BigNumber::parse_scientific("100e-9223372036854775808"), not_an_integer);
+}
+
+#[test]
+fn small_values_format_in_every_radix() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(82);
+    let mut values = vec![0, 1, u32::MAX as u128, u64::MAX as u128, u128::MAX];
+    values.extend((0..50).map(|_| rng.gen::<u128>() >> rng.gen_range(0..128)));
+    for value in values {
+        let n = BigUint::from(value);
+        assert_eq!(n.to_string(), value.to_string());
+        assert_eq!(n.to_str_radix(16), format!("{:x}", value));
+        assert_eq!(n.to_str_radix(2), format!("{:b}", value));
+        for radix in 2..=36 {
+            let digits = n.to_str_radix(radix);
+            assert_eq!(BigUint::from_str_radix(&digits, radix).unwrap(), n, "radix {}", radix);
+            assert!(value == 0 || !digits.starts_with('0'));
+        }
+
+        let negative = -BigNumber::from(n);
+        if value > 0 {
+            assert_eq!(format!("{:>+45}", negative), format!("{:>45}", format!("-{}", value)));
+            assert_eq!(format!("{:045}", negative), format!("-{:044}", value));
+        }
     }
+}
+
+#[test]
+fn calculator_assigns_and_reads_variables() {
+    use crate::cli::{CalcError, Calculator};
+    use crate::expr::EvalError;
+
+    let mut calculator = Calculator::new();
+    let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+    check(calculator.eval_line("x = 2^256 - 1").unwrap(), max);
+    check(calculator.variable("x").unwrap().clone(), max);
+    check(
+        calculator.eval_line("x + 1").unwrap(),
+        "115792089237316195423570985008687907853269984665640564039457584007913129639936",
+    );
+    check(calculator.eval_line("  _y2 =x % 1000").unwrap(), "935");
+    check(calculator.eval_line("_y2 * 2").unwrap(), "1870");
 
+    assert_eq!(calculator.eval_line("3 = 4"), Err(CalcError::InvalidName("3".to_string())));
+    assert_eq!(calculator.eval_line("x y = 4"), Err(CalcError::InvalidName("x y".to_string())));
+    assert_eq!(calculator.eval_line(" = 4"), Err(CalcError::InvalidName(String::new())));
+    assert_eq!(
+        calculator.eval_line("z + 1"),
+        Err(CalcError::Eval(EvalError::UnknownVariable("z".to_string())))
+    );
+    assert!(matches!(calculator.eval_line("a = b = 1"), Err(CalcError::Parse(_))));
+    assert_eq!(calculator.variable("a"), None);
+}
+
+#[test]
+fn commands_check_and_run_their_arguments() {
+    use crate::cli::{Command, CommandError, COMMANDS};
+
+    let run = |name: &str, args: &[&str]| Command::find(name).unwrap().run(args);
+    assert_eq!(run("factor", &["-12"]).unwrap(), ["-1 * 2^2 * 3"]);
+    assert_eq!(run("factor", &["1"]).unwrap(), ["1"]);
+    assert_eq!(run("factor", &["-1"]).unwrap(), ["-1"]);
+    assert_eq!(run("divrem", &["-7", "2"]).unwrap(), ["-3", "-1"]);
+    assert_eq!(run("modpow", &["4", "13", "497"]).unwrap(), ["445"]);
+    assert_eq!(run("gcd", &["-12", "0x12"]).unwrap(), ["6"]);
+
+    assert_eq!(run("add", &["1"]), Err(CommandError::Usage));
+    assert_eq!(run("sqrt", &["4", "9"]), Err(CommandError::Usage));
+    assert!(matches!(
+        run("mul", &["2", "x"]),
+        Err(CommandError::InvalidNumber { argument, .. }) if argument == "x"
+    ));
+    assert_eq!(run("divrem", &["1", "0"]), Err(CommandError::Failed("division by zero".to_string())));
+    assert_eq!(run("pow", &["2", "-1"]), Err(CommandError::Failed("negative exponent".to_string())));
+    assert_eq!(run("modpow", &["2", "3", "0"]), Err(CommandError::Failed("modulus is zero".to_string())));
+    assert!(Command::find("nope").is_none());
+    assert!(COMMANDS.iter().all(|command| Command::find(command.name).is_some()));
+}
+
+#[test]
+fn reductions_fold_lines_and_skip_blanks() {
+    use crate::cli::{ReduceError, Reduction};
 
+    let reduce = |flag: &str, lines: &[&str]| Reduction::find(flag).unwrap().reduce(lines);
+    check(reduce("--gcd", &["-12", "18", "-30"]).unwrap(), "6");
+    check(reduce("--gcd", &["-5"]).unwrap(), "5");
+    check(reduce("--max", &["", "-7", "  ", "-3", "", "-10", ""]).unwrap(), "-3");
+    check(reduce("--max", &["-7"]).unwrap(), "-7");
+    check(reduce("--sum", &[" 0xff ", "-1"]).unwrap(), "254");
+    check(reduce("--product", &["-3", "", "4"]).unwrap(), "-12");
 
+    check(reduce("--sum", &[]).unwrap(), "0");
+    check(reduce("--product", &["", ""]).unwrap(), "1");
+    assert_eq!(reduce("--max", &["", " "]), Err(ReduceError::Empty));
+    assert!(matches!(
+        reduce("--sum", &["1", "", "two"]),
+        Err(ReduceError::InvalidNumber { line: 3, input, .. }) if input == "two"
+    ));
+    assert!(Reduction::find("--mean").is_none());
+
+    // Any iterator of lines will do, owned or borrowed
+    let lines = (1..=10).map(|i| i.to_string());
+    check(Reduction::find("--product").unwrap().reduce(lines).unwrap(), "3628800");
+}
 
-    // Add more test functions for other methods and behaviors
+#[test]
+fn quadratic_sieve_does_not_run_out_of_polynomials() {
+    // Small enough that a single prime used to be the only choice for A
+    let n = BigNumber::from_string("2596593843228821");
+    let factor = n.factor_siqs().unwrap();
+    assert!(factor.to_string() == "41137097" || factor.to_string() == "63120493", "{}", factor);
 }
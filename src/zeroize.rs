@@ -0,0 +1,40 @@
+//! Clearing of secret values, behind the `zeroize` feature.
+//!
+//! A [`BigUint`] overwrites its whole limb buffer, spare capacity included,
+//! when it is dropped or zeroized, and so does the magnitude of a
+//! [`BigNumber`]. Arithmetic that grows a number may move it to a larger
+//! buffer and release the old one without clearing it, and temporaries inside
+//! an operation are only cleared as they are dropped, so this limits how long
+//! secrets linger rather than ruling out every copy.
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{BigNumber, BigUint, Sign};
+
+/// Leaves the number equal to zero.
+impl Zeroize for BigUint {
+    fn zeroize(&mut self) {
+        // Clears the limbs and then the spare capacity, keeping the buffer
+        self.limbs.zeroize();
+        self.limbs.push(0);
+    }
+}
+
+impl Drop for BigUint {
+    fn drop(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for BigUint {}
+
+/// Leaves the number equal to zero.
+impl Zeroize for BigNumber {
+    fn zeroize(&mut self) {
+        self.magnitude.zeroize();
+        self.sign = Sign::Positive;
+    }
+}
+
+// The magnitude clears itself on drop, and the sign carries no secret
+impl ZeroizeOnDrop for BigNumber {}